@@ -18,6 +18,8 @@
 //! Account balance migration.
 
 use crate::*;
+use audit::AccountComponent;
+use sp_runtime::Saturating;
 
 impl<T: Config> Pallet<T> {
 	#[allow(clippy::type_complexity)]
@@ -35,30 +37,25 @@ impl<T: Config> Pallet<T> {
 		let (mut count_good, mut count_bad) = (0, 0);
 
 		for account in accounts {
-			let res = with_transaction_opaque_err::<(), RcAccountFor<T>, _>(|| {
+			let res = with_transaction_opaque_err::<(), (RcAccountFor<T>, Error<T>), _>(|| {
 				match Self::do_receive_account(account.clone()) {
 					Ok(()) => TransactionOutcome::Commit(Ok(())),
-					Err(_) => TransactionOutcome::Rollback(Err(account)),
+					Err(e) => TransactionOutcome::Rollback(Err((account, e))),
 				}
 			})
 			.expect("Always returning Ok; qed");
 
-			if let Err(account) = res {
+			if let Err((account, e)) = res {
 				// unlikely to happen cause we dry run migration, but we keep it for completeness.
 				count_bad += 1;
-				let who = account.who.clone();
-				log::error!(target: LOG_TARGET, "Saving the failed account data: {:?}", who.to_ss58check());
-				RcAccounts::<T>::insert(&who, account);
+				log::error!(target: LOG_TARGET, "Quarantining the failed account data: {:?}", account.who.to_ss58check());
+				Self::quarantine(PalletEventName::Balances, &account, e);
 			} else {
 				count_good += 1;
 			}
 		}
 
-		Self::deposit_event(Event::<T>::BatchProcessed {
-			pallet: PalletEventName::Balances,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Balances, count_good, count_bad);
 		Ok(())
 	}
 
@@ -92,11 +89,33 @@ impl<T: Config> Pallet<T> {
 			},
 		};
 		debug_assert!(minted == total_balance);
+		Self::audit(&who, AccountComponent::Balance { total: minted });
+		let mut integrated = MigratedTotals { balance: minted, ..Default::default() };
 
+		let mut unnamed_reserve = account.unnamed_reserve;
 		for hold in account.holds {
-			if let Err(e) =
-				<T as pallet::Config>::Currency::hold(&hold.id.into(), &who, hold.amount)
-			{
+			let id = match T::HoldReasonRegistry::translate(&hold.id) {
+				Ok(id) => id,
+				Err(policy) => {
+					log::warn!(
+						target: LOG_TARGET,
+						"Unmapped hold reason {:?} of account {}, policy: {:?}",
+						hold.id,
+						who.to_ss58check(),
+						policy
+					);
+					match policy {
+						// the amount is minted as free balance already.
+						UnmappedReasonPolicy::Release => {},
+						// the account is withdrawn from the Relay Chain already, refusing it
+						// would lose the balance.
+						UnmappedReasonPolicy::ConvertToReserve | UnmappedReasonPolicy::Refuse =>
+							unnamed_reserve.saturating_accrue(hold.amount),
+					}
+					continue;
+				},
+			};
+			if let Err(e) = <T as pallet::Config>::Currency::hold(&id, &who, hold.amount) {
 				log::error!(
 					target: LOG_TARGET,
 					"Failed to hold into account {}: {:?}",
@@ -105,9 +124,39 @@ impl<T: Config> Pallet<T> {
 				);
 				return Err(Error::<T>::FailedToProcessAccount);
 			}
+			integrated.add_hold(&hold.id, hold.amount);
+			Self::audit(&who, AccountComponent::Hold { reason: hold.id, amount: hold.amount });
+		}
+
+		let mut frozen_to_reserve = Zero::zero();
+		let mut freezes = Vec::with_capacity(account.freezes.len());
+		for freeze in account.freezes {
+			match T::FreezeReasonRegistry::translate(&freeze.id) {
+				Ok(id) => {
+					let component =
+						AccountComponent::Freeze { reason: freeze.id, amount: freeze.amount };
+					Self::audit(&who, component);
+					freezes.push((id, freeze.amount));
+				},
+				Err(policy) => {
+					log::warn!(
+						target: LOG_TARGET,
+						"Unmapped freeze reason {:?} of account {}, policy: {:?}",
+						freeze.id,
+						who.to_ss58check(),
+						policy
+					);
+					match policy {
+						UnmappedReasonPolicy::Release => {},
+						UnmappedReasonPolicy::ConvertToReserve | UnmappedReasonPolicy::Refuse =>
+							frozen_to_reserve = freeze.amount.max(frozen_to_reserve),
+					}
+				},
+			}
 		}
+		unnamed_reserve.saturating_accrue(frozen_to_reserve);
 
-		if let Err(e) = <T as pallet::Config>::Currency::reserve(&who, account.unnamed_reserve) {
+		if let Err(e) = <T as pallet::Config>::Currency::reserve(&who, unnamed_reserve) {
 			log::error!(
 				target: LOG_TARGET,
 				"Failed to reserve into account {}: {:?}",
@@ -117,10 +166,8 @@ impl<T: Config> Pallet<T> {
 			return Err(Error::<T>::FailedToProcessAccount);
 		}
 
-		for freeze in account.freezes {
-			if let Err(e) =
-				<T as pallet::Config>::Currency::set_freeze(&freeze.id.into(), &who, freeze.amount)
-			{
+		for (id, amount) in freezes {
+			if let Err(e) = <T as pallet::Config>::Currency::set_freeze(&id, &who, amount) {
 				log::error!(
 					target: LOG_TARGET,
 					"Failed to freeze into account {}: {:?}",
@@ -138,6 +185,7 @@ impl<T: Config> Pallet<T> {
 				lock.amount,
 				types::map_lock_reason(lock.reasons),
 			);
+			Self::audit(&who, AccountComponent::Lock { id: lock.id, amount: lock.amount });
 		}
 
 		log::trace!(
@@ -167,6 +215,7 @@ impl<T: Config> Pallet<T> {
 			);
 		}
 
+		AhMigratedTotals::<T>::mutate(|totals| totals.merge(&integrated));
 		Ok(())
 	}
 
@@ -315,8 +364,8 @@ pub mod tests {
 		fn post_check(rc_pre_payload: Self::RcPrePayload, ah_pre_payload: Self::AhPrePayload) {
 			// Check that no failed accounts remain in storage
 			assert!(
-				RcAccounts::<T>::iter().next().is_none(),
-				"Failed accounts should not remain in storage after migration"
+				Quarantine::<T>::iter_prefix(PalletEventName::Balances).next().is_none(),
+				"Failed accounts should not remain in quarantine after migration"
 			);
 
 			let (account_summaries, _) = rc_pre_payload;
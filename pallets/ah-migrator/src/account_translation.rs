@@ -15,9 +15,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Config, Pallet};
-use frame_support::traits::Defensive;
-use pallet_rc_migrator::types::ToPolkadotSs58;
+use crate::{Config, ExtraAccountTranslations, ExtraAccountTranslationsCount, Pallet};
+use codec::Encode;
+use core::marker::PhantomData;
+use pallet_rc_migrator::{accounts, types::ToPolkadotSs58};
+use polkadot_parachain_primitives::primitives::{Id as ParaId, Sibling};
+use sp_runtime::{
+	traits::{AccountIdConversion, Convert},
+	AccountId32,
+};
 
 impl<T: Config> Pallet<T> {
 	/// Translate account from RC format to AH format.
@@ -39,17 +45,7 @@ impl<T: Config> Pallet<T> {
 
 	/// Translate the account if its a parachain sovereign account.
 	pub fn maybe_sovereign_translate(account: &T::AccountId) -> Option<T::AccountId> {
-		let new = crate::sovereign_account_translation::SOV_TRANSLATIONS
-			.binary_search_by_key(account, |((rc_acc, _), _)| rc_acc.clone())
-			.map(|i| {
-				crate::sovereign_account_translation::SOV_TRANSLATIONS
-					.get(i)
-					.map(|(_, (ah_acc, _))| ah_acc)
-					.defensive()
-			})
-			.ok()
-			.flatten()
-			.cloned()?;
+		let new = Self::sovereign_translation(account)?;
 
 		Self::deposit_event(crate::Event::AccountTranslatedParachainSovereign {
 			from: account.clone(),
@@ -61,23 +57,102 @@ impl<T: Config> Pallet<T> {
 
 	/// Translate the account if its derived from a parachain sovereign account.
 	pub fn maybe_derived_translate(account: &T::AccountId) -> Option<T::AccountId> {
-		let (new, idx) = crate::sovereign_account_translation::DERIVED_TRANSLATIONS
-			.binary_search_by_key(account, |((rc_acc, _), _, _)| rc_acc.clone())
-			.map(|i| {
-				crate::sovereign_account_translation::DERIVED_TRANSLATIONS
-					.get(i)
-					.map(|(_, idx, (ah_acc, _))| (ah_acc, idx))
-					.defensive()
-			})
-			.ok()
-			.flatten()?;
+		let (new, idx) = Self::derived_translation(account)?;
 
 		Self::deposit_event(crate::Event::AccountTranslatedParachainSovereignDerived {
 			from: account.clone(),
 			to: new.clone(),
-			derivation_index: *idx,
+			derivation_index: idx,
 		});
 
-		Some(new.clone())
+		Some(new)
+	}
+
+	/// The Asset Hub account of a parachain sovereign account, without emitting an event.
+	///
+	/// Looks up the [`SOV_TRANSLATIONS`](crate::sovereign_account_translation::SOV_TRANSLATIONS)
+	/// table, like the Relay Chain does, and then the [`ExtraAccountTranslations`].
+	pub fn sovereign_translation(account: &T::AccountId) -> Option<T::AccountId> {
+		accounts::sovereign_translation(account).or_else(|| match Self::extra_translation(account) {
+			Some((new, None)) => Some(new),
+			_ => None,
+		})
+	}
+
+	/// The Asset Hub account and derivation index of an account derived from a parachain
+	/// sovereign account, without emitting an event.
+	///
+	/// Looks up the
+	/// [`DERIVED_TRANSLATIONS`](crate::sovereign_account_translation::DERIVED_TRANSLATIONS) table,
+	/// like the Relay Chain does, and then the [`ExtraAccountTranslations`].
+	pub fn derived_translation(account: &T::AccountId) -> Option<(T::AccountId, u16)> {
+		accounts::derived_translation(account).or_else(|| match Self::extra_translation(account) {
+			Some((new, Some(idx))) => Some((new, idx)),
+			_ => None,
+		})
+	}
+
+	/// The translation of `account` in the [`ExtraAccountTranslations`].
+	///
+	/// Only reads the map if it is not empty, so that translating an account reads nothing but the
+	/// whitelisted [`ExtraAccountTranslationsCount`] unless translations were added.
+	fn extra_translation(account: &T::AccountId) -> Option<(T::AccountId, Option<u16>)> {
+		if ExtraAccountTranslationsCount::<T>::get() == 0 {
+			return None;
+		}
+		ExtraAccountTranslations::<T>::get(account)
+	}
+}
+
+/// Sovereign account of the parachain `para_id` on the Relay Chain.
+pub fn para_sovereign_account(para_id: u32) -> AccountId32 {
+	ParaId::from(para_id).into_account_truncating()
+}
+
+/// Sovereign account of the parachain `para_id` on a sibling parachain like the Asset Hub.
+pub fn sibling_sovereign_account(para_id: u32) -> AccountId32 {
+	Sibling::from(para_id).into_account_truncating()
+}
+
+/// Account derived from `who` with `index`, like `pallet_utility::Pallet::derivative_account_id`.
+pub fn derivative_account(who: &AccountId32, index: u16) -> AccountId32 {
+	let entropy = (b"modlpy/utilisuba", who, index).using_encoded(sp_io::hashing::blake2_256);
+	AccountId32::new(entropy)
+}
+
+/// The Relay Chain and Asset Hub account of the sovereign account of `para_id` or, with a
+/// `derivation_index`, of the account derived from it.
+pub fn sovereign_translation_of(
+	para_id: u32,
+	derivation_index: Option<u16>,
+) -> (AccountId32, AccountId32) {
+	let (rc, ah) = (para_sovereign_account(para_id), sibling_sovereign_account(para_id));
+	match derivation_index {
+		Some(index) => (derivative_account(&rc, index), derivative_account(&ah, index)),
+		None => (rc, ah),
+	}
+}
+
+/// Check that `accounts` are strictly sorted, as the binary search of a translation table needs.
+///
+/// Returns the first account that is not greater than its predecessor.
+pub fn ensure_strictly_sorted<'a>(
+	accounts: impl IntoIterator<Item = &'a AccountId32>,
+) -> Result<(), &'a AccountId32> {
+	let mut accounts = accounts.into_iter().peekable();
+	while let Some(account) = accounts.next() {
+		match accounts.peek() {
+			Some(next) if *next <= account => return Err(*next),
+			_ => (),
+		}
+	}
+	Ok(())
+}
+
+/// Translates accounts with [`Pallet::translate_account_rc_to_ah`].
+pub struct TranslateAccountRcToAh<T>(PhantomData<T>);
+impl<T: Config> Convert<T::AccountId, T::AccountId> for TranslateAccountRcToAh<T> {
+	fn convert(account: T::AccountId) -> T::AccountId {
+		Pallet::<T>::translate_account_rc_to_ah(account)
 	}
 }
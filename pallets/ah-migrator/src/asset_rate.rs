@@ -32,11 +32,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		log::info!(target: LOG_TARGET, "Processed {count} asset rates");
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::AssetRates,
-			count_good: count,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::AssetRates, count, 0);
 
 		Ok(())
 	}
@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit trail of the components that the migration integrated per account.
+//!
+//! If [`Config::AccountAudit`] is enabled, every integrated balance, hold, freeze, lock, vesting
+//! schedule, proxy and multisig deposit emits [`Event::AccountComponentMigrated`] for the Asset
+//! Hub account that it arrived at. The events of a block are committed to in [`AuditDigest`],
+//! which is reset at the start of the next block like the events, so that an indexer can check
+//! that it did not miss any.
+//!
+//! [`AuditTrail`] indexes the events off-chain and answers what happened to an account:
+//!
+//! ```ignore
+//! let mut trail = AuditTrail::<AhRuntime>::default();
+//! for (state, records) in blocks {
+//! 	state.execute_with(|| trail.index_block(records.into_iter().map(|record| record.event)))?;
+//! }
+//! println!("{:#?}", trail.account(&who));
+//! ```
+
+use crate::*;
+use pallet_rc_migrator::types::{PortableFreezeReason, PortableHoldReason};
+
+/// A component of an account that the migration integrated.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum AccountComponent<
+	Balance,
+	HoldReason = PortableHoldReason,
+	FreezeReason = PortableFreezeReason,
+> {
+	/// The free and reserved balance that was minted into the account.
+	Balance { total: Balance },
+	/// Balance put on hold.
+	Hold { reason: HoldReason, amount: Balance },
+	/// Balance frozen.
+	Freeze { reason: FreezeReason, amount: Balance },
+	/// Balance locked.
+	Lock { id: [u8; 8], amount: Balance },
+	/// Vesting schedules.
+	Vesting { schedules: u32, locked: Balance },
+	/// Proxies of the account as delegator, including the ones that already existed.
+	Proxies { delegates: u32, deposit: Balance },
+	/// Unreserved deposit of a multisig that the account created.
+	MultisigDeposit { deposit: Balance },
+}
+
+pub type AccountComponentOf<T> = AccountComponent<
+	<T as pallet_balances::Config>::Balance,
+	<T as Config>::PortableHoldReason,
+	<T as Config>::PortableFreezeReason,
+>;
+
+impl<T: Config> Pallet<T> {
+	/// Record that `component` of `who` was integrated, if the audit trail is enabled.
+	pub fn audit(who: &T::AccountId, component: AccountComponentOf<T>) {
+		if !T::AccountAudit::get() {
+			return;
+		}
+
+		Self::record_audit(who, component);
+	}
+
+	/// Record `component` of `who` in the [`AuditDigest`] of the block and deposit its event.
+	pub(crate) fn record_audit(who: &T::AccountId, component: AccountComponentOf<T>) {
+		AuditDigest::<T>::mutate(|digest| digest.record(&(who, &component)));
+		Self::deposit_event(Event::AccountComponentMigrated { who: who.clone(), component });
+	}
+
+	/// The weight of auditing `components` account components, zero if the audit trail is
+	/// disabled.
+	pub fn audit_weight(components: u32) -> Weight {
+		if !T::AccountAudit::get() || components == 0 {
+			return Weight::zero();
+		}
+
+		T::AhWeightInfo::audit_account_components(components)
+	}
+}
+
+/// The audit trail of a block does not match its [`AuditDigest`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditDigestMismatch<BlockNumber> {
+	/// The block of the events.
+	pub block: BlockNumber,
+	/// The digest over the indexed events.
+	pub indexed: MigrationDigest,
+	/// The digest stored on chain.
+	pub expected: MigrationDigest,
+}
+
+/// Off-chain index of the audit trail, by account.
+#[cfg(feature = "std")]
+pub struct AuditTrail<T: Config> {
+	/// The integrated components per account with the block they were integrated in.
+	pub accounts: std::collections::BTreeMap<
+		T::AccountId,
+		Vec<(BlockNumberFor<T>, AccountComponentOf<T>)>,
+	>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> Default for AuditTrail<T> {
+	fn default() -> Self {
+		Self { accounts: Default::default() }
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> AuditTrail<T>
+where
+	<T as Config>::RuntimeEvent: TryInto<Event<T>>,
+{
+	/// Index the audit events of a block from its `events`.
+	///
+	/// Must be called in the externalities of the Asset Hub at the end of the block, to compare
+	/// the events with its [`AuditDigest`]. The events are indexed even if they do not match.
+	pub fn index_block(
+		&mut self,
+		events: impl IntoIterator<Item = <T as frame_system::Config>::RuntimeEvent>,
+	) -> Result<(), AuditDigestMismatch<BlockNumberFor<T>>> {
+		let block = frame_system::Pallet::<T>::block_number();
+		let mut indexed = MigrationDigest::default();
+		for event in events {
+			let event: <T as Config>::RuntimeEvent = event.into();
+			let Ok(Event::<T>::AccountComponentMigrated { who, component }) = event.try_into()
+			else {
+				continue;
+			};
+			indexed.record(&(&who, &component));
+			self.accounts.entry(who).or_default().push((block, component));
+		}
+
+		let expected = AuditDigest::<T>::get();
+		if indexed != expected {
+			return Err(AuditDigestMismatch { block, indexed, expected });
+		}
+		Ok(())
+	}
+
+	/// The integrated components of `who`, in the order they were integrated.
+	pub fn account(&self, who: &T::AccountId) -> &[(BlockNumberFor<T>, AccountComponentOf<T>)] {
+		self.accounts.get(who).map(Vec::as_slice).unwrap_or_default()
+	}
+}
@@ -19,7 +19,7 @@ use crate::*;
 use frame_benchmarking::v2::*;
 use frame_support::traits::{
 	schedule::DispatchTime, tokens::IdAmount, Consideration, Currency, Footprint, Polling,
-	VoteTally,
+	ReservableCurrency, VoteTally,
 };
 use frame_system::RawOrigin;
 use pallet_asset_rate::AssetKindFactory;
@@ -36,7 +36,9 @@ use pallet_rc_migrator::{
 	claims::{RcClaimsMessage, RcClaimsMessageOf},
 	conviction_voting::RcConvictionVotingMessage,
 	crowdloan::RcCrowdloanMessage,
+	fast_unstake::{RcFastUnstakeRequest, RcFastUnstakeRequestOf},
 	indices::RcIndicesIndex,
+	para_reserves::{RcParaReservesMessage, RcParaReservesMessageOf},
 	preimage::{PortableRequestStatus, PortableRequestStatusInner, CHUNK_SIZE},
 	proxy::{RcProxy, RcProxyAnnouncement},
 	scheduler::RcSchedulerMessage,
@@ -49,7 +51,9 @@ use pallet_rc_migrator::{
 	treasury::{PortablePaymentState, PortableSpendStatus, PortableTreasuryMessage},
 	types::{BenchmarkingDefault, DefensiveTruncateInto},
 };
-use pallet_referenda::{Deposit, ReferendumInfo, ReferendumStatus, TallyOf, TracksInfo};
+use pallet_referenda::{
+	Deposit, ReferendumInfo, ReferendumInfoFor, ReferendumStatus, TallyOf, TracksInfo,
+};
 use polkadot_runtime_common::claims::EthereumAddress;
 use scheduler::RcScheduledOf;
 use sp_runtime::traits::Hash;
@@ -973,8 +977,14 @@ pub mod benchmarks {
 	#[benchmark]
 	fn finish_migration() {
 		AhMigrationStage::<T>::put(&MigrationStage::DataMigrationOngoing);
+		let data = MigrationFinishedData {
+			rc_balance_kept: 100,
+			rc_balance_migrated: 100,
+			rc_totals: Default::default(),
+		};
+
 		#[extrinsic_call]
-		_(RawOrigin::Root, Some(MigrationFinishedData { rc_balance_kept: 100 }), 100);
+		_(RawOrigin::Root, Some(data), 100);
 
 		assert_last_event::<T>(
 			Event::StageTransition {
@@ -1040,6 +1050,194 @@ pub mod benchmarks {
 		assert_last_event::<T>(Event::ManagerSet { old, new }.into());
 	}
 
+	#[benchmark]
+	fn record_received_items(n: Linear<1, 255>) {
+		// Items of 1 KiB, above the encoded size of most migrated items.
+		let items = vec![[0u8; 1024]; n as usize];
+		// Worst case: the batch opens the window of the pallet.
+		assert!(IngestingPallet::<T>::get().is_none());
+
+		#[block]
+		{
+			Pallet::<T>::record_received_items(MigratedPallet::Accounts, &items).unwrap();
+		}
+
+		assert_eq!(ReceivedMigrationDigests::<T>::get(MigratedPallet::Accounts).items, n);
+	}
+
+	#[benchmark]
+	fn receive_para_reserves_messages(n: Linear<1, 255>) {
+		// Auction reserves write the reserve and the schedule of a distinct para each.
+		let create_auction_reserve = |n: u32| -> RcParaReservesMessageOf<T> {
+			RcParaReservesMessage::AuctionReserve {
+				bidder: [n as u8; 32].into(),
+				para_id: (2000 + n).into(),
+				amount: n.into(),
+			}
+		};
+
+		let messages = (0..n).map(create_auction_reserve).collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, messages);
+
+		assert_last_event::<T>(
+			Event::BatchProcessed {
+				pallet: PalletEventName::ParaReserves,
+				count_good: n,
+				count_bad: 0,
+			}
+			.into(),
+		);
+	}
+
+	#[benchmark]
+	fn receive_fast_unstake_messages(n: Linear<1, 255>) {
+		let create_request = |n: u8| -> RcFastUnstakeRequestOf<T> {
+			let stash: AccountId32 = [n; 32].into();
+			let deposit = <pallet_balances::Pallet<T> as Currency<_>>::minimum_balance();
+			let _ = <pallet_balances::Pallet<T> as Currency<_>>::deposit_creating(
+				&stash,
+				deposit + deposit,
+			);
+			<pallet_balances::Pallet<T> as ReservableCurrency<_>>::reserve(&stash, deposit)
+				.unwrap();
+
+			RcFastUnstakeRequest { stash, deposit }
+		};
+
+		let messages = (0..n).map(|i| create_request(i.try_into().unwrap())).collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, messages);
+
+		assert_last_event::<T>(
+			Event::BatchProcessed {
+				pallet: PalletEventName::FastUnstake,
+				count_good: n,
+				count_bad: 0,
+			}
+			.into(),
+		);
+	}
+
+	#[benchmark]
+	fn preimage_gc_mark_referendum() {
+		let mut tracks = <T as pallet_referenda::Config>::Tracks::tracks();
+		let track_id = tracks.next().unwrap().id;
+		let deposit = Deposit { who: [1; 32].into(), amount: 1u32.into() };
+		// Large enough to be bounded as a lookup.
+		let call: <T as frame_system::Config>::RuntimeCall =
+			frame_system::Call::remark { remark: vec![1u8; 256] }.into();
+		let referendum = ReferendumInfo::Ongoing(ReferendumStatus {
+			track: track_id,
+			origin: Default::default(),
+			proposal: <T as pallet_referenda::Config>::Preimages::bound(call).unwrap(),
+			enactment: DispatchTime::At(1u32.into()),
+			submitted: 1u32.into(),
+			submission_deposit: deposit.clone(),
+			decision_deposit: Some(deposit),
+			deciding: None,
+			tally: TallyOf::<T, ()>::new(track_id),
+			in_queue: false,
+			alarm: None,
+		});
+		Pallet::<T>::do_receive_referendums(vec![(0, referendum)]).unwrap();
+		let Some(ReferendumInfo::Ongoing(status)) = ReferendumInfoFor::<T, ()>::get(0) else {
+			panic!("referendum is ongoing");
+		};
+		let hash = status.proposal.lookup_hash().unwrap();
+
+		#[block]
+		{
+			Pallet::<T>::mark_referendum_proposal(0);
+		}
+
+		assert!(PreimageGcMarks::<T>::contains_key(hash));
+	}
+
+	#[benchmark]
+	fn preimage_gc_mark_agenda(n: Linear<0, 50>) {
+		let create_scheduled = |n: u8| -> RcScheduledOf<T> {
+			// Large enough to be bounded as a lookup.
+			let call: <T as frame_system::Config>::RuntimeCall =
+				frame_system::Call::remark { remark: vec![n; 256] }.into();
+			RcScheduledOf::<T> {
+				maybe_id: Some([n; 32]),
+				priority: n,
+				call: <T as pallet_referenda::Config>::Preimages::bound(call).unwrap(),
+				maybe_periodic: None,
+				origin: Default::default(),
+			}
+		};
+
+		let block = 1u32.into();
+		let agenda = (0..n).map(|i| Some(create_scheduled(i.try_into().unwrap()))).collect();
+		Pallet::<T>::do_receive_scheduler_agenda_messages(vec![SchedulerAgendaMessage {
+			block,
+			agenda,
+		}])
+		.unwrap();
+
+		#[block]
+		{
+			Pallet::<T>::mark_agenda(block);
+		}
+
+		assert_eq!(PreimageGcMarks::<T>::iter_keys().count(), n as usize);
+	}
+
+	#[benchmark]
+	fn preimage_gc_sweep() {
+		// The worst case is a translated preimage that is requested by the migration and noted
+		// with a deposit, which is refunded after the request is released.
+		let depositor: AccountId32 = [1; 32].into();
+		let preimage = vec![1u8; 512];
+		<T as pallet_preimage::Config>::Consideration::ensure_successful(
+			&depositor,
+			Footprint::from_parts(1, preimage.len()),
+		);
+		pallet_preimage::Pallet::<T>::note_preimage(
+			RawOrigin::Signed(depositor.clone()).into(),
+			preimage.clone(),
+		)
+		.unwrap();
+		let hash = <T as frame_system::Config>::Hashing::hash(&preimage);
+		<T as Config>::Preimage::request(&hash);
+		Pallet::<T>::track_migrated_preimage(hash, preimage_gc::PreimageSource::Translation);
+
+		#[block]
+		{
+			Pallet::<T>::sweep_preimage(hash);
+		}
+
+		assert!(!MigratedPreimages::<T>::contains_key(hash));
+		assert_last_event::<T>(
+			Event::PreimageCollected { hash, depositor: Some(depositor) }.into(),
+		);
+	}
+
+	#[benchmark]
+	fn audit_account_components(n: Linear<1, 255>) {
+		// Components of distinct accounts, each one is hashed into the digest and deposits an
+		// event.
+		let components = (0..n)
+			.map(|i| {
+				let who: AccountId32 = [i as u8; 32].into();
+				(who, audit::AccountComponent::Lock { id: [i as u8; 8], amount: u128::MAX })
+			})
+			.collect::<Vec<_>>();
+
+		#[block]
+		{
+			for (who, component) in components {
+				Pallet::<T>::record_audit(&who, component);
+			}
+		}
+
+		assert_eq!(AuditDigest::<T>::get().items, n);
+	}
+
 	#[cfg(feature = "std")]
 	pub fn test_receive_multisigs<T>(n: u32)
 	where
@@ -1175,6 +1373,24 @@ pub mod benchmarks {
 		_receive_indices::<T>(n, true)
 	}
 
+	#[cfg(feature = "std")]
+	pub fn test_receive_para_reserves_messages<T>(n: u32)
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_receive_para_reserves_messages::<T>(n, true)
+	}
+
+	#[cfg(feature = "std")]
+	pub fn test_receive_fast_unstake_messages<T>(n: u32)
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_receive_fast_unstake_messages::<T>(n, true)
+	}
+
 	#[cfg(feature = "std")]
 	pub fn test_receive_conviction_voting_messages<T>(n: u32)
 	where
@@ -1345,4 +1561,40 @@ pub mod benchmarks {
 	{
 		_set_manager::<T>(true)
 	}
+
+	#[cfg(feature = "std")]
+	pub fn test_preimage_gc_mark_referendum<T>()
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_preimage_gc_mark_referendum::<T>(true)
+	}
+
+	#[cfg(feature = "std")]
+	pub fn test_preimage_gc_mark_agenda<T>(n: u32)
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_preimage_gc_mark_agenda::<T>(n, true)
+	}
+
+	#[cfg(feature = "std")]
+	pub fn test_preimage_gc_sweep<T>()
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_preimage_gc_sweep::<T>(true)
+	}
+
+	#[cfg(feature = "std")]
+	pub fn test_audit_account_components<T>(n: u32)
+	where
+		T: Config,
+		ConvictionVotingIndexOf<T>: From<u8>,
+	{
+		_audit_account_components::<T>(n, true)
+	}
 }
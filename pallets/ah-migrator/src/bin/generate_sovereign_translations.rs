@@ -0,0 +1,230 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generates the `sovereign_account_translation.rs` module of the RC migrator from a list of
+//! parachains.
+//!
+//! Every line of the input file is either a para id, for the translation of its sovereign account,
+//! or a para id and a derivation index separated by a comma, for the translation of the account
+//! derived from its sovereign account. Empty lines and lines starting with `#` are ignored.
+//!
+//! ```sh
+//! cargo run -p pallet-ah-migrator --bin generate-sovereign-translations -- paras.csv \
+//!     > pallets/rc-migrator/src/sovereign_account_translation.rs
+//! ```
+
+use pallet_ah_migrator::account_translation::{ensure_strictly_sorted, sovereign_translation_of};
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::AccountId32;
+use std::{collections::BTreeSet, fmt::Write};
+
+const HEADER: &str = "// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account translation maps for sovereign accounts and their derived accounts.
+
+#![allow(clippy::type_complexity)]
+
+use hex_literal::hex;
+use sp_runtime::AccountId32;
+
+// AUTOGENERATED BELOW
+";
+
+/// A translation of a sovereign or derived account.
+struct Translation {
+	para_id: u32,
+	derivation_index: Option<u16>,
+	rc: AccountId32,
+	ah: AccountId32,
+}
+
+fn main() {
+	let path = std::env::args().nth(1).unwrap_or_else(|| {
+		eprintln!("Usage: generate-sovereign-translations <paras.csv>");
+		std::process::exit(1);
+	});
+	let input = std::fs::read_to_string(&path)
+		.unwrap_or_else(|err| fail(&format!("Failed to read {path}: {err}")));
+
+	print!("{}", generate(&input).unwrap_or_else(|err| fail(&err)));
+}
+
+/// Generate the source file for the paras listed in `input`.
+fn generate(input: &str) -> Result<String, String> {
+	let mut seen = BTreeSet::new();
+	let (mut sovereign, mut derived) = (Vec::new(), Vec::new());
+	for (number, line) in input.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let (para_id, derivation_index) =
+			parse_line(line).ok_or_else(|| format!("Invalid line {}: {line}", number + 1))?;
+		if !seen.insert((para_id, derivation_index)) {
+			return Err(format!("Duplicate line {}: {line}", number + 1));
+		}
+
+		let (rc, ah) = sovereign_translation_of(para_id, derivation_index);
+		let translation = Translation { para_id, derivation_index, rc, ah };
+		match derivation_index {
+			None => sovereign.push(translation),
+			Some(_) => derived.push(translation),
+		}
+	}
+
+	let tables = [("SOV_TRANSLATIONS", &mut sovereign), ("DERIVED_TRANSLATIONS", &mut derived)];
+	for (name, table) in tables {
+		table.sort_by(|a, b| a.rc.cmp(&b.rc));
+		if let Err(account) = ensure_strictly_sorted(table.iter().map(|t| &t.rc)) {
+			return Err(format!("Duplicate RC account {account} in `{name}`"));
+		}
+	}
+
+	Ok(render(&sovereign, &derived))
+}
+
+/// Parse a `para_id[,derivation_index]` line.
+fn parse_line(line: &str) -> Option<(u32, Option<u16>)> {
+	let mut parts = line.split(',').map(str::trim);
+	let para_id = parts.next()?.parse().ok()?;
+	let derivation_index = match parts.next() {
+		Some(index) => Some(index.parse().ok()?),
+		None => None,
+	};
+	parts.next().is_none().then_some((para_id, derivation_index))
+}
+
+/// Render the source file with the sorted `sovereign` and `derived` translations.
+fn render(sovereign: &[Translation], derived: &[Translation]) -> String {
+	let mut out = String::from(HEADER);
+
+	out.push_str(
+		"/// List of RC para to AH sibl sovereign account translation sorted by RC account.\n",
+	);
+	out.push_str(
+		"pub const SOV_TRANSLATIONS: &[((AccountId32, &str), (AccountId32, &str))] = &[\n",
+	);
+	for t in sovereign {
+		writeln!(out, "\t// para {}", t.para_id).unwrap();
+		out.push_str("\t(\n");
+		render_account(&mut out, &t.rc);
+		render_account(&mut out, &t.ah);
+		out.push_str("\t),\n");
+	}
+	out.push_str("];\n\n");
+
+	out.push_str(
+		"/// List of RC para to AH sibl derived account translation sorted by RC account.\n",
+	);
+	out.push_str(concat!(
+		"pub const DERIVED_TRANSLATIONS: ",
+		"&[((AccountId32, &str), u16, (AccountId32, &str))] = &[\n",
+	));
+	for t in derived {
+		let index = t.derivation_index.expect("only derived translations; qed");
+		writeln!(out, "\t// para {} (derivation index {index})", t.para_id).unwrap();
+		out.push_str("\t(\n");
+		render_account(&mut out, &t.rc);
+		writeln!(out, "\t\t{index},").unwrap();
+		render_account(&mut out, &t.ah);
+		out.push_str("\t),\n");
+	}
+	out.push_str("];\n");
+
+	out
+}
+
+/// Render an `(AccountId32, &str)` tuple of the tables.
+fn render_account(out: &mut String, account: &AccountId32) {
+	let bytes: &[u8; 32] = account.as_ref();
+	out.push_str("\t\t(\n\t\t\tAccountId32::new(hex!(\n");
+	writeln!(out, "\t\t\t\t\"{}\"", hex::encode(bytes)).unwrap();
+	out.push_str("\t\t\t)),\n");
+	writeln!(out, "\t\t\t\"{}\",", account.to_ss58check()).unwrap();
+	out.push_str("\t\t),\n");
+}
+
+fn fail(message: &str) -> ! {
+	eprintln!("{message}");
+	std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const TABLES: &str = include_str!("../../../rc-migrator/src/sovereign_account_translation.rs");
+
+	/// The input of the generator for the paras in the comments of the committed tables.
+	fn committed_paras() -> String {
+		TABLES
+			.lines()
+			.filter_map(|line| line.trim().strip_prefix("// para "))
+			.map(|para| match para.split_once(" (derivation index ") {
+				Some((para_id, index)) => format!("{para_id},{}\n", index.trim_end_matches(')')),
+				None => format!("{para}\n"),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn committed_tables_are_generated() {
+		assert_eq!(generate(&committed_paras()).unwrap(), TABLES);
+	}
+
+	#[test]
+	fn order_of_the_input_does_not_matter() {
+		let paras = committed_paras();
+		let reversed: String = paras.lines().rev().map(|line| format!("{line}\n")).collect();
+		assert_eq!(generate(&reversed).unwrap(), TABLES);
+	}
+
+	#[test]
+	fn comments_and_empty_lines_are_ignored() {
+		let input = "# sovereign\n\n 2048 \n# derived\n2048, 1\n";
+		assert_eq!(generate(input), generate("2048\n2048,1"));
+	}
+
+	#[test]
+	fn lines_are_parsed() {
+		assert_eq!(parse_line("2048"), Some((2048, None)));
+		assert_eq!(parse_line("2048, 7"), Some((2048, Some(7))));
+		assert_eq!(parse_line("2048,7,1"), None);
+		assert_eq!(parse_line("2048,70000"), None);
+		assert_eq!(parse_line("para"), None);
+	}
+
+	#[test]
+	fn invalid_and_duplicate_lines_are_rejected() {
+		assert_eq!(generate("2048\nx").unwrap_err(), "Invalid line 2: x");
+		assert_eq!(generate("2048\n 2048").unwrap_err(), "Duplicate line 2: 2048");
+		assert!(generate("2048,1\n2048, 1").is_err());
+	}
+}
@@ -0,0 +1,81 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reports the storage changes of pallets between state snapshots of both chains taken before and
+//! after the migration.
+//!
+//! The snapshots are the ones written by `try-runtime create-snapshot`. Every pallet is given by
+//! its storage prefix, which must be the same on both chains. The JSON report is written to the
+//! given path and the summary to stdout.
+//!
+//! ```sh
+//! cargo run -p pallet-ah-migrator --features report --bin migration-report -- \
+//!     rc-pre.snap rc-post.snap ah-pre.snap ah-post.snap report.json Indices Multisig Proxy
+//! ```
+
+use pallet_ah_migrator::report::{pallet_storage_report, MigrationReport, Snapshots};
+use remote_externalities::{Builder, Mode, OfflineConfig, SnapshotConfig};
+use sp_io::TestExternalities;
+use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic, StateVersion};
+
+/// Block type of both chains, only used to decode the snapshots.
+type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
+const USAGE: &str = "Usage: migration-report <rc-pre.snap> <rc-post.snap> <ah-pre.snap> \
+	<ah-post.snap> <report.json> <pallet>...";
+
+#[tokio::main]
+async fn main() {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	if args.len() < 6 {
+		fail(USAGE);
+	}
+	let (paths, pallets) = args.split_at(5);
+
+	let mut snapshots = Snapshots {
+		rc_pre: load(&paths[0]).await,
+		rc_post: load(&paths[1]).await,
+		ah_pre: load(&paths[2]).await,
+		ah_post: load(&paths[3]).await,
+	};
+
+	let mut report = MigrationReport::default();
+	for pallet in pallets {
+		report.push(pallet_storage_report(pallet, &mut snapshots));
+	}
+
+	std::fs::write(&paths[4], report.to_json())
+		.unwrap_or_else(|err| fail(&format!("Failed to write {}: {err}", paths[4])));
+	println!("{report}");
+}
+
+/// Load the state snapshot at `path`.
+async fn load(path: &str) -> TestExternalities {
+	let state_snapshot = SnapshotConfig::new(path);
+	let ext = Builder::<Block>::default()
+		.mode(Mode::Offline(OfflineConfig { state_snapshot }))
+		.build()
+		.await
+		.unwrap_or_else(|err| fail(&format!("Failed to load {path}: {err:?}")));
+	// Both chains hash with blake2, only the hasher type of the externalities differs.
+	let (raw_storage, storage_root) = ext.inner_ext.into_raw_snapshot();
+	TestExternalities::from_raw_snapshot(raw_storage, storage_root, StateVersion::V1)
+}
+
+fn fail(message: &str) -> ! {
+	eprintln!("{message}");
+	std::process::exit(1);
+}
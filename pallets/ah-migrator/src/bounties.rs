@@ -91,11 +91,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Bounties,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Bounties, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good}/{count_bad} bounties messages");
 
 		Ok(())
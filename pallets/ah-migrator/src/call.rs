@@ -15,6 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::*;
+use codec::DecodeAll;
 use frame_support::traits::Bounded;
 
 pub type BoundedCallOf<T> =
@@ -45,15 +46,32 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::FailedToBoundCall
 		})?;
 
-		if ah_bounded_call.lookup_needed() {
-			// Noted preimages for referendums that did not pass will need to be manually removed
-			// later.
+		if let Some(hash) = ah_bounded_call.lookup_hash() {
+			// Noted preimages for referendums that did not pass are removed by the preimage
+			// garbage collection.
 			log::debug!(target: LOG_TARGET, "New preimage was noted for call");
+			Self::track_migrated_preimage(hash, preimage_gc::PreimageSource::Translation);
 		}
 
 		Ok(ah_bounded_call)
 	}
 
+	/// Translate the origin and call of `migrated` without storing anything.
+	pub fn translate_migrated_call(
+		migrated: &MigratedCall<BlockNumberFor<T>>,
+	) -> Result<(), CallTranslationError> {
+		let origin = T::RcPalletsOrigin::decode_all(&mut &migrated.origin[..])
+			.map_err(|_| CallTranslationError::UnsupportedOrigin)?;
+		T::RcToAhPalletsOrigin::try_convert(origin)
+			.map_err(|_| CallTranslationError::UnsupportedOrigin)?;
+
+		let encoded_call = migrated.call.as_ref().ok_or(CallTranslationError::PreimageMissing)?;
+		T::RcToAhCall::try_convert(&encoded_call[..])
+			.map_err(|_| CallTranslationError::UnsupportedCall)?;
+
+		Ok(())
+	}
+
 	pub fn fetch_preimage(bounded_call: &BoundedCallOf<T>) -> Result<Vec<u8>, Error<T>> {
 		match bounded_call {
 			Bounded::Inline(encoded) => Ok(encoded.clone().into_inner()),
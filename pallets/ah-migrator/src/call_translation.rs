@@ -0,0 +1,469 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Composable translation of Relay Chain calls into Asset Hub calls.
+//!
+//! [`RcToAhCallTranslator`] can be used as [`Config::RcToAhCall`]. It hands the encoded Relay Chain
+//! call to a tuple of [`RcCallRule`]s and the first rule that applies translates it:
+//!
+//! - [`RemapPallets`] and [`RemapCalls`] change the pallet and call index of calls whose arguments
+//!   are encoded the same way on both chains.
+//! - [`TypedCall`] decodes the calls of a pallet into a type of the runtime to rewrite their
+//!   arguments, e.g. the beneficiary of a treasury spend.
+//! - [`XcmCalls`] reanchors the locations and assets of `pallet_xcm` calls to the Asset Hub.
+//! - [`UtilityCalls`], [`ProxyCalls`], [`MultisigCalls`] and [`SchedulerCalls`] translate the calls
+//!   nested in their calls with the same rules. The proxied accounts of proxy calls are translated
+//!   like [`Pallet::translate_account_rc_to_ah`] does.
+//!
+//! The rules only depend on the pallet indices and the encoding of the calls, so the same rules can
+//! be used by every runtime that takes over pallets of another chain.
+
+use crate::{xcm_translation::reanchor_xcm, *};
+use codec::{Compact, DecodeAll};
+use frame_support::traits::schedule::{Period, Priority};
+use pallet_multisig::Timepoint;
+use sp_runtime::MultiAddress;
+
+/// Block number of the Relay Chain.
+type RcBlockNumber = u32;
+
+/// Arguments of `limited_reserve_transfer_assets`.
+type ReserveTransferArgs =
+	(VersionedLocation, VersionedLocation, VersionedAssets, u32, WeightLimit);
+
+/// Translates some calls of the Relay Chain into calls of the Asset Hub.
+pub trait RcCallRule<Call> {
+	/// Translate the encoded call at the start of `input`.
+	///
+	/// Returns `None` and leaves `input` untouched if the rule does not apply to the call.
+	/// Otherwise `input` is advanced past the call. Nested calls are translated with `Rules`.
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(16)]
+impl<Call> RcCallRule<Call> for Tuple {
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		for_tuples!( #(
+			if let Some(call) = Tuple::translate::<Rules>(input) {
+				return Some(call);
+			}
+		)* );
+		None
+	}
+}
+
+/// Translate the encoded Relay Chain call at the start of `input` with `Rules`.
+pub fn translate_rc_call<Call, Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Result<Call, ()> {
+	Rules::translate::<Rules>(input).unwrap_or_else(|| {
+		log::error!(
+			target: LOG_TARGET,
+			"No rule to translate RC call with index {:?}",
+			call_index(*input)
+		);
+		Err(())
+	})
+}
+
+/// Translates an encoded Relay Chain call into an Asset Hub call with `Rules`.
+pub struct RcToAhCallTranslator<Call, Rules>(PhantomData<(Call, Rules)>);
+impl<'a, Call, Rules: RcCallRule<Call>> TryConvert<&'a [u8], Call>
+	for RcToAhCallTranslator<Call, Rules>
+{
+	fn try_convert(encoded: &'a [u8]) -> Result<Call, &'a [u8]> {
+		let mut input = encoded;
+		let call = translate_rc_call::<Call, Rules>(&mut input).map_err(|()| encoded)?;
+		if !input.is_empty() {
+			log::error!(target: LOG_TARGET, "RC call has {} trailing bytes", input.len());
+			return Err(encoded);
+		}
+		Ok(call)
+	}
+}
+
+/// Changes the pallet index of the calls of the Relay Chain pallets in `Table`.
+///
+/// `Table` maps the Relay Chain index to the Asset Hub index of pallets whose calls are encoded the
+/// same way on both chains and do not contain calls.
+pub struct RemapPallets<Table>(PhantomData<Table>);
+impl<Call: Decode, Table: Get<Vec<(u8, u8)>>> RcCallRule<Call> for RemapPallets<Table> {
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (rc_pallet, call) = call_index(input)?;
+		let (_, ah_pallet) = Table::get().into_iter().find(|(pallet, _)| *pallet == rc_pallet)?;
+
+		*input = &input[2..];
+		Some(decode_with_index(ah_pallet, call, input))
+	}
+}
+
+/// Changes the pallet and call index of the Relay Chain calls in `Table`.
+///
+/// `Table` maps the Relay Chain `(pallet, call)` index to the Asset Hub one of calls that are
+/// encoded the same way on both chains and do not contain calls.
+pub struct RemapCalls<Table>(PhantomData<Table>);
+impl<Call: Decode, Table: Get<Vec<((u8, u8), (u8, u8))>>> RcCallRule<Call> for RemapCalls<Table> {
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let rc_index = call_index(input)?;
+		let (_, (ah_pallet, ah_call)) =
+			Table::get().into_iter().find(|(index, _)| *index == rc_index)?;
+
+		*input = &input[2..];
+		Some(decode_with_index(ah_pallet, ah_call, input))
+	}
+}
+
+/// Maps a decoded call of a Relay Chain pallet to an Asset Hub call.
+pub trait MapRcCall<RcCall, Call> {
+	fn map(call: RcCall) -> Result<Call, ()>;
+}
+
+/// Decodes the calls of the Relay Chain pallet `RcIndex` as `RcCall` and maps them with `Map`.
+///
+/// Does not apply to calls that `RcCall` can not decode, so that `RcCall` only needs to know the
+/// calls whose arguments are rewritten.
+pub struct TypedCall<RcIndex, RcCall, Map>(PhantomData<(RcIndex, RcCall, Map)>);
+impl<Call, RcIndex, RcCall, Map> RcCallRule<Call> for TypedCall<RcIndex, RcCall, Map>
+where
+	RcIndex: Get<u8>,
+	RcCall: Decode,
+	Map: MapRcCall<RcCall, Call>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, _) = call_index(input)?;
+		if pallet != RcIndex::get() {
+			return None;
+		}
+
+		let mut args = &input[1..];
+		let rc_call = RcCall::decode(&mut args).ok()?;
+		*input = args;
+		Some(Map::map(rc_call))
+	}
+}
+
+/// Translates the `send`, `execute` and `limited_reserve_transfer_assets` calls of the Relay Chain
+/// `pallet_xcm` with index `RcIndex` to the `pallet_xcm` with index `AhIndex`.
+///
+/// Destinations and assets are relative to the Relay Chain and are reanchored to the Asset Hub,
+/// whose location seen from the Relay Chain is `AhLocation`. `UniversalLocation` is the universal
+/// location of the Relay Chain.
+pub struct XcmCalls<RcIndex, AhIndex, AhLocation, UniversalLocation>(
+	PhantomData<(RcIndex, AhIndex, AhLocation, UniversalLocation)>,
+);
+impl<Call, RcIndex, AhIndex, AhLocation, UniversalLocation> RcCallRule<Call>
+	for XcmCalls<RcIndex, AhIndex, AhLocation, UniversalLocation>
+where
+	Call: Decode,
+	RcIndex: Get<u8>,
+	AhIndex: Get<u8>,
+	AhLocation: Get<Location>,
+	UniversalLocation: Get<InteriorLocation>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, call) = call_index(input)?;
+		if pallet != RcIndex::get() || !matches!(call, 0 | 3 | 8) {
+			return None;
+		}
+		*input = &input[2..];
+
+		let ah_location = AhLocation::get();
+		let universal_location = UniversalLocation::get();
+		let reanchor_location = |location: VersionedLocation| -> Result<VersionedLocation, ()> {
+			let location: Location = location.try_into().map_err(|err| {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to convert versioned location to the latest version: {err:?}",
+				);
+			})?;
+			let location =
+				location.reanchored(&ah_location, &universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor location: {err:?}");
+				})?;
+			Ok(location.into())
+		};
+
+		let args = match call {
+			// `send`
+			0 => decode_args::<(VersionedLocation, VersionedXcm<()>)>(input)
+				.and_then(|(dest, message)| Ok((reanchor_location(dest)?, message).encode())),
+			// `execute`
+			3 => decode_args::<(VersionedXcm<()>, Weight)>(input).and_then(
+				|(message, max_weight)| {
+					let xcm: Xcm<()> = message.try_into().map_err(|err| {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to convert versioned XCM to latest version: {err:?}",
+						);
+					})?;
+					let xcm = reanchor_xcm(xcm, &ah_location, &universal_location)?;
+					Ok((VersionedXcm::from(xcm), max_weight).encode())
+				},
+			),
+			// `limited_reserve_transfer_assets`
+			_ => decode_args::<ReserveTransferArgs>(input).and_then(
+				|(dest, beneficiary, assets, fee_asset_item, weight_limit)| {
+					let assets: xcm::latest::Assets = assets.try_into().map_err(|err| {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to convert versioned assets to the latest version: {err:?}",
+						);
+					})?;
+					let assets =
+						assets.reanchored(&ah_location, &universal_location).map_err(|err| {
+							log::error!(target: LOG_TARGET, "Failed to reanchor assets: {err:?}");
+						})?;
+					let assets = VersionedAssets::from(assets);
+					// The `beneficiary` is relative to the `dest`, it needs no reanchoring.
+					let dest = reanchor_location(dest)?;
+					Ok((dest, beneficiary, assets, fee_asset_item, weight_limit).encode())
+				},
+			),
+		};
+
+		Some(args.and_then(|args| decode_call(AhIndex::get(), call, &args)))
+	}
+}
+
+/// Translates the `batch`, `batch_all`, `dispatch_as`, `force_batch` and `with_weight` calls of the
+/// Relay Chain `pallet_utility` with index `RcIndex` to the `pallet_utility` with index `AhIndex`.
+///
+/// The origins of `dispatch_as` are converted with `Origins`.
+pub struct UtilityCalls<RcIndex, AhIndex, RcOrigin, Origin, Origins>(
+	PhantomData<(RcIndex, AhIndex, RcOrigin, Origin, Origins)>,
+);
+impl<Call, RcIndex, AhIndex, RcOrigin, Origin, Origins> RcCallRule<Call>
+	for UtilityCalls<RcIndex, AhIndex, RcOrigin, Origin, Origins>
+where
+	Call: Encode + Decode,
+	RcIndex: Get<u8>,
+	AhIndex: Get<u8>,
+	RcOrigin: Decode,
+	Origin: Encode,
+	Origins: TryConvert<RcOrigin, Origin>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, call) = call_index(input)?;
+		if pallet != RcIndex::get() {
+			return None;
+		}
+		*input = &input[2..];
+
+		let args = match call {
+			// `batch`, `batch_all` and `force_batch`
+			0 | 2 | 4 => translate_rc_calls::<Call, Rules>(input).map(|calls| calls.encode()),
+			// `dispatch_as`
+			3 => decode_args::<RcOrigin>(input).and_then(|origin| {
+				let origin = Origins::try_convert(origin).map_err(|_| {
+					log::error!(target: LOG_TARGET, "Failed to convert RC dispatch_as origin");
+				})?;
+				let call = translate_rc_call::<Call, Rules>(input)?;
+				Ok((origin, call).encode())
+			}),
+			// `with_weight`
+			5 => translate_rc_call::<Call, Rules>(input)
+				.and_then(|call| Ok((call, decode_args::<Weight>(input)?).encode())),
+			_ => {
+				log::error!(target: LOG_TARGET, "Unsupported RC utility call with index {call}");
+				Err(())
+			},
+		};
+
+		Some(args.and_then(|args| decode_call(AhIndex::get(), call, &args)))
+	}
+}
+
+/// Translates the `proxy` call of the Relay Chain `pallet_proxy` with index `RcIndex` to the
+/// `pallet_proxy` with index `AhIndex`.
+///
+/// The proxied account is translated with `Accounts` and the proxy type with `ProxyTypes`.
+pub struct ProxyCalls<RcIndex, AhIndex, RcProxyType, ProxyType, ProxyTypes, Accounts>(
+	PhantomData<(RcIndex, AhIndex, RcProxyType, ProxyType, ProxyTypes, Accounts)>,
+);
+impl<Call, RcIndex, AhIndex, RcProxyType, ProxyType, ProxyTypes, Accounts> RcCallRule<Call>
+	for ProxyCalls<RcIndex, AhIndex, RcProxyType, ProxyType, ProxyTypes, Accounts>
+where
+	Call: Encode + Decode,
+	RcIndex: Get<u8>,
+	AhIndex: Get<u8>,
+	RcProxyType: Decode,
+	ProxyType: Encode,
+	ProxyTypes: TryConvert<RcProxyType, ProxyType>,
+	Accounts: Convert<AccountId32, AccountId32>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, call) = call_index(input)?;
+		if pallet != RcIndex::get() {
+			return None;
+		}
+		*input = &input[2..];
+
+		let args = match call {
+			// `proxy`
+			0 => decode_args::<(MultiAddress<AccountId32, ()>, Option<RcProxyType>)>(input)
+				.and_then(|(real, proxy_type)| {
+					let real = match real {
+						MultiAddress::Id(who) => MultiAddress::Id(Accounts::convert(who)),
+						real => real,
+					};
+					let proxy_type =
+						proxy_type.map(ProxyTypes::try_convert).transpose().map_err(|_| {
+							log::error!(target: LOG_TARGET, "Failed to convert RC proxy type");
+						})?;
+					let call = translate_rc_call::<Call, Rules>(input)?;
+					Ok((real, proxy_type, call).encode())
+				}),
+			_ => {
+				log::error!(target: LOG_TARGET, "Unsupported RC proxy call with index {call}");
+				Err(())
+			},
+		};
+
+		Some(args.and_then(|args| decode_call(AhIndex::get(), call, &args)))
+	}
+}
+
+/// Translates the `as_multi_threshold_1` and `as_multi` calls of the Relay Chain `pallet_multisig`
+/// with index `RcIndex` to the `pallet_multisig` with index `AhIndex`.
+///
+/// The signatories are not translated, since the multisig account is derived from them and was
+/// migrated as it is. An `as_multi` with a timepoint approves an operation that was started on the
+/// Relay Chain with the hash of the untranslated call, it can not be translated.
+pub struct MultisigCalls<RcIndex, AhIndex>(PhantomData<(RcIndex, AhIndex)>);
+impl<Call, RcIndex, AhIndex> RcCallRule<Call> for MultisigCalls<RcIndex, AhIndex>
+where
+	Call: Encode + Decode,
+	RcIndex: Get<u8>,
+	AhIndex: Get<u8>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, call) = call_index(input)?;
+		if pallet != RcIndex::get() {
+			return None;
+		}
+		*input = &input[2..];
+
+		let args = match call {
+			// `as_multi_threshold_1`
+			0 => decode_args::<Vec<AccountId32>>(input).and_then(|signatories| {
+				let call = translate_rc_call::<Call, Rules>(input)?;
+				Ok((signatories, call).encode())
+			}),
+			// `as_multi`
+			1 => decode_args::<(u16, Vec<AccountId32>, Option<Timepoint<RcBlockNumber>>)>(input)
+				.and_then(|(threshold, signatories, timepoint)| {
+					if timepoint.is_some() {
+						log::error!(
+							target: LOG_TARGET,
+							"Can not translate RC as_multi of a started operation"
+						);
+						return Err(());
+					}
+					let call = translate_rc_call::<Call, Rules>(input)?;
+					let max_weight = decode_args::<Weight>(input)?;
+					Ok((threshold, signatories, timepoint, call, max_weight).encode())
+				}),
+			_ => {
+				log::error!(target: LOG_TARGET, "Unsupported RC multisig call with index {call}");
+				Err(())
+			},
+		};
+
+		Some(args.and_then(|args| decode_call(AhIndex::get(), call, &args)))
+	}
+}
+
+/// Translates the `schedule_after` call of the Relay Chain `pallet_scheduler` with index `RcIndex`
+/// to the `pallet_scheduler` with index `AhIndex`.
+pub struct SchedulerCalls<RcIndex, AhIndex>(PhantomData<(RcIndex, AhIndex)>);
+impl<Call, RcIndex, AhIndex> RcCallRule<Call> for SchedulerCalls<RcIndex, AhIndex>
+where
+	Call: Encode + Decode,
+	RcIndex: Get<u8>,
+	AhIndex: Get<u8>,
+{
+	fn translate<Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Option<Result<Call, ()>> {
+		let (pallet, call) = call_index(input)?;
+		if pallet != RcIndex::get() {
+			return None;
+		}
+		*input = &input[2..];
+
+		let args = match call {
+			// `schedule_after`
+			4 => decode_args::<(RcBlockNumber, Option<Period<RcBlockNumber>>, Priority)>(input)
+				.and_then(|(after, maybe_periodic, priority)| {
+					let call = translate_rc_call::<Call, Rules>(input)?;
+					Ok((after, maybe_periodic, priority, call).encode())
+				}),
+			_ => {
+				log::error!(target: LOG_TARGET, "Unsupported RC scheduler call with index {call}");
+				Err(())
+			},
+		};
+
+		Some(args.and_then(|args| decode_call(AhIndex::get(), call, &args)))
+	}
+}
+
+/// The pallet and call index of the encoded call at the start of `input`.
+fn call_index(input: &[u8]) -> Option<(u8, u8)> {
+	match input {
+		[pallet, call, ..] => Some((*pallet, *call)),
+		_ => None,
+	}
+}
+
+/// Decode the arguments at the start of `input`.
+fn decode_args<Args: Decode>(input: &mut &[u8]) -> Result<Args, ()> {
+	Args::decode(input).map_err(|err| {
+		log::error!(target: LOG_TARGET, "Failed to decode RC call arguments: {err:?}");
+	})
+}
+
+/// Translate the encoded vector of Relay Chain calls at the start of `input` with `Rules`.
+fn translate_rc_calls<Call, Rules: RcCallRule<Call>>(input: &mut &[u8]) -> Result<Vec<Call>, ()> {
+	let Compact(len) = decode_args::<Compact<u32>>(input)?;
+	(0..len).map(|_| translate_rc_call::<Call, Rules>(input)).collect()
+}
+
+/// Decode the call with `pallet` and `call` index from the encoded `args`.
+fn decode_call<Call: Decode>(pallet: u8, call: u8, args: &[u8]) -> Result<Call, ()> {
+	let encoded = [&[pallet, call][..], args].concat();
+	Call::decode_all(&mut &encoded[..]).map_err(|err| {
+		log::error!(
+			target: LOG_TARGET,
+			"Failed to decode AH call with index {:?}: {err:?}",
+			(pallet, call)
+		);
+	})
+}
+
+/// Decode the call with `pallet` and `call` index from the arguments at the start of `input`.
+///
+/// `input` is advanced past the arguments.
+fn decode_with_index<Call: Decode>(pallet: u8, call: u8, input: &mut &[u8]) -> Result<Call, ()> {
+	let encoded = [&[pallet, call][..], *input].concat();
+	let mut remaining = &encoded[..];
+	let decoded = Call::decode(&mut remaining).map_err(|err| {
+		log::error!(
+			target: LOG_TARGET,
+			"Failed to decode AH call with index {:?}: {err:?}",
+			(pallet, call)
+		);
+	})?;
+
+	*input = &input[input.len() - remaining.len()..];
+	Ok(decoded)
+}
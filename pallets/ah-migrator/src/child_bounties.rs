@@ -37,11 +37,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ChildBounties,
-			count_good: good as u32,
-			count_bad: bad as u32,
-		});
+		Self::note_batch_processed(PalletEventName::ChildBounties, good as u32, bad as u32);
 
 		Ok(())
 	}
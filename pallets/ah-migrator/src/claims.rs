@@ -28,19 +28,16 @@ impl<T: Config> Pallet<T> {
 		let (mut count_good, mut count_bad) = (0, 0);
 
 		for message in messages {
-			match Self::do_process_claims(message) {
+			match Self::do_process_claims(message.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating claims: {e:?}");
+					Self::quarantine(PalletEventName::Claims, &message, e);
 				},
 			}
 		}
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Claims,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Claims, count_good, count_bad);
 
 		Ok(())
 	}
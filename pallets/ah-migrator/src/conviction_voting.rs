@@ -39,11 +39,7 @@ impl<T: Config> Pallet<T> {
 			Self::do_receive_conviction_voting_message(message);
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ConvictionVoting,
-			count_good: count,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::ConvictionVoting, count, 0);
 
 		Ok(())
 	}
@@ -32,20 +32,17 @@ impl<T: Config> Pallet<T> {
 		log::info!(target: LOG_TARGET, "Received {} crowdloan messages", messages.len());
 
 		for message in messages {
-			match Self::do_process_crowdloan_message(message) {
+			match Self::do_process_crowdloan_message(message.clone()) {
 				Ok(()) => good += 1,
 				Err(e) => {
 					bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating crowdloan message: {e:?}");
+					Self::quarantine(PalletEventName::Crowdloan, &message, e);
 				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Crowdloan,
-			count_good: good,
-			count_bad: bad,
-		});
+		Self::note_batch_processed(PalletEventName::Crowdloan, good, bad);
 
 		Ok(())
 	}
@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of the data received from the Relay Chain against the digest committed to by the
+//! Relay Chain, see [`pallet_rc_migrator::digest`].
+
+use crate::*;
+use pallet_rc_migrator::{digest::MigrationDigest, sequence::MigratedPallet};
+
+impl<T: Config> Pallet<T> {
+	/// Record the items of a received batch into the digest of `pallet`.
+	///
+	/// Must be called with the items exactly as received, before they are integrated. Fails if the
+	/// batch arrives outside of the ingestion window of `pallet`, see [`crate::ingestion`].
+	///
+	/// Its weight, [`WeightInfo::record_received_items`], is part of the weight of every call that
+	/// receives a batch.
+	pub fn record_received_items<E: Encode>(
+		pallet: MigratedPallet,
+		items: &[E],
+	) -> Result<(), Error<T>> {
+		Self::ensure_ingestion_window(pallet, items.len() as u32)?;
+
+		let mut batch_digest = MigrationDigest::default();
+		batch_digest.record_all(items);
+		let received = ReceivedMigrationDigests::<T>::mutate(pallet, |digest| {
+			digest.merge(&batch_digest);
+			*digest
+		});
+
+		// Items that arrive after the digest, e.g. because their message was resent.
+		if ExpectedMigrationDigests::<T>::get(pallet) == Some(received) {
+			Self::deposit_event(Event::MigrationDigestVerified { pallet, items: received.items });
+			Self::resume_ingestion(pallet);
+		}
+		Ok(())
+	}
+
+	pub fn do_receive_migration_digest(pallet: MigratedPallet, expected: MigrationDigest) {
+		Self::close_ingestion_window(pallet);
+		ExpectedMigrationDigests::<T>::insert(pallet, expected);
+		let received = ReceivedMigrationDigests::<T>::get(pallet);
+
+		if received == expected {
+			log::info!(
+				target: LOG_TARGET,
+				"Migration digest of {pallet:?} verified over {} items",
+				received.items
+			);
+			Self::deposit_event(Event::MigrationDigestVerified { pallet, items: received.items });
+		} else {
+			log::error!(
+				target: LOG_TARGET,
+				"Migration digest mismatch for {pallet:?}: expected {expected:?}, received {received:?}"
+			);
+			Self::deposit_event(Event::MigrationDigestMismatch { pallet, expected, received });
+			if HaltOnDigestMismatch::<T>::get() {
+				DigestMismatchHalt::<T>::put(pallet);
+				Self::deposit_event(Event::IngestionHalted { pallet });
+			}
+		}
+	}
+
+	/// Lift the halt of the ingestion if the digest of `pallet` caused it.
+	fn resume_ingestion(pallet: MigratedPallet) {
+		if DigestMismatchHalt::<T>::get() == Some(pallet) {
+			DigestMismatchHalt::<T>::kill();
+			Self::deposit_event(Event::IngestionResumed { pallet });
+		}
+	}
+}
@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Refunds the deposits of the fast-unstake requests that were drained on the Relay Chain, see
+//! [`pallet_rc_migrator::fast_unstake`].
+
+use crate::*;
+use pallet_rc_migrator::fast_unstake::{FastUnstakeMigrator, RcFastUnstakeRequestOf};
+
+impl<T: Config> Pallet<T> {
+	pub fn do_receive_fast_unstake_messages(
+		messages: Vec<RcFastUnstakeRequestOf<T>>,
+	) -> Result<(), Error<T>> {
+		let len = messages.len() as u32;
+		Self::deposit_event(Event::BatchReceived {
+			pallet: PalletEventName::FastUnstake,
+			count: len,
+		});
+		log::info!(target: LOG_TARGET, "Integrating batch of {len} fast-unstake requests");
+
+		for message in messages {
+			Self::do_receive_fast_unstake_message(message);
+		}
+
+		Self::note_batch_processed(PalletEventName::FastUnstake, len, 0);
+		Ok(())
+	}
+
+	pub fn do_receive_fast_unstake_message(message: RcFastUnstakeRequestOf<T>) {
+		let translated_stash = Self::translate_account_rc_to_ah(message.stash);
+
+		let missing = <T as Config>::Currency::unreserve(&translated_stash, message.deposit);
+
+		if missing != Default::default() {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to unreserve fast-unstake deposit of {}: missing amount: {:?}",
+				translated_stash.to_ss58check(),
+				missing
+			);
+
+			Self::deposit_event(Event::FailedToUnreserveFastUnstakeDeposit {
+				expected_amount: message.deposit,
+				missing_amount: missing,
+				account: translated_stash,
+			});
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> crate::types::AhMigrationCheck for FastUnstakeMigrator<T> {
+	type RcPrePayload = Vec<RcFastUnstakeRequestOf<T>>;
+	type AhPrePayload = ();
+
+	fn pre_check(_: Self::RcPrePayload) -> Self::AhPrePayload {}
+
+	fn post_check(rc_pre_payload: Self::RcPrePayload, _: Self::AhPrePayload) {
+		for request in rc_pre_payload {
+			let translated_stash = Pallet::<T>::translate_account_rc_to_ah(request.stash.clone());
+
+			// Assert storage 'FastUnstake::Queue::ah_post::consistent'
+			assert!(
+				frame_system::Account::<T>::contains_key(&translated_stash),
+				"Fast-unstake stash {:?} -> {:?} from Relay Chain should be present on Asset Hub",
+				request.stash.to_ss58check(),
+				translated_stash.to_ss58check()
+			);
+		}
+	}
+}
@@ -28,11 +28,7 @@ impl<T: Config> Pallet<T> {
 			Self::do_receive_index(index);
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Indices,
-			count_good: len,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::Indices, len, 0);
 		Ok(())
 	}
 
@@ -0,0 +1,215 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-pallet ingestion windows of the data received from the Relay Chain.
+//!
+//! The Relay Chain migrates one pallet after the other and finishes each pallet by sending its
+//! [`MigrationDigest`](pallet_rc_migrator::digest::MigrationDigest). The Asset Hub mirrors this in
+//! [`PalletIngestions`]: the first batch of a pallet opens its window and moves it to
+//! [`PalletMigrationStage::Ongoing`], the digest closes it and moves it to
+//! [`PalletMigrationStage::Done`]. Only one window is open at a time, see [`IngestingPallet`].
+//!
+//! A batch is rejected if another pallet's window is open or if its pallet is done and its
+//! digest was already verified. Batches of a done pallet whose digest does not match yet are still
+//! accepted, since they may be resent messages that resolve the mismatch. A rejected batch fails
+//! its XCM `Transact` and stays pending on the Relay Chain. The stage of a pallet can be corrected
+//! with [`Pallet::force_set_pallet_stage`].
+//!
+//! With [`HaltOnDigestMismatch`] enabled, a digest mismatch halts the ingestion: only batches of
+//! the mismatched pallet are accepted until its digest matches or the halt is lifted with
+//! [`Pallet::set_halt_on_digest_mismatch`].
+
+use crate::*;
+use pallet_rc_migrator::sequence::MigratedPallet;
+use sp_runtime::Saturating;
+
+/// The ingestion stage of a single pallet on the Asset Hub.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum PalletMigrationStage {
+	/// No batch of the pallet was received yet.
+	#[default]
+	Pending,
+	/// The batches of the pallet are being received.
+	Ongoing,
+	/// The Relay Chain sent the digest of the pallet and no further batches are expected.
+	Done,
+}
+
+/// The ingestion state of a single pallet on the Asset Hub.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct PalletIngestion {
+	/// The ingestion stage of the pallet.
+	pub stage: PalletMigrationStage,
+	/// Number of received items.
+	pub received: u32,
+	/// Number of items that were integrated.
+	pub processed: u32,
+	/// Number of items that failed to integrate.
+	pub failed: u32,
+}
+
+impl PalletEventName {
+	/// The migrated pallet that the items of this event belong to.
+	pub fn migrated_pallet(&self) -> Option<MigratedPallet> {
+		use PalletEventName::*;
+
+		Some(match self {
+			AssetRates => MigratedPallet::AssetRate,
+			BagsList => MigratedPallet::BagsList,
+			Balances => MigratedPallet::Accounts,
+			Bounties => MigratedPallet::Bounties,
+			ChildBounties => MigratedPallet::ChildBounties,
+			Claims => MigratedPallet::Claims,
+			ConvictionVoting => MigratedPallet::ConvictionVoting,
+			Crowdloan => MigratedPallet::Crowdloan,
+			DelegatedStaking => MigratedPallet::DelegatedStaking,
+			FastUnstake => MigratedPallet::FastUnstake,
+			Indices => MigratedPallet::Indices,
+			Multisig => MigratedPallet::Multisig,
+			NomPools => MigratedPallet::NomPools,
+			ParaReserves => MigratedPallet::ParaReserves,
+			PreimageChunk | PreimageLegacyStatus | PreimageRequestStatus =>
+				MigratedPallet::Preimage,
+			ProxyAnnouncements | ProxyProxies => MigratedPallet::Proxy,
+			ReferendaMetadata | ReferendaReferendums | ReferendaValues =>
+				MigratedPallet::Referenda,
+			Scheduler | SchedulerAgenda => MigratedPallet::Scheduler,
+			Staking => MigratedPallet::Staking,
+			Treasury => MigratedPallet::Treasury,
+			Vesting => MigratedPallet::Vesting,
+			#[cfg(feature = "kusama-ahm")]
+			Recovery => MigratedPallet::Recovery,
+			#[cfg(feature = "kusama-ahm")]
+			Society => MigratedPallet::Society,
+			#[cfg(not(feature = "kusama-ahm"))]
+			Recovery | Society => return None,
+		})
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Accept a batch of `items` of `pallet` if it arrives within the pallet's window.
+	///
+	/// Opens the window of `pallet` if no window is open and the pallet is still pending.
+	pub fn ensure_ingestion_window(pallet: MigratedPallet, items: u32) -> Result<(), Error<T>> {
+		if DigestMismatchHalt::<T>::get().is_some_and(|halted| halted != pallet) {
+			log::error!(
+				target: LOG_TARGET,
+				"Rejecting batch of {pallet:?} while the ingestion is halted by a digest mismatch"
+			);
+			return Err(Error::<T>::IngestionHalted);
+		}
+
+		let mut ingestion = PalletIngestions::<T>::get(pallet);
+		let ingesting = IngestingPallet::<T>::get();
+		// Resent messages of a done pallet can still resolve a digest mismatch.
+		let mismatched = || {
+			ExpectedMigrationDigests::<T>::get(pallet)
+				.is_some_and(|expected| expected != ReceivedMigrationDigests::<T>::get(pallet))
+		};
+
+		match ingestion.stage {
+			PalletMigrationStage::Pending if ingesting.is_none() => {
+				Self::set_pallet_stage(pallet, &mut ingestion, PalletMigrationStage::Ongoing);
+			},
+			PalletMigrationStage::Ongoing if ingesting == Some(pallet) => (),
+			PalletMigrationStage::Done if mismatched() => (),
+			stage => {
+				log::error!(
+					target: LOG_TARGET,
+					"Rejecting batch of {pallet:?} in stage {stage:?} while ingesting {ingesting:?}"
+				);
+				return Err(Error::<T>::BatchOutOfOrder);
+			},
+		}
+
+		ingestion.received.saturating_accrue(items);
+		PalletIngestions::<T>::insert(pallet, ingestion);
+		Ok(())
+	}
+
+	/// Deposit [`Event::BatchProcessed`] and count the items of the batch in [`PalletIngestions`].
+	pub fn note_batch_processed(pallet: PalletEventName, count_good: u32, count_bad: u32) {
+		if let Some(migrated_pallet) = pallet.migrated_pallet() {
+			PalletIngestions::<T>::mutate(migrated_pallet, |ingestion| {
+				ingestion.processed.saturating_accrue(count_good);
+				ingestion.failed.saturating_accrue(count_bad);
+			});
+		}
+
+		Self::deposit_event(Event::BatchProcessed { pallet, count_good, count_bad });
+	}
+
+	/// Close the window of `pallet` since the Relay Chain finished its migration.
+	pub fn close_ingestion_window(pallet: MigratedPallet) {
+		let ingesting = IngestingPallet::<T>::get();
+		if ingesting.is_some_and(|ingesting| ingesting != pallet) {
+			log::error!(
+				target: LOG_TARGET,
+				"Received the digest of {pallet:?} while ingesting {ingesting:?}"
+			);
+		}
+
+		let mut ingestion = PalletIngestions::<T>::get(pallet);
+		Self::set_pallet_stage(pallet, &mut ingestion, PalletMigrationStage::Done);
+		PalletIngestions::<T>::insert(pallet, ingestion);
+	}
+
+	/// Move `pallet` to the `new` stage and open or close its window accordingly.
+	///
+	/// Does not write `ingestion` back to storage.
+	pub(crate) fn set_pallet_stage(
+		pallet: MigratedPallet,
+		ingestion: &mut PalletIngestion,
+		new: PalletMigrationStage,
+	) {
+		let old = ingestion.stage;
+		ingestion.stage = new;
+
+		if new == PalletMigrationStage::Ongoing {
+			IngestingPallet::<T>::put(pallet);
+		} else if IngestingPallet::<T>::get() == Some(pallet) {
+			IngestingPallet::<T>::kill();
+		}
+
+		Self::deposit_event(Event::PalletStageTransition { pallet, old, new });
+	}
+}
@@ -34,25 +34,38 @@
 pub mod account;
 pub mod account_translation;
 pub mod asset_rate;
+pub mod audit;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 pub mod bounties;
 pub mod call;
+pub mod call_translation;
 pub mod child_bounties;
 pub mod claims;
 pub mod conviction_voting;
 pub mod crowdloan;
+pub mod digest;
+pub mod fast_unstake;
 pub mod indices;
+pub mod ingestion;
+pub mod migrations;
 pub mod multisig;
+pub mod para_reserves;
 pub mod preimage;
+pub mod preimage_gc;
 pub mod proxy;
+pub mod quarantine;
+pub mod reconciliation;
 #[cfg(feature = "kusama-ahm")]
 pub mod recovery;
 pub mod referenda;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod scheduler;
 #[cfg(feature = "kusama-ahm")]
 pub mod society;
-pub mod sovereign_account_translation;
 pub mod staking;
 pub mod treasury;
 pub mod types;
@@ -62,11 +75,13 @@ pub mod xcm_translation;
 
 pub use pallet::*;
 pub use pallet_rc_migrator::{
+	call_report::{CallTranslation, CallTranslationError, MigratedCall},
 	types::{
 		BenchmarkingDefault, ExceptResponseFor, LeftIfFinished, LeftIfPending, LeftOrRight,
-		MaxOnIdleOrInner, QueuePriority as DmpQueuePriority, RouteInnerWithException,
+		MaxOnIdleOrInner, QueuePriority as DmpQueuePriority, ReasonRegistry,
+		RouteInnerWithException, UnmappedReasonPolicy,
 	},
-	weights_ah,
+	sovereign_account_translation, weights_ah,
 };
 pub use weights_ah::WeightInfo;
 
@@ -90,9 +105,12 @@ use pallet_rc_migrator::recovery::{PortableRecoveryMessage, MAX_FRIENDS};
 use pallet_rc_migrator::society::{PortableSocietyMessage, MAX_PAYOUTS};
 use pallet_rc_migrator::{
 	bounties::RcBountiesMessageOf, child_bounties::PortableChildBountiesMessage,
-	claims::RcClaimsMessageOf, crowdloan::RcCrowdloanMessageOf, referenda::ReferendaMessage,
+	claims::RcClaimsMessageOf, crowdloan::RcCrowdloanMessageOf,
+	fast_unstake::RcFastUnstakeRequestOf, para_reserves::RcParaReservesMessageOf,
+	referenda::ReferendaMessage,
 	scheduler::SchedulerAgendaMessage, staking::PortableStakingMessage,
-	treasury::PortableTreasuryMessage, types::MigrationStatus,
+	digest::MigrationDigest, sequence::MigratedPallet, treasury::PortableTreasuryMessage,
+	types::MigrationStatus,
 };
 use parachains_common::pay::VersionedLocatableAccount;
 
@@ -110,7 +128,7 @@ use pallet_rc_migrator::{
 		bags_list::PortableBagsListMessage, delegated_staking::PortableDelegatedStakingMessage,
 		nom_pools::*,
 	},
-	types::MigrationFinishedData,
+	types::{MigratedTotals, MigrationFinishedData},
 	vesting::RcVestingSchedule,
 };
 use pallet_referenda::TrackIdOf;
@@ -176,6 +194,8 @@ pub enum PalletEventName {
 	Treasury,
 	Vesting,
 	Society,
+	FastUnstake,
+	ParaReserves,
 }
 
 /// The migration stage on the Asset Hub.
@@ -289,16 +309,26 @@ pub mod pallet {
 		+ pallet_staking_async::Config<CurrencyBalance = u128>
 		+ pallet_xcm::Config
 	{
-		type RuntimeHoldReason: Parameter
-			+ VariantCount
-			+ MaxEncodedLen
-			+ From<<Self as Config>::PortableHoldReason>;
-		type RuntimeFreezeReason: Parameter
-			+ VariantCount
-			+ MaxEncodedLen
-			+ From<<Self as Config>::PortableFreezeReason>;
+		type RuntimeHoldReason: Parameter + VariantCount + MaxEncodedLen;
+		type RuntimeFreezeReason: Parameter + VariantCount + MaxEncodedLen;
 		type PortableHoldReason: Parameter + MaxEncodedLen + BenchmarkingDefault;
 		type PortableFreezeReason: Parameter + MaxEncodedLen + BenchmarkingDefault;
+		/// Translates the portable hold reasons of the received accounts.
+		///
+		/// Holds with an unmapped reason are handled according to the registry's
+		/// [`UnmappedReasonPolicy`].
+		type HoldReasonRegistry: ReasonRegistry<
+			<Self as Config>::PortableHoldReason,
+			Target = <Self as Config>::RuntimeHoldReason,
+		>;
+		/// Translates the portable freeze reasons of the received accounts.
+		///
+		/// Freezes with an unmapped reason are handled according to the registry's
+		/// [`UnmappedReasonPolicy`].
+		type FreezeReasonRegistry: ReasonRegistry<
+			<Self as Config>::PortableFreezeReason,
+			Target = <Self as Config>::RuntimeFreezeReason,
+		>;
 		/// The overarching event type.
 		#[allow(deprecated)]
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -431,14 +461,62 @@ pub mod pallet {
 		///
 		/// This configuration can be overridden by a storage item [`DmpQueuePriorityConfig`].
 		type DmpQueuePriorityPattern: Get<(BlockNumberFor<Self>, BlockNumberFor<Self>)>;
+
+		/// The largest tolerated difference between a total reported by the Relay Chain and the
+		/// corresponding total of the Asset Hub, see [`reconciliation`].
+		///
+		/// The migration stays in the `CoolOff` stage if it is exceeded. `None` never holds it.
+		type ReconciliationThreshold: Get<Option<Self::Balance>>;
+
+		/// Preimages that are used outside of referenda and scheduler agendas, e.g. by whitelisted
+		/// calls.
+		///
+		/// They are never removed by [`Pallet::collect_preimages`].
+		type PreimagesInUse: Contains<H256>;
+
+		/// Whether to record the components that the migration integrates per account.
+		///
+		/// The records are accounted for in the weights of the `receive_*` calls that integrate
+		/// account components. See [`audit`].
+		type AccountAudit: Get<bool>;
 	}
 
-	/// RC accounts that failed to migrate when were received on the Asset Hub.
+	/// Items received from the Relay Chain that failed to integrate, by pallet and hash of the
+	/// encoded item.
 	///
 	/// This is unlikely to happen, since we dry run the migration, but we keep it for completeness.
+	/// See [`quarantine`].
+	#[pallet::storage]
+	pub type Quarantine<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PalletEventName,
+		Identity,
+		H256,
+		quarantine::QuarantinedItemOf<T>,
+		OptionQuery,
+	>;
+
+	/// Number of items in the [`Quarantine`].
 	#[pallet::storage]
-	pub type RcAccounts<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, RcAccountFor<T>, OptionQuery>;
+	pub type QuarantineCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Translations of parachain sovereign accounts and their derived accounts that are not in the
+	/// tables of [`sovereign_account_translation`].
+	///
+	/// Maps the Relay Chain account to its Asset Hub account and its derivation index, if it is
+	/// derived. Added with [`Pallet::add_account_translation`].
+	#[pallet::storage]
+	pub type ExtraAccountTranslations<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::AccountId, Option<u16>), OptionQuery>;
+
+	/// Number of translations in the [`ExtraAccountTranslations`].
+	///
+	/// Lets the translation of accounts skip the [`ExtraAccountTranslations`] while it is empty.
+	/// Read once per block in `on_initialize` while the migration is ongoing.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type ExtraAccountTranslationsCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
 	/// The Asset Hub migration state.
 	#[pallet::storage]
@@ -481,6 +559,72 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MigrationEndBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
 
+	/// Digest of the items received from the Relay Chain per migrated pallet.
+	#[pallet::storage]
+	pub type ReceivedMigrationDigests<T: Config> =
+		StorageMap<_, Twox64Concat, MigratedPallet, MigrationDigest, ValueQuery>;
+
+	/// Digest of the items sent by the Relay Chain per migrated pallet.
+	///
+	/// Set once the Relay Chain finished the migration of the pallet.
+	#[pallet::storage]
+	pub type ExpectedMigrationDigests<T: Config> =
+		StorageMap<_, Twox64Concat, MigratedPallet, MigrationDigest, OptionQuery>;
+
+	/// Ingestion stage and item counts per migrated pallet, see [`ingestion`].
+	#[pallet::storage]
+	pub type PalletIngestions<T: Config> =
+		StorageMap<_, Twox64Concat, MigratedPallet, ingestion::PalletIngestion, ValueQuery>;
+
+	/// The pallet whose ingestion window is open, if any.
+	#[pallet::storage]
+	pub type IngestingPallet<T: Config> = StorageValue<_, MigratedPallet, OptionQuery>;
+
+	/// Whether to halt the ingestion of further pallets when a digest does not match.
+	///
+	/// Set with [`Pallet::set_halt_on_digest_mismatch`].
+	#[pallet::storage]
+	pub type HaltOnDigestMismatch<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The pallet whose digest mismatch halted the ingestion, see [`HaltOnDigestMismatch`].
+	///
+	/// While set, only batches of this pallet are accepted. Cleared once its digest matches.
+	#[pallet::storage]
+	pub type DigestMismatchHalt<T: Config> = StorageValue<_, MigratedPallet, OptionQuery>;
+
+	/// Holds and locked vesting amounts integrated on the Asset Hub, see [`reconciliation`].
+	#[pallet::storage]
+	pub type AhMigratedTotals<T: Config> = StorageValue<
+		_,
+		MigratedTotals<<T as Config>::PortableHoldReason, T::Balance>,
+		ValueQuery,
+	>;
+
+	/// The report of the balance reconciliation done when the migration finished.
+	#[pallet::storage]
+	pub type Reconciliation<T: Config> =
+		StorageValue<_, reconciliation::BalanceReconciliationOf<T>, OptionQuery>;
+
+	/// Preimages integrated by the migration that are not collected yet, see [`preimage_gc`].
+	#[pallet::storage]
+	pub type MigratedPreimages<T: Config> =
+		StorageMap<_, Identity, H256, preimage_gc::PreimageSource, OptionQuery>;
+
+	/// Migrated preimages that are used in the current garbage collection round.
+	#[pallet::storage]
+	pub type PreimageGcMarks<T: Config> = StorageMap<_, Identity, H256, (), OptionQuery>;
+
+	/// The state of the preimage garbage collection.
+	#[pallet::storage]
+	pub type PreimageGc<T: Config> = StorageValue<_, preimage_gc::PreimageGcStateOf<T>, ValueQuery>;
+
+	/// Digest of the [`Event::AccountComponentMigrated`] events of the current block, see
+	/// [`audit`].
+	///
+	/// Reset at the start of every block, like the events.
+	#[pallet::storage]
+	pub type AuditDigest<T: Config> = StorageValue<_, MigrationDigest, ValueQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Failed to unreserve deposit.
@@ -521,6 +665,28 @@ pub mod pallet {
 		BadXcmVersion,
 		/// The origin is invalid.
 		InvalidOrigin,
+		/// The item is not in the quarantine.
+		NotQuarantined,
+		/// Failed to decode the item of a quarantined pallet.
+		FailedToDecodeQuarantined,
+		/// Items of this pallet can not be integrated from the quarantine.
+		QuarantineNotSupported,
+		/// The quarantined item failed to integrate again.
+		FailedToIntegrateQuarantined,
+		/// The account already has a translation.
+		AccountTranslationExists,
+		/// The account has no translation in [`ExtraAccountTranslations`].
+		AccountTranslationNotFound,
+		/// A batch arrived outside of the ingestion window of its pallet.
+		BatchOutOfOrder,
+		/// The migration is not finished.
+		MigrationNotFinished,
+		/// There are no migrated preimages to collect.
+		NoPreimagesToCollect,
+		/// The ingestion is halted by a digest mismatch, see [`DigestMismatchHalt`].
+		IngestionHalted,
+		/// The migration is not ongoing.
+		MigrationNotOngoing,
 	}
 
 	#[pallet::event]
@@ -612,6 +778,25 @@ pub mod pallet {
 			message: Xcm<()>,
 			message_id: XcmHash,
 		},
+		/// The data received for a pallet matches the digest sent by the Relay Chain.
+		MigrationDigestVerified {
+			/// The migrated pallet.
+			pallet: MigratedPallet,
+			/// The number of received items.
+			items: u32,
+		},
+		/// The data received for a pallet does not match the digest sent by the Relay Chain.
+		///
+		/// Messages that are still in flight can resolve the mismatch, in which case
+		/// `MigrationDigestVerified` follows.
+		MigrationDigestMismatch {
+			/// The migrated pallet.
+			pallet: MigratedPallet,
+			/// The digest sent by the Relay Chain.
+			expected: MigrationDigest,
+			/// The digest of the received items.
+			received: MigrationDigest,
+		},
 		/// Failed to unreserve a multisig deposit.
 		FailedToUnreserveMultisigDeposit {
 			/// The expected amount of the deposit that was expected to be unreserved.
@@ -630,9 +815,111 @@ pub mod pallet {
 			/// The account that the deposit was unreserved from.
 			account: T::AccountId,
 		},
+		/// Failed to unreserve the deposit of a drained fast-unstake request.
+		FailedToUnreserveFastUnstakeDeposit {
+			/// The expected amount of the deposit that was expected to be unreserved.
+			expected_amount: BalanceOf<T>,
+			/// The missing amount of the deposit.
+			missing_amount: BalanceOf<T>,
+			/// The account that the deposit was unreserved from.
+			account: T::AccountId,
+		},
+		/// An item that failed to integrate was quarantined.
+		ItemQuarantined {
+			/// The pallet of the item.
+			pallet: PalletEventName,
+			/// The hash of the encoded item.
+			hash: H256,
+			/// Why the item failed to integrate.
+			error: DispatchError,
+		},
+		/// An item that failed to integrate could not be quarantined since it is too large or the
+		/// quarantine is full.
+		QuarantineRejected {
+			/// The pallet of the item.
+			pallet: PalletEventName,
+			/// The hash of the encoded item.
+			hash: H256,
+			/// Why the item failed to integrate.
+			error: DispatchError,
+		},
+		/// A quarantined item was integrated and removed from the quarantine.
+		QuarantinedItemApplied {
+			/// The pallet of the item.
+			pallet: PalletEventName,
+			/// The hash of the quarantined item.
+			hash: H256,
+			/// Whether a corrected item was integrated instead of the quarantined one.
+			forced: bool,
+		},
+		/// A translation was added to the [`ExtraAccountTranslations`].
+		AccountTranslationAdded {
+			/// The parachain of the sovereign account.
+			para_id: u32,
+			/// The derivation index if the account is derived from the sovereign account.
+			derivation_index: Option<u16>,
+			/// The Relay Chain account.
+			from: T::AccountId,
+			/// The Asset Hub account.
+			to: T::AccountId,
+		},
+		/// A translation was removed from the [`ExtraAccountTranslations`].
+		AccountTranslationRemoved {
+			/// The Relay Chain account.
+			from: T::AccountId,
+		},
+		/// The ingestion stage of a migrated pallet changed.
+		PalletStageTransition {
+			/// The migrated pallet.
+			pallet: MigratedPallet,
+			/// The old stage before the transition.
+			old: ingestion::PalletMigrationStage,
+			/// The new stage after the transition.
+			new: ingestion::PalletMigrationStage,
+		},
+		/// The migrated totals of the Relay Chain were reconciled with those of the Asset Hub.
+		ReconciliationReport {
+			/// The reconciliation report.
+			report: reconciliation::BalanceReconciliationOf<T>,
+		},
+		/// A migrated preimage that is not used anymore was removed.
+		PreimageCollected {
+			/// The hash of the preimage.
+			hash: H256,
+			/// The account whose deposit was refunded, if the preimage had a deposit.
+			depositor: Option<T::AccountId>,
+		},
+		/// A round of the preimage garbage collection completed.
+		PreimageGcRoundCompleted,
+		/// A component of an account was integrated, see [`audit`].
+		AccountComponentMigrated {
+			/// The Asset Hub account.
+			who: T::AccountId,
+			/// The integrated component.
+			component: audit::AccountComponentOf<T>,
+		},
+		/// Whether to halt the ingestion on a digest mismatch was set.
+		HaltOnDigestMismatchSet {
+			/// Whether to halt.
+			halt: bool,
+		},
+		/// The ingestion of further pallets was halted by a digest mismatch.
+		IngestionHalted {
+			/// The pallet whose digest does not match.
+			pallet: MigratedPallet,
+		},
+		/// The ingestion was resumed after a halt.
+		IngestionResumed {
+			/// The pallet that halted the ingestion.
+			pallet: MigratedPallet,
+		},
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::call]
@@ -656,7 +943,17 @@ pub mod pallet {
 				};
 				total = total.saturating_add(weight);
 			}
+			// The balance and every hold, freeze and lock of an account are audited.
+			let components = accounts.iter().fold(0u32, |n, account| {
+				n.saturating_add(
+					1 + account.holds.len() as u32 +
+						account.freezes.len() as u32 +
+						account.locks.len() as u32,
+				)
+			});
 			total
+				.saturating_add(T::AhWeightInfo::record_received_items(accounts.len() as u32))
+				.saturating_add(Pallet::<T>::audit_weight(components))
 		})]
 		pub fn receive_accounts(
 			origin: OriginFor<T>,
@@ -664,6 +961,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Accounts, &accounts)?;
 			Self::do_receive_accounts(accounts).map_err(Into::into)
 		}
 
@@ -673,37 +971,51 @@ pub mod pallet {
 		/// multisigs were prepared by
 		/// `pallet_rc_migrator::multisig::MultisigMigrator::migrate_many`.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::AhWeightInfo::receive_multisigs(accounts.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_multisigs(accounts.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(accounts.len() as u32))
+				.saturating_add(Pallet::<T>::audit_weight(accounts.len() as u32))
+		)]
 		pub fn receive_multisigs(
 			origin: OriginFor<T>,
 			accounts: Vec<RcMultisigOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Multisig, &accounts)?;
 			Self::do_receive_multisigs(accounts).map_err(Into::into)
 		}
 
 		/// Receive proxies from the Relay Chain.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::AhWeightInfo::receive_proxy_proxies(proxies.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_proxy_proxies(proxies.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(proxies.len() as u32))
+				.saturating_add(Pallet::<T>::audit_weight(proxies.len() as u32))
+		)]
 		pub fn receive_proxy_proxies(
 			origin: OriginFor<T>,
 			proxies: Vec<RcProxyOf<T, T::RcProxyType>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Proxy, &proxies)?;
 			Self::do_receive_proxies(proxies).map_err(Into::into)
 		}
 
 		/// Receive proxy announcements from the Relay Chain.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::AhWeightInfo::receive_proxy_announcements(announcements.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_proxy_announcements(announcements.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(announcements.len() as u32))
+		)]
 		pub fn receive_proxy_announcements(
 			origin: OriginFor<T>,
 			announcements: Vec<RcProxyAnnouncementOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Proxy, &announcements)?;
 			Self::do_receive_proxy_announcements(announcements).map_err(Into::into)
 		}
 
@@ -717,7 +1029,7 @@ pub mod pallet {
 					),
 				);
 			}
-			total
+			total.saturating_add(T::AhWeightInfo::record_received_items(chunks.len() as u32))
 		})]
 		pub fn receive_preimage_chunks(
 			origin: OriginFor<T>,
@@ -725,56 +1037,77 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Preimage, &chunks)?;
 			Self::do_receive_preimage_chunks(chunks).map_err(Into::into)
 		}
 
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::AhWeightInfo::receive_preimage_request_status(request_status.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_preimage_request_status(request_status.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(request_status.len() as u32))
+		)]
 		pub fn receive_preimage_request_status(
 			origin: OriginFor<T>,
 			request_status: Vec<PortableRequestStatus>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Preimage, &request_status)?;
 			Self::do_receive_preimage_request_statuses(request_status).map_err(Into::into)
 		}
 
 		#[pallet::call_index(6)]
-		#[pallet::weight(T::AhWeightInfo::receive_preimage_legacy_status(legacy_status.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_preimage_legacy_status(legacy_status.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(legacy_status.len() as u32))
+		)]
 		pub fn receive_preimage_legacy_status(
 			origin: OriginFor<T>,
 			legacy_status: Vec<RcPreimageLegacyStatusOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Preimage, &legacy_status)?;
 			Self::do_receive_preimage_legacy_statuses(legacy_status).map_err(Into::into)
 		}
 
 		#[pallet::call_index(7)]
-		#[pallet::weight(T::AhWeightInfo::receive_nom_pools_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_nom_pools_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_nom_pools_messages(
 			origin: OriginFor<T>,
 			messages: Vec<RcNomPoolsMessage<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::NomPools, &messages)?;
 			Self::do_receive_nom_pools_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(8)]
-		#[pallet::weight(T::AhWeightInfo::receive_vesting_schedules(schedules.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_vesting_schedules(schedules.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(schedules.len() as u32))
+				.saturating_add(Pallet::<T>::audit_weight(schedules.len() as u32))
+		)]
 		pub fn receive_vesting_schedules(
 			origin: OriginFor<T>,
 			schedules: Vec<RcVestingSchedule<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Vesting, &schedules)?;
 			Self::do_receive_vesting_schedules(schedules).map_err(Into::into)
 		}
 
 		/// Receive referendum counts, deciding counts, votes for the track queue.
 		#[pallet::call_index(10)]
-		#[pallet::weight(T::AhWeightInfo::receive_referenda_values())]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_referenda_values()
+				.saturating_add(T::AhWeightInfo::record_received_items(values.len() as u32))
+		)]
 		pub fn receive_referenda_values(
 			origin: OriginFor<T>,
 			// we accept a vector here only to satisfy the signature of the
@@ -785,6 +1118,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Referenda, &values)?;
 			ensure!(values.len() == 1, Error::<T>::InvalidParameter);
 
 			let ReferendaMessage { referendum_count, deciding_count, track_queue } =
@@ -817,7 +1151,7 @@ pub mod pallet {
 				};
 				total = total.saturating_add(weight);
 			}
-			total
+			total.saturating_add(T::AhWeightInfo::record_received_items(referendums.len() as u32))
 		})]
 		pub fn receive_referendums(
 			origin: OriginFor<T>,
@@ -825,112 +1159,153 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Referenda, &referendums)?;
 			Self::do_receive_referendums(referendums).map_err(Into::into)
 		}
 		#[pallet::call_index(12)]
-		#[pallet::weight(T::AhWeightInfo::receive_claims(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_claims(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_claims(
 			origin: OriginFor<T>,
 			messages: Vec<RcClaimsMessageOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Claims, &messages)?;
 			Self::do_receive_claims(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(13)]
-		#[pallet::weight(T::AhWeightInfo::receive_bags_list_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_bags_list_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_bags_list_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableBagsListMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::BagsList, &messages)?;
 			Self::do_receive_bags_list_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(14)]
-		#[pallet::weight(T::AhWeightInfo::receive_scheduler_lookup(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_scheduler_lookup(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_scheduler_messages(
 			origin: OriginFor<T>,
 			messages: Vec<RcSchedulerMessageOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Scheduler, &messages)?;
 			Self::do_receive_scheduler_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(15)]
-		#[pallet::weight(T::AhWeightInfo::receive_indices(indices.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_indices(indices.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(indices.len() as u32))
+		)]
 		pub fn receive_indices(
 			origin: OriginFor<T>,
 			indices: Vec<RcIndicesIndexOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Indices, &indices)?;
 			Self::do_receive_indices(indices).map_err(Into::into)
 		}
 
 		#[pallet::call_index(16)]
-		#[pallet::weight(T::AhWeightInfo::receive_conviction_voting_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_conviction_voting_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_conviction_voting_messages(
 			origin: OriginFor<T>,
 			messages: Vec<RcConvictionVotingMessageOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::ConvictionVoting, &messages)?;
 			Self::do_receive_conviction_voting_messages(messages).map_err(Into::into)
 		}
 		#[pallet::call_index(17)]
-		#[pallet::weight(T::AhWeightInfo::receive_bounties_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_bounties_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_bounties_messages(
 			origin: OriginFor<T>,
 			messages: Vec<RcBountiesMessageOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Bounties, &messages)?;
 			Self::do_receive_bounties_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(18)]
-		#[pallet::weight(T::AhWeightInfo::receive_asset_rates(rates.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_asset_rates(rates.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(rates.len() as u32))
+		)]
 		pub fn receive_asset_rates(
 			origin: OriginFor<T>,
 			rates: Vec<(<T as pallet_asset_rate::Config>::AssetKind, FixedU128)>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::AssetRate, &rates)?;
 			Self::do_receive_asset_rates(rates).map_err(Into::into)
 		}
 		#[pallet::call_index(19)]
-		#[pallet::weight(T::AhWeightInfo::receive_crowdloan_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_crowdloan_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_crowdloan_messages(
 			origin: OriginFor<T>,
 			messages: Vec<RcCrowdloanMessageOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Crowdloan, &messages)?;
 			Self::do_receive_crowdloan_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(20)]
-		#[pallet::weight(T::AhWeightInfo::receive_referenda_metadata(metadata.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_referenda_metadata(metadata.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(metadata.len() as u32))
+		)]
 		pub fn receive_referenda_metadata(
 			origin: OriginFor<T>,
 			metadata: Vec<(u32, <T as frame_system::Config>::Hash)>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Referenda, &metadata)?;
 			Self::do_receive_referenda_metadata(metadata).map_err(Into::into)
 		}
 		#[pallet::call_index(21)]
-		#[pallet::weight(T::AhWeightInfo::receive_treasury_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_treasury_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_treasury_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableTreasuryMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Treasury, &messages)?;
 			Self::do_receive_treasury_messages(messages)
 		}
 
@@ -951,7 +1326,7 @@ pub mod pallet {
 					);
 				}
 			}
-			total
+			total.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
 		})]
 		pub fn receive_scheduler_agenda_messages(
 			origin: OriginFor<T>,
@@ -959,51 +1334,68 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Scheduler, &messages)?;
 			Self::do_receive_scheduler_agenda_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(23)]
-		#[pallet::weight(T::AhWeightInfo::receive_delegated_staking_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_delegated_staking_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_delegated_staking_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableDelegatedStakingMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::DelegatedStaking, &messages)?;
 			Self::do_receive_delegated_staking_messages(messages)
 		}
 
 		#[pallet::call_index(24)]
-		#[pallet::weight(T::AhWeightInfo::receive_child_bounties_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_child_bounties_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_child_bounties_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableChildBountiesMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::ChildBounties, &messages)?;
 			Self::do_receive_child_bounties_messages(messages).map_err(Into::into)
 		}
 
 		#[pallet::call_index(25)]
-		#[pallet::weight(T::AhWeightInfo::receive_staking_messages(messages.len() as u32))]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_staking_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_staking_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableStakingMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Staking, &messages)?;
 			Self::do_receive_staking_messages(messages).map_err(Into::into)
 		}
 
 		#[cfg(feature = "kusama-ahm")]
 		#[pallet::call_index(26)]
-		#[pallet::weight(pallet_rc_migrator::recovery::ah_receive_recovery_msg_weight(messages.len() as u32))]
+		#[pallet::weight(
+			pallet_rc_migrator::recovery::ah_receive_recovery_msg_weight(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
 		pub fn receive_recovery_messages(
 			origin: OriginFor<T>,
 			messages: Vec<PortableRecoveryMessage>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Recovery, &messages)?;
 			Self::do_receive_recovery_messages(messages).map_err(Into::into)
 		}
 
@@ -1012,6 +1404,7 @@ pub mod pallet {
 		#[pallet::weight({
 			Weight::from_parts(10_000_000, 1000)
 				.saturating_add(T::DbWeight::get().writes(1_u64).saturating_mul(messages.len() as u64))
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
 		})]
 		pub fn receive_society_messages(
 			origin: OriginFor<T>,
@@ -1019,9 +1412,64 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
+			Self::record_received_items(MigratedPallet::Society, &messages)?;
 			Self::do_receive_society_messages(messages).map_err(Into::into)
 		}
 
+		/// Receive the fast-unstake requests that were drained on the Relay Chain.
+		///
+		/// Refunds the deposit of each request.
+		#[pallet::call_index(29)]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_fast_unstake_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
+		pub fn receive_fast_unstake_messages(
+			origin: OriginFor<T>,
+			messages: Vec<RcFastUnstakeRequestOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::record_received_items(MigratedPallet::FastUnstake, &messages)?;
+			Self::do_receive_fast_unstake_messages(messages).map_err(Into::into)
+		}
+
+		/// Receive the unlock schedule of the lease and auction reserves from the Relay Chain.
+		///
+		/// The scheduled reserves are released by `pallet_ah_ops` in `on_idle`. Registration
+		/// deposits that were migrated with the account of their manager are unreserved.
+		#[pallet::call_index(30)]
+		#[pallet::weight(
+			T::AhWeightInfo::receive_para_reserves_messages(messages.len() as u32)
+				.saturating_add(T::AhWeightInfo::record_received_items(messages.len() as u32))
+		)]
+		pub fn receive_para_reserves_messages(
+			origin: OriginFor<T>,
+			messages: Vec<RcParaReservesMessageOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::record_received_items(MigratedPallet::ParaReserves, &messages)?;
+			Self::do_receive_para_reserves_messages(messages).map_err(Into::into)
+		}
+
+		/// Receive the digest of the data sent for a pallet from the Relay Chain.
+		///
+		/// Sent by `pallet_rc_migrator::Pallet::complete_pallet_migration` and compared against
+		/// the digest of the received data.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(5, 4))]
+		pub fn receive_migration_digest(
+			origin: OriginFor<T>,
+			pallet: MigratedPallet,
+			digest: MigrationDigest,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::do_receive_migration_digest(pallet, digest);
+			Ok(())
+		}
+
 		/// Set the migration stage.
 		///
 		/// This call is intended for emergency use only and is guarded by the
@@ -1035,6 +1483,25 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Set the ingestion stage of a migrated pallet.
+		///
+		/// Setting a pallet to `Ongoing` opens its window and closes any other. Intended for
+		/// emergency use only, e.g. to accept resent batches of a pallet that is already done.
+		#[pallet::call_index(104)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn force_set_pallet_stage(
+			origin: OriginFor<T>,
+			pallet: MigratedPallet,
+			stage: ingestion::PalletMigrationStage,
+		) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+
+			PalletIngestions::<T>::mutate(pallet, |ingestion| {
+				Self::set_pallet_stage(pallet, ingestion, stage)
+			});
+			Ok(())
+		}
+
 		/// Start the data migration.
 		///
 		/// This is typically called by the Relay Chain to start the migration on the Asset Hub and
@@ -1096,7 +1563,7 @@ pub mod pallet {
 		#[pallet::weight(T::AhWeightInfo::finish_migration())]
 		pub fn finish_migration(
 			origin: OriginFor<T>,
-			data: Option<MigrationFinishedData<T::Balance>>,
+			data: Option<MigrationFinishedData<T::Balance, T::PortableHoldReason>>,
 			cool_off_end_at: u32,
 		) -> DispatchResult {
 			Self::ensure_admin_or_manager(origin)?;
@@ -1155,6 +1622,188 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Integrate a quarantined item again.
+		///
+		/// Intended to be used once the cause of the failure is fixed. The item stays quarantined
+		/// if it fails again.
+		#[pallet::call_index(112)]
+		#[pallet::weight(
+			Pallet::<T>::weight_apply_quarantined(pallet, quarantine::MAX_QUARANTINED_ITEM_SIZE)
+		)]
+		pub fn retry_quarantined(
+			origin: OriginFor<T>,
+			pallet: PalletEventName,
+			hash: H256,
+		) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+
+			let item = Quarantine::<T>::get(&pallet, hash).ok_or(Error::<T>::NotQuarantined)?;
+			Self::do_apply_quarantined(pallet, hash, &item.payload, false)
+		}
+
+		/// Integrate a corrected `payload` in place of a quarantined item.
+		///
+		/// `payload` is the encoded item in the same format as the quarantined one. The
+		/// quarantined item is removed if the corrected one integrates.
+		///
+		/// Can only be called by the [`Config::AdminOrigin`], since the payload is not checked
+		/// against the quarantined item.
+		#[pallet::call_index(113)]
+		#[pallet::weight(Pallet::<T>::weight_apply_quarantined(pallet, payload.len() as u32))]
+		pub fn force_apply_quarantined(
+			origin: OriginFor<T>,
+			pallet: PalletEventName,
+			hash: H256,
+			payload: Vec<u8>,
+		) -> DispatchResult {
+			<T as Config>::AdminOrigin::ensure_origin(origin)?;
+
+			Self::do_apply_quarantined(pallet, hash, &payload, true)
+		}
+
+		/// Add the translation of the sovereign account of `para_id` or, with a
+		/// `derivation_index`, of the account derived from it.
+		///
+		/// For accounts that are missing in the tables of [`sovereign_account_translation`], e.g.
+		/// a newly discovered derived account. Accounts that were already migrated are not
+		/// translated again.
+		///
+		/// While any translation is added, every translated account costs a read of the
+		/// [`ExtraAccountTranslations`] that the benchmarked weights of the migration do not
+		/// include, so translations should be removed once they are not needed anymore.
+		#[pallet::call_index(114)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn add_account_translation(
+			origin: OriginFor<T>,
+			para_id: u32,
+			derivation_index: Option<u16>,
+		) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+
+			let (from, to) =
+				account_translation::sovereign_translation_of(para_id, derivation_index);
+			ensure!(
+				Self::sovereign_translation(&from).is_none() &&
+					Self::derived_translation(&from).is_none(),
+				Error::<T>::AccountTranslationExists
+			);
+
+			ExtraAccountTranslations::<T>::insert(&from, (to.clone(), derivation_index));
+			ExtraAccountTranslationsCount::<T>::mutate(|count| *count = count.saturating_add(1));
+			Self::deposit_event(Event::AccountTranslationAdded {
+				para_id,
+				derivation_index,
+				from,
+				to,
+			});
+			Ok(())
+		}
+
+		/// Remove a translation that was added with [`Pallet::add_account_translation`].
+		#[pallet::call_index(115)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+		pub fn remove_account_translation(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+
+			ensure!(
+				ExtraAccountTranslations::<T>::take(&from).is_some(),
+				Error::<T>::AccountTranslationNotFound
+			);
+			ExtraAccountTranslationsCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			Self::deposit_event(Event::AccountTranslationRemoved { from });
+			Ok(())
+		}
+
+		/// Remove the migrated preimages that are not used anymore.
+		///
+		/// Runs up to `max_steps` steps of the garbage collection, see [`preimage_gc`]. Deposits
+		/// of removed preimages are refunded to their depositors.
+		///
+		/// Can be called by any signed origin once the migration is finished.
+		#[pallet::call_index(116)]
+		#[pallet::weight(Pallet::<T>::preimage_gc_step_weight().saturating_mul(max_steps.into()))]
+		pub fn collect_preimages(
+			origin: OriginFor<T>,
+			max_steps: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(AhMigrationStage::<T>::get().is_finished(), Error::<T>::MigrationNotFinished);
+			ensure!(
+				PreimageGc::<T>::get().stage != preimage_gc::PreimageGcStage::Idle ||
+					MigratedPreimages::<T>::iter_keys().next().is_some(),
+				Error::<T>::NoPreimagesToCollect
+			);
+
+			let limit = Self::preimage_gc_step_weight().saturating_mul(max_steps.into());
+			let mut meter = WeightMeter::with_limit(limit);
+			Self::do_collect_preimages(&mut meter);
+
+			Ok(Some(meter.consumed()).into())
+		}
+
+		/// Set whether to halt the ingestion of further pallets when a digest does not match.
+		///
+		/// Disabling it also lifts an ongoing halt.
+		#[pallet::call_index(117)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+		pub fn set_halt_on_digest_mismatch(origin: OriginFor<T>, halt: bool) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+
+			HaltOnDigestMismatch::<T>::put(halt);
+			if !halt {
+				if let Some(pallet) = DigestMismatchHalt::<T>::take() {
+					Self::deposit_event(Event::IngestionResumed { pallet });
+				}
+			}
+			Self::deposit_event(Event::HaltOnDigestMismatchSet { halt });
+			Ok(())
+		}
+	}
+
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// The pallet whose ingestion window is open and the ingestion state of all pallets that
+		/// received data or were finished by the Relay Chain.
+		pub fn pallet_ingestions(
+		) -> (Option<MigratedPallet>, Vec<(MigratedPallet, ingestion::PalletIngestion)>) {
+			(IngestingPallet::<T>::get(), PalletIngestions::<T>::iter().collect())
+		}
+
+		/// Up to `limit` items that failed to integrate and are quarantined, starting after the
+		/// item `start_after` or with the first one.
+		///
+		/// The last returned item is the `start_after` of the next page.
+		pub fn quarantined_items(
+			start_after: Option<(PalletEventName, H256)>,
+			limit: u32,
+		) -> Vec<(PalletEventName, H256, quarantine::QuarantinedItemOf<T>)> {
+			let limit = limit.min(quarantine::MAX_QUARANTINED_ITEMS_PAGE) as usize;
+			match start_after {
+				Some((pallet, hash)) =>
+					Quarantine::<T>::iter_from(Quarantine::<T>::hashed_key_for(pallet, hash))
+						.take(limit)
+						.collect(),
+				None => Quarantine::<T>::iter().take(limit).collect(),
+			}
+		}
+
+		/// Dry run the translation of the calls returned by the `migrated_calls` view function of
+		/// the Relay Chain.
+		pub fn call_translation_report(
+			calls: Vec<MigratedCall<BlockNumberFor<T>>>,
+		) -> Vec<CallTranslation<BlockNumberFor<T>>> {
+			calls
+				.into_iter()
+				.map(|call| CallTranslation {
+					result: Self::translate_migrated_call(&call),
+					source: call.source,
+				})
+				.collect()
+		}
 	}
 
 	#[pallet::hooks]
@@ -1164,12 +1813,20 @@ pub mod pallet {
 
 			if Self::is_ongoing() {
 				weight = weight.saturating_add(T::AhWeightInfo::force_dmp_queue_priority());
+				// The whitelisted `ExtraAccountTranslationsCount` read by the account translation.
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+
+			if Self::is_ongoing() && T::AccountAudit::get() {
+				AuditDigest::<T>::kill();
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
 			}
 
 			if let MigrationStage::CoolOff { end_at } = AhMigrationStage::<T>::get() {
 				// `TreasuryBlockNumberProvider` is the RC block number provider.
 				let rc_block = T::TreasuryBlockNumberProvider::current_block_number();
-				if rc_block >= end_at {
+				// A failed reconciliation holds the migration until an admin moves it on.
+				if rc_block >= end_at && !Self::reconciliation_blocks_completion() {
 					Self::transition(MigrationStage::MigrationDone);
 				}
 			}
@@ -1186,6 +1843,18 @@ pub mod pallet {
 		fn integrity_test() {
 			let (dmp_priority_blocks, _) = T::DmpQueuePriorityPattern::get();
 			assert!(!dmp_priority_blocks.is_zero(), "the `dmp_priority_blocks` should be non-zero");
+
+			use sovereign_account_translation::{DERIVED_TRANSLATIONS, SOV_TRANSLATIONS};
+			if let Err(account) = account_translation::ensure_strictly_sorted(
+				SOV_TRANSLATIONS.iter().map(|((rc_acc, _), _)| rc_acc),
+			) {
+				panic!("`SOV_TRANSLATIONS` is not strictly sorted at {account:?}");
+			}
+			if let Err(account) = account_translation::ensure_strictly_sorted(
+				DERIVED_TRANSLATIONS.iter().map(|((rc_acc, _), _, _)| rc_acc),
+			) {
+				panic!("`DERIVED_TRANSLATIONS` is not strictly sorted at {account:?}");
+			}
 		}
 	}
 
@@ -1244,11 +1913,18 @@ pub mod pallet {
 
 		/// Auxiliary logic to be done after the migration finishes.
 		pub fn migration_finish_hook(
-			data: Option<MigrationFinishedData<T::Balance>>,
+			data: Option<MigrationFinishedData<T::Balance, T::PortableHoldReason>>,
 			cool_off_end_at: u32,
 		) -> Result<(), Error<T>> {
 			// Accounts
 			if let Some(data) = data {
+				// Reconcile only once, the balances before are consumed below.
+				if AhBalancesBefore::<T>::exists() {
+					let issuance_delta = <T as Config>::Currency::total_issuance()
+						.saturating_sub(AhBalancesBefore::<T>::get().total_issuance);
+					Self::do_reconcile_balances(&data, issuance_delta);
+				}
+
 				if let Err(err) = Self::finish_accounts_migration(data.rc_balance_kept) {
 					defensive!("Account migration failed: {:?}", err);
 				}
@@ -0,0 +1,71 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the pallet.
+//!
+//! [`Migrations`] contains every migration in order and must be added to the runtime's migrations.
+
+use crate::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+/// All migrations of the pallet, in order.
+pub type Migrations<T> = (MigrateToV1<T>,);
+
+/// Move the accounts that failed to integrate into the [`Quarantine`], see [`quarantine`].
+pub type MigrateToV1<T> = VersionedMigration<
+	0,
+	1,
+	v1::QuarantineFailedAccounts<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
+
+pub mod v1 {
+	use super::*;
+	use frame_support::storage_alias;
+
+	/// The accounts that failed to integrate before version 1.
+	#[storage_alias]
+	pub type RcAccounts<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		RcAccountFor<T>,
+		OptionQuery,
+	>;
+
+	/// The accounts carry no error, they are quarantined with
+	/// [`Error::FailedToProcessAccount`] and can be retried like any other quarantined account.
+	pub struct QuarantineFailedAccounts<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for QuarantineFailedAccounts<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut moved = 0u64;
+			for (_, account) in RcAccounts::<T>::drain() {
+				moved += 1;
+				Pallet::<T>::quarantine(
+					PalletEventName::Balances,
+					&account,
+					Error::<T>::FailedToProcessAccount,
+				);
+			}
+
+			// per account: the drained entry, the quarantine entry and its counter.
+			T::DbWeight::get().reads_writes(3 * moved + 1, 3 * moved)
+		}
+	}
+}
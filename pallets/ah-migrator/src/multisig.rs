@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use crate::*;
+use sp_runtime::SaturatedConversion;
 
 #[cfg(feature = "std")]
 use pallet_rc_migrator::types::AccountIdOf;
@@ -30,19 +31,16 @@ impl<T: Config> Pallet<T> {
 		log::info!(target: LOG_TARGET, "Integrating {} multisigs", multisigs.len());
 
 		for multisig in multisigs {
-			match Self::do_receive_multisig(multisig) {
+			match Self::do_receive_multisig(multisig.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating multisig: {e:?}");
+					Self::quarantine(PalletEventName::Multisig, &multisig, e);
 				},
 			}
 		}
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Multisig,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Multisig, count_good, count_bad);
 
 		Ok(())
 	}
@@ -67,9 +65,12 @@ impl<T: Config> Pallet<T> {
 			Self::deposit_event(Event::FailedToUnreserveMultisigDeposit {
 				expected_amount: multisig.deposit,
 				missing_amount: missing,
-				account: translated_creator,
+				account: translated_creator.clone(),
 			});
 		}
+		let deposit = multisig.deposit.saturated_into();
+		let component = audit::AccountComponent::MultisigDeposit { deposit };
+		Self::audit(&translated_creator, component);
 
 		Ok(())
 	}
@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds the unlock schedule of `pallet_ah_ops`, see [`pallet_rc_migrator::para_reserves`].
+
+use crate::*;
+use pallet_rc_migrator::para_reserves::{
+	ParaReservesMigrator, RcParaReservesMessage, RcParaReservesMessageOf,
+};
+
+impl<T: Config> Pallet<T> {
+	pub fn do_receive_para_reserves_messages(
+		messages: Vec<RcParaReservesMessageOf<T>>,
+	) -> Result<(), Error<T>> {
+		let (mut good, mut bad) = (0, 0);
+		Self::deposit_event(Event::BatchReceived {
+			pallet: PalletEventName::ParaReserves,
+			count: messages.len() as u32,
+		});
+		log::info!(target: LOG_TARGET, "Received {} para reserves messages", messages.len());
+
+		for message in messages {
+			match Self::do_process_para_reserves_message(message.clone()) {
+				Ok(()) => good += 1,
+				Err(e) => {
+					bad += 1;
+					log::error!(target: LOG_TARGET, "Error while integrating para reserves message: {e:?}");
+					Self::quarantine(PalletEventName::ParaReserves, &message, e);
+				},
+			}
+		}
+
+		Self::note_batch_processed(PalletEventName::ParaReserves, good, bad);
+
+		Ok(())
+	}
+
+	pub fn do_process_para_reserves_message(
+		message: RcParaReservesMessageOf<T>,
+	) -> Result<(), Error<T>> {
+		match message {
+			RcParaReservesMessage::LeaseUnlock { para_id, unlock_block } => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Scheduling unlock of para_id: {:?} at block: {:?}",
+					&para_id,
+					&unlock_block
+				);
+				// A full schedule is reported by `pallet_ah_ops`, the reserves can still be
+				// released manually.
+				pallet_ah_ops::Pallet::<T>::schedule_unlock(unlock_block, para_id);
+				Ok(())
+			},
+			RcParaReservesMessage::AuctionReserve { bidder, para_id, amount } => {
+				let translated_bidder = Self::translate_account_rc_to_ah(bidder);
+				// The auction will not conclude anymore, so the bid is released right away. All
+				// bids share the first block to take a single entry of the schedule.
+				let unlock_block = Zero::zero();
+				log::debug!(
+					target: LOG_TARGET,
+					"Integrating auction reserve for para_id: {:?}, bidder: {:?}, amount: {:?}",
+					&para_id,
+					&translated_bidder,
+					&amount
+				);
+
+				pallet_ah_ops::RcLeaseReserve::<T>::mutate(
+					(unlock_block, para_id, &translated_bidder),
+					|reserve| *reserve = Some(reserve.unwrap_or_default().saturating_add(amount)),
+				);
+				pallet_ah_ops::Pallet::<T>::schedule_unlock(unlock_block, para_id);
+				Ok(())
+			},
+			RcParaReservesMessage::RegistrarDeposit { manager, para_id, amount } => {
+				let translated_manager = Self::translate_account_rc_to_ah(manager);
+				log::debug!(
+					target: LOG_TARGET,
+					"Unreserving registration deposit of para_id: {:?}, manager: {:?}, amount: {:?}",
+					&para_id,
+					&translated_manager,
+					&amount
+				);
+
+				let missing = <T as Config>::Currency::unreserve(&translated_manager, amount);
+				if !missing.is_zero() {
+					return Err(Error::<T>::FailedToUnreserveDeposit);
+				}
+				Ok(())
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> crate::types::AhMigrationCheck for ParaReservesMigrator<T> {
+	type RcPrePayload = Vec<RcParaReservesMessageOf<T>>;
+	type AhPrePayload = ();
+
+	fn pre_check(_: Self::RcPrePayload) -> Self::AhPrePayload {}
+
+	fn post_check(rc_pre_payload: Self::RcPrePayload, _: Self::AhPrePayload) {
+		for message in rc_pre_payload {
+			match message {
+				// Unreserved on arrival, nothing is left to check.
+				RcParaReservesMessage::RegistrarDeposit { .. } => {},
+				RcParaReservesMessage::LeaseUnlock { para_id, unlock_block } => {
+					// Assert storage 'AhOps::UnlockSchedule::ah_post::correct'
+					assert!(
+						pallet_ah_ops::UnlockSchedule::<T>::contains_key(unlock_block, para_id),
+						"Unlock of para {para_id:?} at block {unlock_block:?} should be scheduled"
+					);
+				},
+				RcParaReservesMessage::AuctionReserve { bidder, para_id, .. } => {
+					let translated_bidder = Pallet::<T>::translate_account_rc_to_ah(bidder);
+					// Assert storage 'AhOps::RcLeaseReserve::ah_post::correct'
+					assert!(
+						pallet_ah_ops::RcLeaseReserve::<T>::iter_keys()
+							.any(|(_, id, who)| id == para_id && who == translated_bidder),
+						"Auction reserve of {translated_bidder:?} for para {para_id:?} should be \
+							migrated"
+					);
+				},
+			}
+		}
+	}
+}
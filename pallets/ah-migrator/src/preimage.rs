@@ -41,11 +41,7 @@ impl<T: Config> Pallet<T> {
 				},
 			}
 		}
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::PreimageChunk,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::PreimageChunk, count_good, count_bad);
 
 		Ok(())
 	}
@@ -111,20 +107,17 @@ impl<T: Config> Pallet<T> {
 		let (mut count_good, mut count_bad) = (0, 0);
 
 		for request_status in request_status {
-			match Self::do_receive_preimage_request_status(request_status) {
+			match Self::do_receive_preimage_request_status(request_status.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating preimage request status: {e:?}");
+					Self::quarantine(PalletEventName::PreimageRequestStatus, &request_status, e);
 				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::PreimageRequestStatus,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::PreimageRequestStatus, count_good, count_bad);
 		Ok(())
 	}
 
@@ -208,6 +201,7 @@ impl<T: Config> Pallet<T> {
 		};
 
 		pallet_preimage::RequestStatusFor::<T>::insert(request_status.hash, &new_request_status);
+		Self::track_migrated_preimage(request_status.hash, preimage_gc::PreimageSource::RelayChain);
 		log::debug!(target: LOG_TARGET, "Integrating preimage request status: {new_request_status:?}");
 
 		Ok(())
@@ -224,19 +218,16 @@ impl<T: Config> Pallet<T> {
 		let (mut count_good, mut count_bad) = (0, 0);
 
 		for status in statuses {
-			match Self::do_receive_preimage_legacy_status(status) {
+			match Self::do_receive_preimage_legacy_status(status.clone()) {
 				Ok(()) => count_good += 1,
-				Err(_) => {
+				Err(e) => {
 					count_bad += 1;
+					Self::quarantine(PalletEventName::PreimageLegacyStatus, &status, e);
 				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::PreimageLegacyStatus,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::PreimageLegacyStatus, count_good, count_bad);
 		Ok(())
 	}
 
@@ -0,0 +1,265 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Garbage collection of the preimages migrated from the Relay Chain.
+//!
+//! The migration tracks the preimages that it integrates in [`MigratedPreimages`], once per hash:
+//! the request statuses received from the Relay Chain and the preimages it notes for translated
+//! referendum and scheduler calls. Many of them are not used anymore after the migration, e.g.
+//! the preimages of referendums that did not pass.
+//!
+//! [`Pallet::collect_preimages`] removes them in rounds of three stages, a few steps per call:
+//! 1. Mark the tracked preimages that ongoing referendums propose.
+//! 2. Mark the tracked preimages that scheduler agendas call.
+//! 3. Sweep the tracked preimages. Unmarked ones that are not in [`Config::PreimagesInUse`] are
+//!    removed and their deposit is refunded to their depositor. Marked ones are kept for the next
+//!    round.
+//!
+//! Referendums submitted after the first stage are marked during the sweep, one per step, before
+//! it continues. Calls that are scheduled after the second stage are expected to come from marked
+//! referendums.
+//!
+//! Every step does a bounded amount of work, the largest of which is benchmarked as the weight of a
+//! step, see [`Pallet::preimage_gc_step_weight`].
+
+use crate::*;
+use frame_support::traits::Consideration;
+use pallet_preimage::RequestStatus;
+use pallet_rc_migrator::scheduler::{alias::Agenda, SchedulerBlockNumberFor};
+use pallet_referenda::{ReferendumCount, ReferendumIndex, ReferendumInfo, ReferendumInfoFor};
+use sp_runtime::Saturating;
+
+/// Where a preimage that is tracked in [`MigratedPreimages`] comes from.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum PreimageSource {
+	/// The request status was migrated from the Relay Chain.
+	RelayChain,
+	/// The migration noted the preimage for a translated referendum or scheduler call.
+	Translation,
+}
+
+/// The stage of the preimage garbage collection round.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum PreimageGcStage<BlockNumber> {
+	/// No round is in progress.
+	#[default]
+	Idle,
+	/// Marking the proposals of the referendums, starting at index `next`.
+	MarkReferenda { next: ReferendumIndex },
+	/// Marking the calls of the scheduler agendas after the block `last`.
+	MarkAgendas { last: Option<BlockNumber> },
+	/// Sweeping the tracked preimages after the hash `last`.
+	Sweep { last: Option<H256> },
+}
+
+/// The state of the preimage garbage collection.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct PreimageGcState<BlockNumber> {
+	/// The stage of the current round.
+	pub stage: PreimageGcStage<BlockNumber>,
+	/// The number of referendums whose proposals are marked.
+	pub referendum_count: ReferendumIndex,
+}
+
+pub type PreimageGcStateOf<T> = PreimageGcState<SchedulerBlockNumberFor<T>>;
+
+impl<T: Config> Pallet<T> {
+	/// Track a preimage that the migration integrated.
+	///
+	/// A preimage that the migration noted takes precedence, since its request is released when it
+	/// is collected.
+	pub fn track_migrated_preimage(hash: H256, source: PreimageSource) {
+		MigratedPreimages::<T>::mutate(hash, |tracked| {
+			if source == PreimageSource::Translation || tracked.is_none() {
+				*tracked = Some(source);
+			}
+		});
+	}
+
+	/// The weight of one step of [`Self::do_collect_preimages`].
+	pub fn preimage_gc_step_weight() -> Weight {
+		let max_scheduled = <T as pallet_scheduler::Config>::MaxScheduledPerBlock::get();
+		T::AhWeightInfo::preimage_gc_mark_referendum()
+			.max(T::AhWeightInfo::preimage_gc_mark_agenda(max_scheduled))
+			.max(T::AhWeightInfo::preimage_gc_sweep())
+			// The `ReferendumCount` and the next key that the stage reads.
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+
+	/// Run the steps of the preimage garbage collection that fit into `meter`.
+	///
+	/// Returns whether a round is in progress.
+	pub fn do_collect_preimages(meter: &mut WeightMeter) -> bool {
+		let mut state = PreimageGc::<T>::get();
+
+		while meter.try_consume(Self::preimage_gc_step_weight()).is_ok() {
+			state.stage = match state.stage {
+				PreimageGcStage::Idle => {
+					if MigratedPreimages::<T>::iter_keys().next().is_none() {
+						break;
+					}
+					state.referendum_count = ReferendumCount::<T, ()>::get();
+					PreimageGcStage::MarkReferenda { next: 0 }
+				},
+				PreimageGcStage::MarkReferenda { next } if next < state.referendum_count => {
+					Self::mark_referendum_proposal(next);
+					PreimageGcStage::MarkReferenda { next: next + 1 }
+				},
+				PreimageGcStage::MarkReferenda { .. } =>
+					PreimageGcStage::MarkAgendas { last: None },
+				PreimageGcStage::MarkAgendas { last } => {
+					let next = match last {
+						Some(last) => Agenda::<T>::iter_keys_from_key(last).next(),
+						None => Agenda::<T>::iter_keys().next(),
+					};
+					match next {
+						Some(block) => {
+							Self::mark_agenda(block);
+							PreimageGcStage::MarkAgendas { last: Some(block) }
+						},
+						None => PreimageGcStage::Sweep { last: None },
+					}
+				},
+				PreimageGcStage::Sweep { last }
+					if state.referendum_count < ReferendumCount::<T, ()>::get() =>
+				{
+					Self::mark_referendum_proposal(state.referendum_count);
+					state.referendum_count.saturating_inc();
+					PreimageGcStage::Sweep { last }
+				},
+				PreimageGcStage::Sweep { last } => {
+					let next = match last {
+						Some(last) => MigratedPreimages::<T>::iter_keys_from_key(last).next(),
+						None => MigratedPreimages::<T>::iter_keys().next(),
+					};
+					match next {
+						Some(hash) => {
+							Self::sweep_preimage(hash);
+							PreimageGcStage::Sweep { last: Some(hash) }
+						},
+						None => {
+							Self::deposit_event(Event::PreimageGcRoundCompleted);
+							PreimageGcStage::Idle
+						},
+					}
+				},
+			};
+		}
+
+		let in_progress = state.stage != PreimageGcStage::Idle;
+		PreimageGc::<T>::put(state);
+		in_progress
+	}
+
+	/// Mark the proposal of the referendum `index` if it is ongoing.
+	pub(crate) fn mark_referendum_proposal(index: ReferendumIndex) {
+		if let Some(ReferendumInfo::Ongoing(status)) = ReferendumInfoFor::<T, ()>::get(index) {
+			if let Some(hash) = status.proposal.lookup_hash() {
+				Self::mark_preimage(hash);
+			}
+		}
+	}
+
+	/// Mark the calls of the scheduler agenda of `block`.
+	///
+	/// The agenda holds up to `MaxScheduledPerBlock` calls.
+	pub(crate) fn mark_agenda(block: SchedulerBlockNumberFor<T>) {
+		for task in Agenda::<T>::get(block).into_iter().flatten() {
+			if let Some(hash) = task.call.lookup_hash() {
+				Self::mark_preimage(hash);
+			}
+		}
+	}
+
+	/// Mark `hash` as used in this round if it is tracked.
+	fn mark_preimage(hash: H256) {
+		if MigratedPreimages::<T>::contains_key(hash) {
+			PreimageGcMarks::<T>::insert(hash, ());
+		}
+	}
+
+	/// Remove the tracked preimage `hash` unless it is marked or in use.
+	pub(crate) fn sweep_preimage(hash: H256) {
+		if PreimageGcMarks::<T>::take(hash).is_some() || T::PreimagesInUse::contains(&hash) {
+			return;
+		}
+		let Some(source) = MigratedPreimages::<T>::take(hash) else {
+			defensive!("Swept preimage is tracked");
+			return;
+		};
+
+		let mut status = pallet_preimage::RequestStatusFor::<T>::get(hash);
+		if source == PreimageSource::Translation &&
+			matches!(status, Some(RequestStatus::Requested { .. }))
+		{
+			// Release the request of the migration, this removes the preimage if it was the only
+			// request and nobody paid a deposit for it.
+			<T as Config>::Preimage::unrequest(&hash);
+			status = pallet_preimage::RequestStatusFor::<T>::get(hash);
+			if status.is_none() {
+				Self::deposit_event(Event::PreimageCollected { hash, depositor: None });
+			}
+		}
+
+		// Preimages that are still requested stay, their requester is responsible for them.
+		if let Some(RequestStatus::Unrequested { ticket: (depositor, ticket), len }) = status {
+			if let Err(err) = ticket.drop(&depositor) {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to refund the deposit of preimage {hash:?} to {}: {err:?}",
+					depositor.to_ss58check()
+				);
+			}
+			pallet_preimage::PreimageFor::<T>::remove((hash, len));
+			pallet_preimage::RequestStatusFor::<T>::remove(hash);
+			Self::deposit_event(Event::PreimageCollected { hash, depositor: Some(depositor) });
+		}
+	}
+}
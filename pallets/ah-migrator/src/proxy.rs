@@ -17,7 +17,7 @@
 
 use crate::*;
 use pallet_rc_migrator::types::ToPolkadotSs58;
-use sp_runtime::{traits::Zero, BoundedSlice, Saturating};
+use sp_runtime::{traits::Zero, BoundedSlice, SaturatedConversion, Saturating};
 
 impl<T: Config> Pallet<T> {
 	pub fn do_receive_proxies(proxies: Vec<RcProxyOf<T, T::RcProxyType>>) -> Result<(), Error<T>> {
@@ -29,19 +29,16 @@ impl<T: Config> Pallet<T> {
 		log::info!(target: LOG_TARGET, "Integrating batch proxies of with len {}", proxies.len());
 
 		for proxy in proxies {
-			match Self::do_receive_proxy(proxy) {
+			match Self::do_receive_proxy(proxy.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating proxy: {e:?}");
+					Self::quarantine(PalletEventName::ProxyProxies, &proxy, e);
 				},
 			}
 		}
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ProxyProxies,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::ProxyProxies, count_good, count_bad);
 
 		Ok(())
 	}
@@ -106,7 +103,11 @@ impl<T: Config> Pallet<T> {
 			);
 
 		// Add the proxies
+		let delegates = bounded_proxies.len() as u32;
 		pallet_proxy::Proxies::<T>::insert(&translated_delegator, (bounded_proxies, proxy.deposit));
+		let deposit = proxy.deposit.saturated_into();
+		let component = audit::AccountComponent::Proxies { delegates, deposit };
+		Self::audit(&translated_delegator, component);
 
 		Ok(())
 	}
@@ -123,20 +124,17 @@ impl<T: Config> Pallet<T> {
 		log::info!(target: LOG_TARGET, "Unreserving deposits for {} proxy announcements", announcements.len());
 
 		for announcement in announcements {
-			match Self::do_receive_proxy_announcement(announcement) {
+			match Self::do_receive_proxy_announcement(announcement.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating proxy announcement: {e:?}");
+					Self::quarantine(PalletEventName::ProxyAnnouncements, &announcement, e);
 				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ProxyAnnouncements,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::ProxyAnnouncements, count_good, count_bad);
 
 		Ok(())
 	}
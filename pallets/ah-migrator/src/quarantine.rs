@@ -0,0 +1,217 @@
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Quarantine for items received from the Relay Chain that failed to integrate.
+//!
+//! Instead of only being counted in [`Event::BatchProcessed`], a failed item is stored encoded in
+//! [`Quarantine`] together with its error, keyed by its pallet and the hash of its encoding. It can
+//! be integrated later with [`Pallet::retry_quarantined`] once the cause of the failure is fixed,
+//! or with [`Pallet::force_apply_quarantined`] with a corrected item.
+//!
+//! The item is quarantined in the form that its single-item integration function takes, e.g.
+//! [`Pallet::do_receive_account`] for accounts. Only pallets whose items can be integrated one by
+//! one are quarantined, see [`Pallet::integrate_quarantined`]. Scheduler agenda tasks are not,
+//! since their index in the agenda is part of their address.
+
+use crate::*;
+use codec::DecodeAll;
+use sp_runtime::{DispatchError, Saturating};
+
+/// Maximum number of items in the [`Quarantine`].
+pub const MAX_QUARANTINED_ITEMS: u32 = 1_000;
+
+/// Maximum size of an encoded quarantined item.
+pub const MAX_QUARANTINED_ITEM_SIZE: u32 = pallet_rc_migrator::MAX_XCM_SIZE;
+
+/// Maximum number of items returned by one call of the `quarantined_items` view function.
+pub const MAX_QUARANTINED_ITEMS_PAGE: u32 = 100;
+
+/// An item that failed to integrate.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct QuarantinedItem<BlockNumber> {
+	/// The encoded item.
+	pub payload: BoundedVec<u8, ConstU32<MAX_QUARANTINED_ITEM_SIZE>>,
+	/// The error of the last failed integration.
+	pub error: DispatchError,
+	/// The block in which the item was quarantined.
+	pub quarantined_at: BlockNumber,
+}
+
+pub type QuarantinedItemOf<T> = QuarantinedItem<BlockNumberFor<T>>;
+
+impl<T: Config> Pallet<T> {
+	/// Put an `item` of `pallet` that failed to integrate with `error` into the [`Quarantine`].
+	///
+	/// The item is dropped if it is too large or the quarantine is full.
+	pub fn quarantine(
+		pallet: PalletEventName,
+		item: &impl Encode,
+		error: impl Into<DispatchError>,
+	) {
+		let error = error.into();
+		let payload = item.encode();
+		let hash = H256(sp_io::hashing::blake2_256(&payload));
+
+		let is_new = !Quarantine::<T>::contains_key(&pallet, hash);
+		let payload = match BoundedVec::try_from(payload) {
+			Ok(payload) if !is_new || QuarantineCount::<T>::get() < MAX_QUARANTINED_ITEMS =>
+				payload,
+			_ => {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to quarantine {pallet:?} item {hash:?} with error {error:?}"
+				);
+				Self::deposit_event(Event::QuarantineRejected { pallet, hash, error });
+				return;
+			},
+		};
+
+		log::warn!(target: LOG_TARGET, "Quarantining {pallet:?} item {hash:?} with error {error:?}");
+		if is_new {
+			QuarantineCount::<T>::mutate(|count| count.saturating_inc());
+		}
+		let quarantined_at = frame_system::Pallet::<T>::block_number();
+		Quarantine::<T>::insert(&pallet, hash, QuarantinedItem { payload, error, quarantined_at });
+		Self::deposit_event(Event::ItemQuarantined { pallet, hash, error });
+	}
+
+	/// Integrate the quarantined item `hash` of `pallet` with `payload`.
+	///
+	/// `payload` is the quarantined one for a retry or a corrected one. The quarantined item is
+	/// only removed if the integration succeeds. Only possible while the migration is ongoing,
+	/// since the state of the migrated pallets is not modified by users until then.
+	pub fn do_apply_quarantined(
+		pallet: PalletEventName,
+		hash: H256,
+		payload: &[u8],
+		forced: bool,
+	) -> DispatchResult {
+		ensure!(AhMigrationStage::<T>::get().is_ongoing(), Error::<T>::MigrationNotOngoing);
+		ensure!(Self::is_quarantine_supported(&pallet), Error::<T>::QuarantineNotSupported);
+		ensure!(Quarantine::<T>::contains_key(&pallet, hash), Error::<T>::NotQuarantined);
+		Quarantine::<T>::remove(&pallet, hash);
+		QuarantineCount::<T>::mutate(|count| count.saturating_dec());
+
+		Self::integrate_quarantined(&pallet, payload)?;
+
+		// Handlers that recover from a failure by themselves quarantine the item again.
+		let payload_hash = H256(sp_io::hashing::blake2_256(payload));
+		ensure!(
+			!Quarantine::<T>::contains_key(&pallet, payload_hash),
+			Error::<T>::FailedToIntegrateQuarantined
+		);
+
+		Self::deposit_event(Event::QuarantinedItemApplied { pallet, hash, forced });
+		Ok(())
+	}
+
+	/// Whether items of `pallet` can be integrated from the quarantine.
+	pub fn is_quarantine_supported(pallet: &PalletEventName) -> bool {
+		matches!(
+			pallet,
+			PalletEventName::Balances |
+				PalletEventName::Multisig |
+				PalletEventName::Claims |
+				PalletEventName::ProxyProxies |
+				PalletEventName::ProxyAnnouncements |
+				PalletEventName::Vesting |
+				PalletEventName::Crowdloan |
+				PalletEventName::ParaReserves |
+				PalletEventName::BagsList |
+				PalletEventName::PreimageRequestStatus |
+				PalletEventName::PreimageLegacyStatus |
+				PalletEventName::ReferendaReferendums
+		)
+	}
+
+	/// Decode `payload` as an item of `pallet` and integrate it.
+	pub fn integrate_quarantined(pallet: &PalletEventName, payload: &[u8]) -> DispatchResult {
+		let decode = decode_quarantined::<T, _>;
+
+		match pallet {
+			PalletEventName::Balances => {
+				let account: RcAccountFor<T> = decode(payload)?;
+				with_transaction_opaque_err::<(), Error<T>, _>(|| {
+					match Self::do_receive_account(account) {
+						Ok(()) => TransactionOutcome::Commit(Ok(())),
+						Err(e) => TransactionOutcome::Rollback(Err(e)),
+					}
+				})
+				.map_err(|()| Error::<T>::FailedToProcessAccount)??
+			},
+			PalletEventName::Multisig => Self::do_receive_multisig(decode(payload)?)?,
+			PalletEventName::Claims => Self::do_process_claims(decode(payload)?)?,
+			PalletEventName::ProxyProxies => Self::do_receive_proxy(decode(payload)?)?,
+			PalletEventName::ProxyAnnouncements =>
+				Self::do_receive_proxy_announcement(decode(payload)?)?,
+			PalletEventName::Vesting => Self::do_process_vesting_schedule(decode(payload)?)?,
+			PalletEventName::Crowdloan => Self::do_process_crowdloan_message(decode(payload)?)?,
+			PalletEventName::ParaReserves =>
+				Self::do_process_para_reserves_message(decode(payload)?)?,
+			PalletEventName::BagsList => Self::do_receive_bags_list_message(decode(payload)?)?,
+			PalletEventName::PreimageRequestStatus =>
+				Self::do_receive_preimage_request_status(decode(payload)?)?,
+			PalletEventName::PreimageLegacyStatus =>
+				Self::do_receive_preimage_legacy_status(decode(payload)?)?,
+			PalletEventName::ReferendaReferendums => {
+				let (id, referendum): (u32, RcReferendumInfoOf<T, ()>) = decode(payload)?;
+				Self::do_receive_referendum(id, referendum)?
+			},
+			_ => return Err(Error::<T>::QuarantineNotSupported.into()),
+		}
+
+		Ok(())
+	}
+
+	/// Weight of integrating a quarantined item of `pallet` with an encoded size of `len`.
+	pub fn weight_apply_quarantined(pallet: &PalletEventName, len: u32) -> Weight {
+		let integrate = match pallet {
+			PalletEventName::Balances => T::AhWeightInfo::receive_accounts(1),
+			PalletEventName::Multisig => T::AhWeightInfo::receive_multisigs(1),
+			PalletEventName::Claims => T::AhWeightInfo::receive_claims(1),
+			PalletEventName::ProxyProxies => T::AhWeightInfo::receive_proxy_proxies(1),
+			PalletEventName::ProxyAnnouncements => T::AhWeightInfo::receive_proxy_announcements(1),
+			PalletEventName::Vesting => T::AhWeightInfo::receive_vesting_schedules(1),
+			PalletEventName::Crowdloan => T::AhWeightInfo::receive_crowdloan_messages(1),
+			PalletEventName::ParaReserves => T::AhWeightInfo::receive_para_reserves_messages(1),
+			PalletEventName::BagsList => T::AhWeightInfo::receive_bags_list_messages(1),
+			PalletEventName::PreimageRequestStatus =>
+				T::AhWeightInfo::receive_preimage_request_status(1),
+			PalletEventName::PreimageLegacyStatus =>
+				T::AhWeightInfo::receive_preimage_legacy_status(1),
+			PalletEventName::ReferendaReferendums =>
+				T::AhWeightInfo::receive_single_active_referendums(len),
+			// rejected by `do_apply_quarantined` after reading the migration stage.
+			_ => return T::DbWeight::get().reads(1),
+		};
+
+		integrate.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+}
+
+/// Decode a quarantined item from its whole `payload`.
+fn decode_quarantined<T: Config, I: Decode>(mut payload: &[u8]) -> Result<I, DispatchError> {
+	I::decode_all(&mut payload).map_err(|_| Error::<T>::FailedToDecodeQuarantined.into())
+}
@@ -0,0 +1,146 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reconciliation of the migrated balances at the end of the migration.
+//!
+//! The Relay Chain reports the balance, holds and vesting schedules that it sent in
+//! [`MigrationFinishedData`]. The Asset Hub compares them against the balance that it minted and
+//! the holds and vesting schedules that it integrated, tracked in [`AhMigratedTotals`]. Both sides
+//! count the same items, so only items that failed to integrate and were quarantined, see
+//! [`quarantine`], show up as a discrepancy.
+//!
+//! The increase of the total issuance of the Asset Hub over the migration is reported as well, but
+//! not compared, since it also contains the fees and burns of the Asset Hub during the migration.
+//!
+//! The result is stored in [`Reconciliation`] and emitted with [`Event::ReconciliationReport`].
+//!
+//! If the largest discrepancy exceeds [`Config::ReconciliationThreshold`], the Asset Hub stays in
+//! the `CoolOff` stage until it is moved on with [`Pallet::force_set_stage`].
+
+use crate::*;
+use pallet_rc_migrator::types::MAX_MIGRATED_HOLD_REASONS;
+use sp_runtime::Saturating;
+
+/// The comparison of the totals reported by the Relay Chain with those of the Asset Hub.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct BalanceReconciliation<HoldReason, Balance> {
+	/// The free and reserved balance of the accounts sent by the Relay Chain.
+	pub rc_migrated: Balance,
+	/// The balance minted into the integrated accounts on the Asset Hub.
+	pub ah_minted: Balance,
+	/// The increase of the total issuance of the Asset Hub over the migration.
+	///
+	/// Not compared, since it also contains the fees and burns of the Asset Hub.
+	pub ah_issuance_delta: Balance,
+	/// The amount put on hold per reason, sent by the Relay Chain and held on the Asset Hub.
+	pub holds: BoundedVec<(HoldReason, Balance, Balance), ConstU32<MAX_MIGRATED_HOLD_REASONS>>,
+	/// The locked amount of the vesting schedules sent by the Relay Chain.
+	pub rc_vesting_locked: Balance,
+	/// The locked amount of the vesting schedules integrated on the Asset Hub.
+	pub ah_vesting_locked: Balance,
+	/// The largest difference between two compared totals.
+	pub max_discrepancy: Balance,
+	/// Whether `max_discrepancy` exceeds the [`Config::ReconciliationThreshold`].
+	pub exceeds_threshold: bool,
+}
+
+pub type BalanceReconciliationOf<T> = BalanceReconciliation<
+	<T as Config>::PortableHoldReason,
+	<T as pallet_balances::Config>::Balance,
+>;
+
+impl<T: Config> Pallet<T> {
+	/// Compare the totals reported by the Relay Chain in `data` with those of the Asset Hub.
+	///
+	/// `ah_issuance_delta` is the increase of the total issuance before the checking account was
+	/// corrected by [`Self::finish_accounts_migration`].
+	pub fn reconcile_balances(
+		data: &MigrationFinishedData<T::Balance, T::PortableHoldReason>,
+		ah_issuance_delta: T::Balance,
+	) -> BalanceReconciliationOf<T> {
+		let ah_totals = AhMigratedTotals::<T>::take();
+		let difference = |a: T::Balance, b: T::Balance| a.max(b).saturating_sub(a.min(b));
+
+		let reasons = data.rc_totals.holds.iter().chain(ah_totals.holds.iter()).map(|(id, _)| id);
+		let mut holds = Vec::new();
+		for reason in reasons {
+			if holds.iter().all(|(id, _, _)| id != reason) {
+				holds.push((
+					reason.clone(),
+					data.rc_totals.held(reason),
+					ah_totals.held(reason),
+				));
+			}
+		}
+
+		let max_discrepancy = holds
+			.iter()
+			.map(|(_, rc, ah)| difference(*rc, *ah))
+			.chain([
+				difference(data.rc_totals.balance, ah_totals.balance),
+				difference(data.rc_totals.vesting_locked, ah_totals.vesting_locked),
+			])
+			.max()
+			.unwrap_or_default();
+
+		BalanceReconciliation {
+			rc_migrated: data.rc_totals.balance,
+			ah_minted: ah_totals.balance,
+			ah_issuance_delta,
+			holds: BoundedVec::truncate_from(holds),
+			rc_vesting_locked: data.rc_totals.vesting_locked,
+			ah_vesting_locked: ah_totals.vesting_locked,
+			max_discrepancy,
+			exceeds_threshold: T::ReconciliationThreshold::get()
+				.is_some_and(|threshold| max_discrepancy > threshold),
+		}
+	}
+
+	/// Reconcile the migration, store the report and emit it.
+	pub fn do_reconcile_balances(
+		data: &MigrationFinishedData<T::Balance, T::PortableHoldReason>,
+		ah_issuance_delta: T::Balance,
+	) {
+		let report = Self::reconcile_balances(data, ah_issuance_delta);
+		if report.exceeds_threshold {
+			log::error!(
+				target: LOG_TARGET,
+				"Balance reconciliation exceeds the threshold, staying in cool-off: {report:?}"
+			);
+		} else {
+			log::info!(target: LOG_TARGET, "Balance reconciliation: {report:?}");
+		}
+
+		Reconciliation::<T>::put(&report);
+		Self::deposit_event(Event::ReconciliationReport { report });
+	}
+
+	/// Whether the reconciliation holds the migration in the `CoolOff` stage.
+	pub fn reconciliation_blocks_completion() -> bool {
+		Reconciliation::<T>::get().is_some_and(|report| report.exceeds_threshold)
+	}
+}
@@ -30,11 +30,7 @@ impl<T: Config> Pallet<T> {
 			Self::do_receive_recovery_message(message.clone());
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Recovery,
-			count_good: messages.len() as u32,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::Recovery, messages.len() as u32, 0);
 
 		Ok(())
 	}
@@ -160,11 +160,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ReferendaReferendums,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::ReferendaReferendums, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good} referendums");
 
 		Ok(())
@@ -178,7 +174,13 @@ impl<T: Config> Pallet<T> {
 
 		let referendum: AhReferendumInfoOf<T, ()> = match referendum {
 			ReferendumInfo::Ongoing(status) => {
-				let cancel_referendum = |id, status: RcReferendumStatusOf<T, ()>| {
+				let cancel_referendum = |id, status: RcReferendumStatusOf<T, ()>, error: Error<T>| {
+					// keep the original referendum to be able to restore it.
+					Self::quarantine(
+						PalletEventName::ReferendaReferendums,
+						&(id, ReferendumInfo::Ongoing(status.clone())),
+						error,
+					);
 					let now = <T as Config>::RcBlockNumberProvider::current_block_number();
 					ReferendumInfoFor::<T, ()>::insert(
 						id,
@@ -199,7 +201,7 @@ impl<T: Config> Pallet<T> {
 							"Failed to convert RC origin to AH origin for referendum {}",
 							id
 						);
-						cancel_referendum(id, status);
+						cancel_referendum(id, status, Error::<T>::FailedToConvertType);
 						return Ok(());
 					},
 				};
@@ -208,7 +210,7 @@ impl<T: Config> Pallet<T> {
 					proposal
 				} else {
 					log::error!(target: LOG_TARGET, "Failed to convert RC call to AH call for referendum {id}");
-					cancel_referendum(id, status);
+					cancel_referendum(id, status, Error::<T>::FailedToConvertCall);
 					return Ok(());
 				};
 
@@ -258,11 +260,7 @@ impl<T: Config> Pallet<T> {
 			log::debug!(target: LOG_TARGET, "Referendum {id} integrated");
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ReferendaMetadata,
-			count_good: count,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::ReferendaMetadata, count, 0);
 		log::info!(target: LOG_TARGET, "Processed {count} metadata");
 
 		Ok(())
@@ -295,11 +293,7 @@ impl<T: Config> Pallet<T> {
 			});
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::ReferendaValues,
-			count_good: 1,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::ReferendaValues, 1, 0);
 		log::info!(target: LOG_TARGET, "Referenda pallet values integrated");
 		Ok(())
 	}
@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replay of a message log recorded with [`pallet_rc_migrator::message_log`].
+//!
+//! Dispatches the logged calls into an Asset Hub state snapshot, like the `Transact` instructions
+//! of the Relay Chain messages would, without running the Relay Chain. A range of the log can be
+//! replayed on its own to bisect a failing integration by message.
+//!
+//! ```ignore
+//! let messages = read_message_log("migration.ahmlog")?;
+//! ah_ext.execute_with(|| {
+//! 	for replayed in replay_messages::<AhRuntime>(&messages, 0..messages.len()) {
+//! 		println!("{replayed}");
+//! 	}
+//! });
+//! ```
+//!
+//! The calls are dispatched with the root origin in the current block. Hooks do not run between
+//! them.
+
+use crate::*;
+use codec::DecodeAll;
+use core::{fmt, ops::Range};
+use frame_support::traits::GetCallMetadata;
+pub use pallet_rc_migrator::message_log::*;
+use sp_runtime::traits::Dispatchable;
+
+/// The outcome of a replayed message.
+#[derive(Debug)]
+pub struct ReplayedMessage {
+	/// The index of the message in the log.
+	pub index: usize,
+	/// The Relay Chain block in which the message was sent.
+	pub rc_block: u32,
+	/// The name of the dispatched call, if it could be decoded.
+	pub call: Option<&'static str>,
+	/// The result of the dispatch.
+	pub result: DispatchResult,
+}
+
+impl fmt::Display for ReplayedMessage {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"#{} (RC block {}) {}: {:?}",
+			self.index,
+			self.rc_block,
+			self.call.unwrap_or("<undecodable>"),
+			self.result
+		)
+	}
+}
+
+/// Dispatch the messages of `range` of the log and return their outcomes.
+///
+/// Must be called in the externalities of the Asset Hub.
+pub fn replay_messages<T: Config>(
+	messages: &[LoggedMessage],
+	range: Range<usize>,
+) -> Vec<ReplayedMessage>
+where
+	<T as frame_system::Config>::RuntimeCall: GetCallMetadata,
+{
+	let start = range.start;
+	messages[range]
+		.iter()
+		.enumerate()
+		.map(|(offset, message)| {
+			let (call, result) = replay_message::<T>(&message.call);
+			ReplayedMessage { index: start + offset, rc_block: message.rc_block, call, result }
+		})
+		.collect()
+}
+
+/// Decode and dispatch a single logged call.
+fn replay_message<T: Config>(encoded: &[u8]) -> (Option<&'static str>, DispatchResult)
+where
+	<T as frame_system::Config>::RuntimeCall: GetCallMetadata,
+{
+	let call = match <T as frame_system::Config>::RuntimeCall::decode_all(&mut &encoded[..]) {
+		Ok(call) => call,
+		Err(err) => {
+			log::error!(target: LOG_TARGET, "Failed to decode logged call: {err:?}");
+			return (None, Err(DispatchError::Other("Undecodable call")));
+		},
+	};
+
+	let name = call.get_call_metadata().function_name;
+	let result = call
+		.dispatch(frame_system::RawOrigin::Root.into())
+		.map(|_| ())
+		.map_err(|err| err.error);
+	(Some(name), result)
+}
@@ -0,0 +1,98 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Hub side of the off-chain migration report, see [`pallet_rc_migrator::report`].
+//!
+//! ```ignore
+//! let mut report = MigrationReport::default();
+//! report.push(pallet_report::<
+//! 	pallet_rc_migrator::indices::IndicesMigrator<RcRuntime>,
+//! 	pallet_rc_migrator::indices::IndicesMigrator<AhRuntime>,
+//! >("Indices", &mut snapshots));
+//! std::fs::write("report.json", report.to_json())?;
+//! println!("{report}");
+//! ```
+//!
+//! The `migration-report` tool reports the storage changes of a list of pallets between snapshots
+//! without a runtime, see `src/bin/migration_report.rs`.
+
+use crate::types::AhMigrationCheck;
+pub use pallet_rc_migrator::report::*;
+use pallet_rc_migrator::types::RcMigrationCheck;
+use sp_io::TestExternalities;
+
+/// State snapshots of both chains before and after the migration.
+pub struct Snapshots {
+	/// Relay Chain state before the migration.
+	pub rc_pre: TestExternalities,
+	/// Relay Chain state after the migration.
+	pub rc_post: TestExternalities,
+	/// Asset Hub state before the migration.
+	pub ah_pre: TestExternalities,
+	/// Asset Hub state after the migration.
+	pub ah_post: TestExternalities,
+}
+
+/// Run the checks of both chains for a single pallet and report the results.
+///
+/// The Asset Hub checks are skipped if the Relay Chain `pre_check` fails, since they take its
+/// payload as input. The storage changes are reported in any case.
+pub fn pallet_report<Rc, Ah>(pallet: &str, snapshots: &mut Snapshots) -> PalletReport
+where
+	Rc: RcMigrationCheck,
+	Ah: AhMigrationCheck<RcPrePayload = Rc::RcPrePayload>,
+	Rc::RcPrePayload: PayloadItems,
+	Ah::AhPrePayload: PayloadItems,
+{
+	let (report, rc_pre_payload) =
+		PalletReport::relay_chain::<Rc>(pallet, &mut snapshots.rc_pre, &mut snapshots.rc_post);
+	let storage = Some(storage_diff(pallet, &mut snapshots.ah_pre, &mut snapshots.ah_post));
+	let Some(rc_pre_payload) = rc_pre_payload else {
+		return report.with_asset_hub(ChainReport { storage, ..ChainReport::skipped() });
+	};
+
+	let (pre_check, ah_pre_payload) =
+		CheckOutcome::run(&mut snapshots.ah_pre, || Ah::pre_check(rc_pre_payload.clone()));
+	let post_check = match ah_pre_payload.clone() {
+		Some(ah_pre_payload) =>
+			CheckOutcome::run(&mut snapshots.ah_post, || {
+				Ah::post_check(rc_pre_payload, ah_pre_payload)
+			})
+			.0,
+		None => CheckOutcome::Skipped,
+	};
+
+	report.with_asset_hub(ChainReport {
+		pre: ah_pre_payload.as_ref().map(PayloadEntries::of),
+		pre_check,
+		post_check,
+		storage,
+	})
+}
+
+/// Report the storage changes of a single pallet on both chains without running any checks.
+pub fn pallet_storage_report(pallet: &str, snapshots: &mut Snapshots) -> PalletReport {
+	PalletReport::new(
+		pallet,
+		ChainReport::storage_only(pallet, &mut snapshots.rc_pre, &mut snapshots.rc_post),
+	)
+	.with_asset_hub(ChainReport::storage_only(
+		pallet,
+		&mut snapshots.ah_pre,
+		&mut snapshots.ah_post,
+	))
+}
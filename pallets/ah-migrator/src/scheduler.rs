@@ -52,11 +52,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Scheduler,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Scheduler, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good} scheduler messages");
 
 		Ok(())
@@ -142,11 +138,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::SchedulerAgenda,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::SchedulerAgenda, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good} scheduler agenda messages");
 
 		Ok(())
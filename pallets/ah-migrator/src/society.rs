@@ -34,11 +34,7 @@ impl<T: Config> Pallet<T> {
 			Self::do_receive_society_message(message.clone());
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Society,
-			count_good: messages.len() as u32,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::Society, messages.len() as u32, 0);
 
 		log::info!("Processed {} society messages", messages.len());
 
@@ -41,17 +41,16 @@ impl<T: Config> Pallet<T> {
 		// (nodes are added to tail), creating different prev/next relationships even with
 		// identical scores. This breaks post-check validation which expects structural match.
 		for message in messages {
-			match Self::do_receive_bags_list_message(message) {
+			match Self::do_receive_bags_list_message(message.clone()) {
 				Ok(_) => good += 1,
-				Err(_) => bad += 1,
+				Err(e) => {
+					bad += 1;
+					Self::quarantine(PalletEventName::BagsList, &message, e);
+				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::BagsList,
-			count_good: good as u32,
-			count_bad: bad as u32,
-		});
+		Self::note_batch_processed(PalletEventName::BagsList, good as u32, bad as u32);
 
 		Ok(())
 	}
@@ -65,11 +65,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::DelegatedStaking,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::DelegatedStaking, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good} delegated staking messages");
 
 		Ok(())
@@ -185,11 +185,7 @@ impl<T: Config> Pallet<T> {
 			good += 1;
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::NomPools,
-			count_good: good as u32,
-			count_bad: 0,
-		});
+		Self::note_batch_processed(PalletEventName::NomPools, good as u32, 0);
 		Ok(())
 	}
 
@@ -39,11 +39,7 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Treasury,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Treasury, count_good, count_bad);
 		log::info!(target: LOG_TARGET, "Processed {count_good} treasury messages");
 
 		Ok(())
@@ -19,6 +19,7 @@ use super::*;
 use pallet_rc_migrator::vesting::{
 	BalanceOf, GenericVestingInfo, RcVestingSchedule, VestingMigrator,
 };
+use sp_runtime::{SaturatedConversion, Saturating};
 
 impl<T: Config> Pallet<T> {
 	pub fn do_receive_vesting_schedules(
@@ -33,20 +34,17 @@ impl<T: Config> Pallet<T> {
 		let (mut count_good, mut count_bad) = (0, 0);
 
 		for message in messages {
-			match Self::do_process_vesting_schedule(message) {
+			match Self::do_process_vesting_schedule(message.clone()) {
 				Ok(()) => count_good += 1,
 				Err(e) => {
 					count_bad += 1;
 					log::error!(target: LOG_TARGET, "Error while integrating vesting: {e:?}");
+					Self::quarantine(PalletEventName::Vesting, &message, e);
 				},
 			}
 		}
 
-		Self::deposit_event(Event::BatchProcessed {
-			pallet: PalletEventName::Vesting,
-			count_good,
-			count_bad,
-		});
+		Self::note_batch_processed(PalletEventName::Vesting, count_good, count_bad);
 
 		Ok(())
 	}
@@ -62,7 +60,9 @@ impl<T: Config> Pallet<T> {
 			defensive!("We disabled vesting, looks like someone used it. Manually verify this and then remove this defensive assert.");
 		}
 
+		let mut locked: T::Balance = Zero::zero();
 		for schedule in message.schedules {
+			locked.saturating_accrue(schedule.locked().saturated_into());
 			ah_schedules
 				.try_push(schedule)
 				.defensive()
@@ -70,6 +70,9 @@ impl<T: Config> Pallet<T> {
 		}
 
 		pallet_vesting::Vesting::<T>::insert(&translated_account, &ah_schedules);
+		AhMigratedTotals::<T>::mutate(|totals| totals.vesting_locked.saturating_accrue(locked));
+		let schedules = ah_schedules.len() as u32;
+		Self::audit(&translated_account, audit::AccountComponent::Vesting { schedules, locked });
 		log::debug!(target: LOG_TARGET, "Integrated vesting schedule for {:?}, len {}", translated_account, ah_schedules.len());
 
 		Ok(())
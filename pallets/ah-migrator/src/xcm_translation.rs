@@ -115,3 +115,111 @@ impl<T: Config> Pallet<T> {
 		Ok(location)
 	}
 }
+
+/// Reanchors an XCM used in `execute` to the Asset Hub.
+///
+/// It iterates over all instructions, changing locations and assets when needed.
+///
+/// Only some instructions are mapped, the rest will throw an error.
+#[allow(clippy::result_unit_err)]
+pub fn reanchor_xcm(
+	xcm: Xcm<()>,
+	ah_location: &Location,
+	universal_location: &InteriorLocation,
+) -> Result<Xcm<()>, ()> {
+	let reanchored_instructions: Result<Vec<_>, ()> = xcm
+		.0
+		.into_iter()
+		.map(|instruction| reanchor_instruction(instruction, ah_location, universal_location))
+		.collect();
+
+	Ok(Xcm(reanchored_instructions?))
+}
+
+fn reanchor_instruction(
+	instruction: Instruction<()>,
+	ah_location: &Location,
+	universal_location: &InteriorLocation,
+) -> Result<Instruction<()>, ()> {
+	use Instruction::*;
+
+	// We only map a particular subset of instructions, to keep the logic minimal.
+	match instruction {
+		WithdrawAsset(assets) => {
+			let reanchored_assets =
+				assets.reanchored(ah_location, universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor assets: {err:?}");
+				})?;
+			Ok(WithdrawAsset(reanchored_assets))
+		},
+		PayFees { asset } => {
+			let reanchored_asset =
+				asset.reanchored(ah_location, universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor asset: {err:?}");
+				})?;
+			Ok(PayFees { asset: reanchored_asset })
+		},
+		DepositAsset { assets, beneficiary } => {
+			let reanchored_beneficiary = if is_local_account(&beneficiary) {
+				// Local accounts (parents: 0, AccountId32/AccountKey20) don't need reanchoring.
+				beneficiary
+			} else {
+				beneficiary.reanchored(ah_location, universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor beneficiary: {err:?}");
+				})?
+			};
+			Ok(DepositAsset { assets, beneficiary: reanchored_beneficiary })
+		},
+		DepositReserveAsset { assets, dest, xcm } => {
+			let reanchored_dest =
+				dest.reanchored(ah_location, universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor dest: {err:?}");
+				})?;
+			let reanchored_assets = reanchor_asset_filter(assets, ah_location, universal_location)?;
+			// The nested xcm is already from the perspective of the dest, so no reanchoring needed.
+			Ok(DepositReserveAsset { assets: reanchored_assets, dest: reanchored_dest, xcm })
+		},
+		RefundSurplus => Ok(RefundSurplus),
+
+		// All other instructions are not supported in the migration.
+		instruction => {
+			log::error!(
+				target: LOG_TARGET,
+				"Unsupported XCM instruction in migration: {instruction:?}",
+			);
+			Err(())
+		},
+	}
+}
+
+fn is_local_account(location: &Location) -> bool {
+	// Check if this is a local account (parents: 0, AccountId32/AccountKey20)
+	if location.parents != 0 || location.interior.len() != 1 {
+		return false;
+	}
+
+	matches!(
+		location.interior.first(),
+		Some(Junction::AccountId32 { .. }) | Some(Junction::AccountKey20 { .. })
+	)
+}
+
+fn reanchor_asset_filter(
+	filter: AssetFilter,
+	ah_location: &Location,
+	universal_location: &InteriorLocation,
+) -> Result<AssetFilter, ()> {
+	match filter {
+		AssetFilter::Definite(assets) => {
+			let reanchored_assets =
+				assets.reanchored(ah_location, universal_location).map_err(|err| {
+					log::error!(target: LOG_TARGET, "Failed to reanchor asset filter assets: {err:?}");
+				})?;
+			Ok(AssetFilter::Definite(reanchored_assets))
+		},
+		AssetFilter::Wild(wild) => {
+			// Wild filters don't contain specific locations, so pass through unchanged.
+			Ok(AssetFilter::Wild(wild))
+		},
+	}
+}
@@ -16,6 +16,7 @@
 use crate::*;
 use frame_benchmarking::{account, v2::*};
 use frame_support::{dispatch::RawOrigin, traits::Currency};
+use sp_runtime::traits::StaticLookup;
 
 #[benchmarks]
 pub mod benchmarks {
@@ -82,6 +83,45 @@ pub mod benchmarks {
 		assert_eq!(RcCrowdloanReserve::<T>::get((block, para_id, &sender)), None);
 	}
 
+	#[benchmark]
+	fn claim_derived_sovereign_account() -> Result<(), BenchmarkError> {
+		T::BenchmarkHelper::complete_migration();
+		let origin =
+			T::SiblingParaOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let para_id = T::SiblingParaOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let derivation_index = 0;
+		let parent = Pallet::<T>::rc_sovereign_account(para_id);
+		let from = derivative_account_id(parent.clone(), derivation_index);
+		let (to, _) = Pallet::<T>::try_rc_sovereign_derived_to_ah(&from, &parent, derivation_index)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		// Worst case: the account holds balance for every reason and has the maximum of proxies.
+		let ed = <T::Currency as Currency<_>>::minimum_balance();
+		let _ = T::Currency::deposit_creating(&from, ed * 1_000_000_000);
+		let hold_reasons = T::BenchmarkHelper::hold_reasons();
+		for reason in &hold_reasons {
+			<T::Currency as MutateHold<_>>::hold(reason, &from, ed)?;
+		}
+		let max_proxies = <T as pallet_proxy::Config>::MaxProxies::get();
+		for i in 0..max_proxies {
+			pallet_proxy::Pallet::<T>::add_proxy(
+				RawOrigin::Signed(from.clone()).into(),
+				T::Lookup::unlookup(account("delegate", i, 0)),
+				Default::default(),
+				Default::default(),
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, derivation_index);
+
+		assert_eq!(pallet_proxy::Proxies::<T>::get(&to).0.len() as u32, max_proxies);
+		assert_eq!(pallet_balances::Holds::<T>::get(&to).len(), hold_reasons.len());
+		assert!(pallet_balances::Holds::<T>::get(&from).is_empty());
+		Ok(())
+	}
+
 	#[cfg(feature = "std")]
 	pub fn test_unreserve_lease_deposit<T: Config>() {
 		_unreserve_lease_deposit::<T>(true)
@@ -97,5 +137,10 @@ pub mod benchmarks {
 		_unreserve_crowdloan_reserve::<T>(true)
 	}
 
+	#[cfg(feature = "std")]
+	pub fn test_claim_derived_sovereign_account<T: Config>() {
+		_claim_derived_sovereign_account::<T>(true).unwrap()
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
 }
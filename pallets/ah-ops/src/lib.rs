@@ -40,16 +40,17 @@ pub mod weights;
 pub use pallet::*;
 pub use weights::WeightInfo;
 
-use codec::DecodeAll;
+use codec::{DecodeAll, Encode};
 use cumulus_primitives_core::ParaId;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		fungible::{InspectFreeze, Mutate, MutateFreeze, MutateHold, Unbalanced},
+		fungible::{Inspect, InspectFreeze, Mutate, MutateFreeze, MutateHold, Unbalanced},
 		fungibles::{Inspect as FungiblesInspect, Mutate as FungiblesMutate},
-		tokens::Preservation,
-		Defensive, LockableCurrency, ReservableCurrency,
+		tokens::{Fortitude, Precision, Preservation, Restriction},
+		BalanceStatus, Defensive, LockableCurrency, ReservableCurrency,
 	},
+	weights::WeightMeter,
 };
 use frame_system::pallet_prelude::*;
 use pallet_balances::AccountData;
@@ -57,8 +58,9 @@ use sp_application_crypto::ByteArray;
 use sp_core::blake2_256;
 use sp_runtime::{
 	traits::{BlockNumberProvider, TrailingZeroInput},
-	AccountId32,
+	AccountId32, DispatchError,
 };
+
 use sp_std::prelude::*;
 
 /// The log target of this pallet.
@@ -67,14 +69,42 @@ pub const LOG_TARGET: &str = "runtime::ah-ops";
 pub type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
 pub type DerivationIndex = u16;
 
+/// Maximum number of distinct blocks in the [`UnlockBlocks`] schedule.
+///
+/// Leases end at the end of a lease period and a para can not win more than eight periods ahead,
+/// and all auction reserves share a single block. Paras that do not fit are not scheduled and
+/// can be released with the manual extrinsics, see [`Event::UnlockNotScheduled`].
+pub const MAX_UNLOCK_BLOCKS: u32 = 64;
+
+/// Sets up the runtime state that the benchmarks of this pallet need.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<HoldReason> {
+	/// The hold reasons that a migrated account can hold balance for at the same time.
+	fn hold_reasons() -> Vec<HoldReason>;
+
+	/// Complete the Asset Hub migration, see [`Config::MigrationCompletion`].
+	fn complete_migration();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<HoldReason> BenchmarkHelper<HoldReason> for () {
+	fn hold_reasons() -> Vec<HoldReason> {
+		Vec::new()
+	}
+
+	fn complete_migration() {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use frame_support::storage::with_storage_layer;
 
 	#[pallet::config]
 	pub trait Config:
 		frame_system::Config<AccountData = AccountData<u128>, AccountId = AccountId32>
 		+ pallet_balances::Config<Balance = u128>
+		+ pallet_proxy::Config
 		+ pallet_timestamp::Config<Moment = u64> // Needed for testing
 	{
 		/// The overarching event type.
@@ -108,8 +138,18 @@ pub mod pallet {
 		/// The post-migration treasury account address.
 		type TreasuryPostMigrationAccount: Get<Self::AccountId>;
 
+		/// Origin of a sibling parachain, as sent over XCM from its sibling location.
+		///
+		/// Used by parachains to claim their derived accounts that were not translated by the
+		/// migration.
+		type SiblingParaOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = ParaId>;
+
 		/// The Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+
+		/// Helper to set up the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::RuntimeHoldReason>;
 	}
 
 	/// Amount of balance that was reserved for winning a lease auction.
@@ -183,6 +223,32 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Paras whose reserves and contributions are released automatically in `on_idle`.
+	///
+	/// Keys:
+	/// - Block number after which the reserves of the para can be released. The same block is the
+	///   first key of the para's entries in [`RcLeaseReserve`], [`RcCrowdloanContribution`] and
+	///   [`RcCrowdloanReserve`].
+	/// - The para_id of the lease slot or crowdloan.
+	///
+	/// Entries are removed once everything was released. The entries of a para that fails to be
+	/// released are left for the manual extrinsics.
+	#[pallet::storage]
+	pub type UnlockSchedule<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		ParaId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The blocks of the [`UnlockSchedule`] in ascending order.
+	#[pallet::storage]
+	pub type UnlockBlocks<T: Config> =
+		StorageValue<_, BoundedVec<BlockNumberFor<T>, ConstU32<MAX_UNLOCK_BLOCKS>>, ValueQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Either no lease deposit or already unreserved.
@@ -207,6 +273,8 @@ pub mod pallet {
 		MigrationNotCompleted,
 		/// The balance is zero.
 		ZeroBalance,
+		/// The account that the proxies should be moved into already has proxies.
+		ProxiesExist,
 	}
 
 	#[pallet::event]
@@ -238,6 +306,46 @@ pub mod pallet {
 			/// Set if this account was derived from a para sovereign account.
 			derivation_index: Option<DerivationIndex>,
 		},
+
+		/// The reserves and contributions of a para were released by the unlock schedule.
+		UnlockScheduleReleased {
+			/// The block after which the reserves could be released.
+			block: BlockNumberFor<T>,
+			/// The para_id of the lease slot or crowdloan.
+			para_id: ParaId,
+		},
+
+		/// Releasing the reserves or contributions of a para failed and was taken off the unlock
+		/// schedule. The remaining entries can still be released with the manual extrinsics.
+		UnlockScheduleFailed {
+			/// The block after which the reserves could be released.
+			block: BlockNumberFor<T>,
+			/// The para_id of the lease slot or crowdloan.
+			para_id: ParaId,
+			/// The account whose reserve or contribution could not be released.
+			account: T::AccountId,
+			/// The reason of the failure.
+			error: DispatchError,
+		},
+
+		/// The unlock schedule has no space for another block, so the reserves and contributions
+		/// of a para are not released automatically. They can still be released with the manual
+		/// extrinsics.
+		UnlockNotScheduled {
+			/// The block after which the reserves can be released.
+			block: BlockNumberFor<T>,
+			/// The para_id of the lease slot or crowdloan.
+			para_id: ParaId,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(limit);
+			Self::release_scheduled_unlocks(&mut meter);
+			meter.consumed()
+		}
 	}
 
 	#[pallet::pallet]
@@ -344,6 +452,38 @@ pub mod pallet {
 
 			Ok(Pays::No.into())
 		}
+
+		/// Move a derived account of a parachain sovereign account from its RC-format address to
+		/// its sibling-format address.
+		///
+		/// The migration only translates the derived accounts that are known at build time. A
+		/// parachain whose derived account at `derivation_index` was missed can claim it by
+		/// dispatching this call over XCM from its sibling location. The free balance, holds and
+		/// proxies of the RC-format account are moved to the account derived from the sibling
+		/// sovereign account.
+		///
+		/// Locked, frozen and reserved balance that is not a proxy deposit stays with the
+		/// RC-format account. Proxies that delegate to the RC-format account are not changed.
+		///
+		/// This call can only be called after the migration is completed.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_derived_sovereign_account())]
+		pub fn claim_derived_sovereign_account(
+			origin: OriginFor<T>,
+			derivation_index: DerivationIndex,
+		) -> DispatchResultWithPostInfo {
+			let para_id = T::SiblingParaOrigin::ensure_origin(origin)?;
+
+			ensure!(T::MigrationCompletion::get(), Error::<T>::MigrationNotCompleted);
+
+			let parent = Self::rc_sovereign_account(para_id);
+			let from = derivative_account_id(parent.clone(), derivation_index);
+			let (to, _) = Self::try_rc_sovereign_derived_to_ah(&from, &parent, derivation_index)?;
+
+			Self::do_migrate_derived_account(para_id, from, to, derivation_index)?;
+
+			Ok(Pays::No.into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -434,6 +574,130 @@ pub mod pallet {
 			contrib_iter.next().is_none()
 		}
 
+		/// Release the reserves and contributions of `para_id` in `on_idle` once `block` passed.
+		///
+		/// The entries are released in the same order as they must be released manually:
+		/// contributions, lease reserves and finally the crowdloan reserve. Returns `false` and
+		/// emits [`Event::UnlockNotScheduled`] if [`UnlockBlocks`] is full.
+		pub fn schedule_unlock(block: BlockNumberFor<T>, para_id: ParaId) -> bool {
+			let scheduled =
+				UnlockBlocks::<T>::try_mutate(|blocks| match blocks.binary_search(&block) {
+					Ok(_) => Ok(()),
+					Err(index) => blocks.try_insert(index, block).map(|_| ()),
+				});
+			if scheduled.is_err() {
+				log::warn!(
+					target: LOG_TARGET,
+					"Unlock schedule is full, para {para_id:?} must be released manually after \
+						block {block:?}"
+				);
+				Self::deposit_event(Event::UnlockNotScheduled { block, para_id });
+				return false;
+			}
+			UnlockSchedule::<T>::insert(block, para_id, ());
+
+			true
+		}
+
+		/// Release the due entries of the [`UnlockSchedule`] until `meter` is exhausted.
+		pub fn release_scheduled_unlocks(meter: &mut WeightMeter) {
+			if meter.try_consume(T::DbWeight::get().reads_writes(2, 1)).is_err() ||
+				!T::MigrationCompletion::get()
+			{
+				return;
+			}
+			let now = T::RcBlockNumberProvider::current_block_number();
+			let mut blocks = UnlockBlocks::<T>::get();
+			let mut released_blocks = 0;
+
+			while let Some(&block) = blocks.get(released_blocks) {
+				if block > now || meter.try_consume(T::DbWeight::get().reads(1)).is_err() {
+					break;
+				}
+				match UnlockSchedule::<T>::iter_key_prefix(block).next() {
+					Some(para_id) =>
+						if !Self::release_para_unlocks(block, para_id, meter) {
+							break;
+						},
+					None => released_blocks += 1,
+				}
+			}
+
+			if released_blocks > 0 {
+				blocks.drain(..released_blocks);
+				UnlockBlocks::<T>::put(blocks);
+			}
+		}
+
+		/// Release the entries of `para_id` that are due after `block`.
+		///
+		/// Returns `false` if `meter` was exhausted before all entries were released.
+		fn release_para_unlocks(
+			block: BlockNumberFor<T>,
+			para_id: ParaId,
+			meter: &mut WeightMeter,
+		) -> bool {
+			loop {
+				if meter.try_consume(T::DbWeight::get().reads_writes(3, 1)).is_err() {
+					return false;
+				}
+
+				let (weight, account, release): (
+					Weight,
+					T::AccountId,
+					fn(BlockNumberFor<T>, T::AccountId, ParaId) -> Result<(), Error<T>>,
+				) = if let Some(account) =
+					RcCrowdloanContribution::<T>::iter_key_prefix((block, para_id)).next()
+				{
+					(
+						<T as Config>::WeightInfo::withdraw_crowdloan_contribution(),
+						account,
+						Self::do_withdraw_crowdloan_contribution,
+					)
+				} else if let Some(account) =
+					RcLeaseReserve::<T>::iter_key_prefix((block, para_id)).next()
+				{
+					(
+						<T as Config>::WeightInfo::unreserve_lease_deposit(),
+						account,
+						Self::do_unreserve_lease_deposit,
+					)
+				} else if let Some(account) =
+					RcCrowdloanReserve::<T>::iter_key_prefix((block, para_id)).next()
+				{
+					(
+						<T as Config>::WeightInfo::unreserve_crowdloan_reserve(),
+						account,
+						Self::do_unreserve_crowdloan_reserve,
+					)
+				} else {
+					UnlockSchedule::<T>::remove(block, para_id);
+					Self::deposit_event(Event::UnlockScheduleReleased { block, para_id });
+					return true;
+				};
+
+				if meter.try_consume(weight).is_err() {
+					return false;
+				}
+				if let Err(error) = with_storage_layer(|| {
+					release(block, account.clone(), para_id).map_err(DispatchError::from)
+				}) {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to release the reserve of {account:?} for para {para_id:?}: {error:?}"
+					);
+					UnlockSchedule::<T>::remove(block, para_id);
+					Self::deposit_event(Event::UnlockScheduleFailed {
+						block,
+						para_id,
+						account,
+						error,
+					});
+					return true;
+				}
+			}
+		}
+
 		/// Try to translate a Parachain sovereign account to the Parachain AH sovereign account.
 		///
 		/// Returns:
@@ -474,6 +738,85 @@ pub mod pallet {
 			Ok((ah_raw.into(), ParaId::from(para_id as u32)))
 		}
 
+		/// The sovereign account of `para_id` in its RC-format with the `para` type-ID.
+		pub fn rc_sovereign_account(para_id: ParaId) -> AccountId32 {
+			let mut rc_raw = [0u8; 32];
+			rc_raw[0..4].copy_from_slice(b"para");
+			rc_raw[4..8].copy_from_slice(&u32::from(para_id).encode());
+			rc_raw.into()
+		}
+
+		/// Move the free balance, holds and proxies of the derived account `from` to `to`.
+		pub fn do_migrate_derived_account(
+			para_id: ParaId,
+			from: T::AccountId,
+			to: T::AccountId,
+			derivation_index: DerivationIndex,
+		) -> DispatchResult {
+			ensure!(
+				<<T as Config>::Currency as Inspect<_>>::total_balance(&from) > 0,
+				Error::<T>::ZeroBalance
+			);
+
+			// The free balance goes first since the holds and reserves can only be moved into an
+			// existing account.
+			Self::transfer_reducible(&from, &to)?;
+
+			for hold in pallet_balances::Holds::<T>::get(&from) {
+				<T as Config>::Currency::transfer_on_hold(
+					&hold.id,
+					&from,
+					&to,
+					hold.amount,
+					Precision::Exact,
+					Restriction::OnHold,
+					Fortitude::Polite,
+				)?;
+			}
+
+			let (proxies, deposit) = pallet_proxy::Proxies::<T>::take(&from);
+			if !proxies.is_empty() {
+				ensure!(!pallet_proxy::Proxies::<T>::contains_key(&to), Error::<T>::ProxiesExist);
+				let missing = <T as pallet_proxy::Config>::Currency::repatriate_reserved(
+					&from,
+					&to,
+					deposit,
+					BalanceStatus::Reserved,
+				)?;
+				defensive_assert!(missing == Default::default());
+				pallet_proxy::Proxies::<T>::insert(&to, (proxies, deposit));
+			}
+
+			// Without holds and proxies the existential deposit can be moved as well.
+			Self::transfer_reducible(&from, &to)?;
+
+			log::info!(
+				target: LOG_TARGET,
+				"Migrated derived account {from:?} of para {para_id:?} to {to:?}"
+			);
+			Self::deposit_event(Event::SovereignMigrated {
+				para_id,
+				from,
+				to,
+				derivation_index: Some(derivation_index),
+			});
+
+			Ok(())
+		}
+
+		/// Transfer all the balance of `from` that can be transferred to `to`.
+		fn transfer_reducible(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+			let amount = <T as Config>::Currency::reducible_balance(
+				from,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			);
+			if amount > 0 {
+				<T as Config>::Currency::transfer(from, to, amount, Preservation::Expendable)?;
+			}
+			Ok(())
+		}
+
 		/// Same as `try_translate_rc_sovereign_to_ah` but for derived accounts.
 		///
 		/// The `from` and `to` arguments are the final account IDs that will be migrated. The
@@ -17,7 +17,7 @@
 use crate as pallet_ah_ops;
 use crate::*;
 use frame_support::derive_impl;
-use frame_system::EnsureSigned;
+use frame_system::{EnsureRootWithSuccess, EnsureSigned};
 use sp_core::H256;
 use sp_runtime::traits::{parameter_types, BlakeTwo256, IdentityLookup};
 
@@ -31,6 +31,7 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances,
 		AhOps: pallet_ah_ops,
 		Timestamp: pallet_timestamp,
+		Proxy: pallet_proxy,
 	}
 );
 
@@ -83,21 +84,42 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ();
+	type ProxyDepositBase = frame_support::traits::ConstU128<1>;
+	type ProxyDepositFactor = frame_support::traits::ConstU128<1>;
+	type MaxProxies = frame_support::traits::ConstU32<4>;
+	type WeightInfo = ();
+	type MaxPending = frame_support::traits::ConstU32<2>;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = frame_support::traits::ConstU128<1>;
+	type AnnouncementDepositFactor = frame_support::traits::ConstU128<1>;
+	type BlockNumberProvider = System;
+}
+
 parameter_types! {
 	pub const MigrationCompletion: bool = true;
 	pub TreasuryPreMigrationAccount: AccountId32 = AccountId32::from([1; 32]);
 	pub TreasuryPostMigrationAccount: AccountId32 = AccountId32::from([2; 32]);
+	pub SiblingParaId: ParaId = ParaId::from(2030);
 }
 
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type Fungibles = Assets;
-	type RcBlockNumberProvider = System; // Wrong but unused
+	type RcBlockNumberProvider = System; // Relay Chain block equals the local block in tests
 	type WeightInfo = ();
 	type MigrationCompletion = MigrationCompletion;
 	type TreasuryPreMigrationAccount = TreasuryPreMigrationAccount;
 	type TreasuryPostMigrationAccount = TreasuryPostMigrationAccount;
+	// Root acts as the sibling para in tests
+	type SiblingParaOrigin = EnsureRootWithSuccess<AccountId32, SiblingParaId>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 #[cfg(feature = "runtime-benchmarks")]
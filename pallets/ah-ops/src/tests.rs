@@ -131,3 +131,116 @@ fn sovereign_account_translation() {
 		}
 	}
 }
+
+#[test]
+fn unlock_schedule_releases_due_reserves() {
+	use crate::{mock::*, *};
+	use frame_support::traits::{fungible::Mutate, Hooks, ReservableCurrency};
+	use sp_runtime::BuildStorage;
+
+	let t = frame_system::GenesisConfig::<AssetHub>::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		let (bidder, depositor) = (AccountId32::from([3; 32]), AccountId32::from([4; 32]));
+		let para_id = ParaId::from(2000);
+		for who in [&bidder, &depositor] {
+			Balances::set_balance(who, 1_000);
+			Balances::reserve(who, 500).unwrap();
+		}
+
+		RcLeaseReserve::<AssetHub>::insert((10, para_id, &bidder), 500);
+		RcCrowdloanReserve::<AssetHub>::insert((10, para_id, &depositor), 500);
+		assert!(Pallet::<AssetHub>::schedule_unlock(10, para_id));
+		assert!(Pallet::<AssetHub>::schedule_unlock(20, ParaId::from(2001)));
+		assert_eq!(UnlockBlocks::<AssetHub>::get().into_inner(), vec![10, 20]);
+
+		// Not due yet.
+		System::set_block_number(9);
+		AhOps::on_idle(9, Weight::MAX);
+		assert_eq!(Balances::reserved_balance(&bidder), 500);
+
+		System::set_block_number(10);
+		AhOps::on_idle(10, Weight::MAX);
+		assert_eq!(Balances::reserved_balance(&bidder), 0);
+		assert_eq!(Balances::reserved_balance(&depositor), 0);
+		assert!(UnlockSchedule::<AssetHub>::get(10, para_id).is_none());
+		assert_eq!(UnlockBlocks::<AssetHub>::get().into_inner(), vec![20]);
+	});
+}
+
+#[test]
+fn claim_derived_sovereign_account_moves_balance_and_proxies() {
+	use crate::{mock::*, *};
+	use frame_support::{
+		assert_noop,
+		traits::{fungible::Mutate, ReservableCurrency},
+	};
+	use sp_runtime::BuildStorage;
+
+	let t = frame_system::GenesisConfig::<AssetHub>::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		// para 2030 index 0
+		let from =
+			AccountId32::from_str("14vtfeKAVKh1Jzb3s7e43SqZ3zB5MLsdCxZPoKDxeoCFKLu5").unwrap();
+		// sibl 2030 index 0
+		let to = AccountId32::from_str("5ETehspFKFNpBbe5DsfuziN6BWq5Qwp1J8qcTQQoAxwa7BsS").unwrap();
+		let delegate = AccountId32::from([3; 32]);
+
+		Balances::set_balance(&from, 1_000);
+		Proxy::add_proxy(RuntimeOrigin::signed(from.clone()), delegate.clone(), (), 0).unwrap();
+		assert_eq!(Balances::reserved_balance(&from), 2);
+
+		// Only the sibling para can claim its accounts.
+		assert_noop!(
+			AhOps::claim_derived_sovereign_account(RuntimeOrigin::signed(from.clone()), 0),
+			DispatchError::BadOrigin
+		);
+		AhOps::claim_derived_sovereign_account(RuntimeOrigin::root(), 0).unwrap();
+
+		assert_eq!(Balances::total_balance(&from), 0);
+		assert_eq!(Balances::free_balance(&to), 998);
+		assert_eq!(Balances::reserved_balance(&to), 2);
+		assert!(!pallet_proxy::Proxies::<AssetHub>::contains_key(&from));
+		assert_eq!(pallet_proxy::Proxies::<AssetHub>::get(&to).0[0].delegate, delegate);
+		System::assert_last_event(
+			Event::<AssetHub>::SovereignMigrated {
+				para_id: ParaId::from(2030),
+				from: from.clone(),
+				to,
+				derivation_index: Some(0),
+			}
+			.into(),
+		);
+
+		// Nothing left to claim.
+		assert_noop!(
+			AhOps::claim_derived_sovereign_account(RuntimeOrigin::root(), 0),
+			Error::<AssetHub>::ZeroBalance
+		);
+	});
+}
+
+#[test]
+fn full_unlock_schedule_leaves_paras_for_manual_release() {
+	use crate::{mock::*, *};
+	use sp_runtime::BuildStorage;
+
+	let t = frame_system::GenesisConfig::<AssetHub>::default().build_storage().unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		let max = MAX_UNLOCK_BLOCKS as u64;
+		for block in 0..max {
+			assert!(Pallet::<AssetHub>::schedule_unlock(block, ParaId::from(2000)));
+		}
+		// An already scheduled block still takes more paras.
+		assert!(Pallet::<AssetHub>::schedule_unlock(0, ParaId::from(2001)));
+
+		let para_id = ParaId::from(2002);
+		assert!(!Pallet::<AssetHub>::schedule_unlock(max, para_id));
+		System::assert_last_event(
+			Event::<AssetHub>::UnlockNotScheduled { block: max, para_id }.into(),
+		);
+		assert!(UnlockSchedule::<AssetHub>::get(max, para_id).is_none());
+		assert_eq!(UnlockBlocks::<AssetHub>::get().len() as u64, max);
+	});
+}
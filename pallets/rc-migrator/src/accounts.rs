@@ -17,12 +17,14 @@
 //! Account/Balance data migrator module.
 
 use crate::{types::*, *};
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use frame_support::{
 	traits::tokens::{Balance as BalanceT, IdAmount},
 	weights::WeightMeter,
 };
 use frame_system::Account as SystemAccount;
 use pallet_balances::{AccountData, BalanceLock};
+use polkadot_parachain_primitives::primitives::HrmpChannelId;
 use sp_runtime::{traits::Zero, BoundedVec};
 
 /// Account type meant to transfer data between RC and AH.
@@ -213,6 +215,146 @@ pub type AccountFor<T> = Account<
 	PortableFreezeReason,
 >;
 
+/// What the accounts migration does to an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum AccountMigrationKind {
+	/// The account does not exist on the Relay Chain.
+	NonExistent,
+	/// The account stays on the Relay Chain with its entire balance, e.g. a system account.
+	Preserved,
+	/// The account can not be migrated since its total balance is below the existential deposit.
+	/// It is left on the Relay Chain as is.
+	BelowExistentialDeposit,
+	/// The account has no balance to migrate.
+	NothingToMigrate,
+	/// The account is migrated to the Asset Hub entirely.
+	Migrated,
+	/// The account is migrated to the Asset Hub, except for the existential deposit and the
+	/// deposits of parachain registrations and HRMP channels that stay on the Relay Chain.
+	PartiallyMigrated,
+	/// The account has a hold or freeze with an unmapped reason whose
+	/// [`UnmappedReasonPolicy`] refuses the migration. The migration does not start while there
+	/// are such accounts.
+	Refused,
+}
+
+impl<Balance: Saturating + Ord + Copy> TranslatedReasons<Balance> {
+	/// Move the amounts of unmapped holds and freezes between the migrated `free`, `reserved`
+	/// and `unnamed_reserve` balances according to their [`UnmappedReasonPolicy`].
+	///
+	/// Returns the new `(free, reserved, unnamed_reserve)`.
+	fn apply_unmapped_reasons(
+		&self,
+		mut free: Balance,
+		mut reserved: Balance,
+		mut unnamed_reserve: Balance,
+	) -> (Balance, Balance, Balance) {
+		let held_to_free = self.held_to_free.min(reserved);
+		reserved.saturating_reduce(held_to_free);
+		free.saturating_accrue(held_to_free);
+
+		unnamed_reserve.saturating_accrue(self.held_to_reserve);
+
+		let frozen_to_reserve = self.frozen_to_reserve.min(free);
+		free.saturating_reduce(frozen_to_reserve);
+		reserved.saturating_accrue(frozen_to_reserve);
+		unnamed_reserve.saturating_accrue(frozen_to_reserve);
+
+		(free, reserved, unnamed_reserve)
+	}
+}
+
+/// Preview of what the accounts migration does to an account, see
+/// [`AccountsMigrator::preview_account`].
+///
+/// Balances are the ones at the time of the preview and change with the account until it is
+/// migrated.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AccountMigrationPreview<AccountId, Balance> {
+	/// What is done to the account.
+	pub kind: AccountMigrationKind,
+	/// The account on the Asset Hub that receives the migrated balance.
+	///
+	/// Sovereign accounts and accounts derived from them are translated if they are in the
+	/// translation tables of [`crate::sovereign_account_translation`]. Translations added on the
+	/// Asset Hub with `add_account_translation` are not known to the Relay Chain.
+	pub ah_account: AccountId,
+	/// The parachain whose Relay Chain sovereign account this is.
+	///
+	/// Only sovereign accounts in the translation tables are translated, see `ah_account`.
+	pub sovereign_of: Option<ParaId>,
+	/// Free balance that stays on the Relay Chain.
+	pub rc_free: Balance,
+	/// Reserved balance that stays on the Relay Chain.
+	pub rc_reserved: Balance,
+	/// Free balance that is moved to the Asset Hub.
+	pub migrated_free: Balance,
+	/// Reserved balance that is moved to the Asset Hub.
+	///
+	/// Made up of the `holds` and the `unnamed_reserve`.
+	pub migrated_reserved: Balance,
+	/// Holds that are moved to the Asset Hub.
+	pub holds: Vec<IdAmount<PortableHoldReason, Balance>>,
+	/// Freezes that are moved to the Asset Hub.
+	pub freezes: Vec<IdAmount<PortableFreezeReason, Balance>>,
+	/// Locks that are moved to the Asset Hub.
+	pub locks: Vec<BalanceLock<Balance>>,
+	/// Reserve without a reason that is moved to the Asset Hub.
+	pub unnamed_reserve: Balance,
+}
+
+pub type AccountMigrationPreviewOf<T> = AccountMigrationPreview<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_balances::Config>::Balance,
+>;
+
+/// Holds and freezes of an account translated with [`Config::HoldReasonRegistry`] and
+/// [`Config::FreezeReasonRegistry`].
+struct TranslatedReasons<Balance> {
+	/// Holds with a mapped reason.
+	holds: Vec<IdAmount<PortableHoldReason, Balance>>,
+	/// Freezes with a mapped reason.
+	freezes: Vec<IdAmount<PortableFreezeReason, Balance>>,
+	/// Held amount with an unmapped reason that is migrated as free balance.
+	held_to_free: Balance,
+	/// Held amount with an unmapped reason that is migrated as unnamed reserve.
+	held_to_reserve: Balance,
+	/// Frozen amount with an unmapped reason that is migrated as unnamed reserve.
+	///
+	/// Freezes overlap, so this is the largest of these freezes.
+	frozen_to_reserve: Balance,
+}
+
+/// The parachain whose sovereign account `who` is.
+///
+/// Sovereign accounts are `b"para"` followed by the SCALE encoded para id and zeros.
+pub fn sovereign_para_id(who: &AccountId32) -> Option<ParaId> {
+	let raw: &[u8] = who.as_ref();
+	let raw = raw.strip_prefix(b"para")?;
+	let (para_id, zeros) = raw.split_at(4);
+	if zeros.iter().any(|byte| *byte != 0) {
+		return None;
+	}
+	u32::decode(&mut &para_id[..]).ok().map(ParaId::from)
+}
+
+/// The Asset Hub account of the parachain sovereign account `who` in the
+/// [`SOV_TRANSLATIONS`](crate::sovereign_account_translation::SOV_TRANSLATIONS) table.
+pub fn sovereign_translation(who: &AccountId32) -> Option<AccountId32> {
+	let table = crate::sovereign_account_translation::SOV_TRANSLATIONS;
+	let index = table.binary_search_by(|((rc_acc, _), _)| rc_acc.cmp(who)).ok()?;
+	table.get(index).map(|(_, (ah_acc, _))| ah_acc.clone())
+}
+
+/// The Asset Hub account and derivation index of the account `who`, derived from a parachain
+/// sovereign account, in the
+/// [`DERIVED_TRANSLATIONS`](crate::sovereign_account_translation::DERIVED_TRANSLATIONS) table.
+pub fn derived_translation(who: &AccountId32) -> Option<(AccountId32, u16)> {
+	let table = crate::sovereign_account_translation::DERIVED_TRANSLATIONS;
+	let index = table.binary_search_by(|((rc_acc, _), _, _)| rc_acc.cmp(who)).ok()?;
+	table.get(index).map(|(_, idx, (ah_acc, _))| (ah_acc.clone(), *idx))
+}
+
 /// Helper struct tracking total balance kept on RC and total migrated.
 #[derive(
 	Encode,
@@ -263,6 +405,12 @@ impl<T: Config> PalletMigration for AccountsMigrator<T> {
 			SystemAccount::<T>::iter()
 		};
 
+		// 1 read and 1 write of the `RcMigratedTotals` once the batch is assembled.
+		if weight_counter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return Err(Error::OutOfWeight);
+		}
+		let mut sent = types::MigratedTotals::default();
+
 		let mut maybe_last_key = last_key;
 		let mut total_items_iterated = 0;
 		loop {
@@ -293,11 +441,11 @@ impl<T: Config> PalletMigration for AccountsMigrator<T> {
 				break;
 			}
 
-			if batch.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if batch.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					batch.batch_count()
 				);
 				break;
@@ -334,6 +482,11 @@ impl<T: Config> PalletMigration for AccountsMigrator<T> {
 				Ok(Some(ah_account)) => {
 					// if this the last account to handle at this iteration, we skip it next time.
 					maybe_last_key = Some(who);
+					let balance = ah_account.free.saturating_add(ah_account.reserved);
+					sent.balance.saturating_accrue(balance);
+					for hold in ah_account.holds.iter() {
+						sent.add_hold(&hold.id, hold.amount);
+					}
 					batch.push(ah_account)
 				},
 				// Not enough weight, lets try again in the next block since we made some progress.
@@ -364,6 +517,7 @@ impl<T: Config> PalletMigration for AccountsMigrator<T> {
 			Pallet::<T>::send_chunked_xcm_and_track(batch, |batch| {
 				types::AhMigratorCall::<T>::ReceiveAccounts { accounts: batch }
 			})?;
+			RcMigratedTotals::<T>::mutate(|totals| totals.merge(&sent));
 		}
 
 		Ok(maybe_last_key)
@@ -412,6 +566,18 @@ impl<T: Config> AccountsMigrator<T> {
 
 		let freezes: Vec<IdAmount<<T as pallet::Config>::RuntimeFreezeReason, T::Balance>> =
 			pallet_balances::Freezes::<T>::get(&who).into_inner();
+		let holds: Vec<IdAmount<<T as pallet_balances::Config>::RuntimeHoldReason, T::Balance>> =
+			pallet_balances::Holds::<T>::get(&who).into();
+
+		// translate the reasons before touching the account, a refused account stays as it is.
+		let Some(translated) = Self::translate_reasons(&holds, &freezes) else {
+			// the migration does not start while accounts are refused.
+			defensive!(
+				"Refusing to migrate account with unmapped hold or freeze reason",
+				who.to_ss58check()
+			);
+			return Ok(None);
+		};
 
 		for freeze in &freezes {
 			if let Err(e) = <T as Config>::Currency::thaw(&freeze.id, &who) {
@@ -429,8 +595,6 @@ impl<T: Config> AccountsMigrator<T> {
 
 		let rc_ed = <T as Config>::Currency::minimum_balance();
 		let ah_ed = T::AhExistentialDeposit::get();
-		let holds: Vec<IdAmount<<T as pallet_balances::Config>::RuntimeHoldReason, T::Balance>> =
-			pallet_balances::Holds::<T>::get(&who).into();
 
 		for hold in &holds {
 			let IdAmount { id, amount } = hold.clone();
@@ -588,16 +752,16 @@ impl<T: Config> AccountsMigrator<T> {
 
 		let consumers = Self::get_consumer_count(&who, &account_info);
 		let providers = Self::get_provider_count(&who, &account_info, &holds);
-		let portable_holds = holds.into_iter().map(IntoPortable::into_portable).collect();
-		let portable_freezes = freezes.into_iter().map(IntoPortable::into_portable).collect();
+		let (free, reserved, unnamed_reserve) =
+			translated.apply_unmapped_reasons(teleport_free, teleport_reserved, unnamed_reserve);
 
 		let withdrawn_account = AccountFor::<T> {
 			who: who.clone(),
-			free: teleport_free,
-			reserved: teleport_reserved,
+			free,
+			reserved,
 			frozen: account_data.frozen,
-			holds: BoundedVec::defensive_truncate_from(portable_holds),
-			freezes: BoundedVec::defensive_truncate_from(portable_freezes),
+			holds: BoundedVec::defensive_truncate_from(translated.holds),
+			freezes: BoundedVec::defensive_truncate_from(translated.freezes),
 			locks: BoundedVec::defensive_truncate_from(locks),
 			unnamed_reserve,
 			consumers,
@@ -614,6 +778,184 @@ impl<T: Config> AccountsMigrator<T> {
 		Ok(Some(withdrawn_account))
 	}
 
+	/// Preview what the accounts migration does to `who` with its current state.
+	///
+	/// Mirrors [`Self::withdraw_account`] without modifying the account. Before the migration
+	/// started, the accounts that stay partially on the Relay Chain are derived from the current
+	/// deposits as [`Self::obtain_rc_accounts`] would.
+	pub fn preview_account(who: T::AccountId) -> AccountMigrationPreviewOf<T> {
+		let sovereign_of = sovereign_para_id(&who);
+		let ah_account = sovereign_translation(&who)
+			.or_else(|| derived_translation(&who).map(|(ah_account, _)| ah_account))
+			.unwrap_or_else(|| who.clone());
+		let mut preview = AccountMigrationPreview {
+			kind: AccountMigrationKind::NonExistent,
+			ah_account,
+			sovereign_of,
+			rc_free: Zero::zero(),
+			rc_reserved: Zero::zero(),
+			migrated_free: Zero::zero(),
+			migrated_reserved: Zero::zero(),
+			holds: Vec::new(),
+			freezes: Vec::new(),
+			locks: Vec::new(),
+			unnamed_reserve: Zero::zero(),
+		};
+
+		if !SystemAccount::<T>::contains_key(&who) {
+			return preview;
+		}
+		let mut account_info = SystemAccount::<T>::get(&who);
+
+		let on_demand_pallet_account: T::AccountId =
+			T::OnDemandPalletId::get().into_account_truncating();
+		let (account_state, missing_free) = if RcAccounts::<T>::iter_keys().next().is_some() {
+			(Self::get_account_state(&who), Zero::zero())
+		} else if who == on_demand_pallet_account {
+			(AccountState::Preserve, Zero::zero())
+		} else {
+			Some(Self::rc_deposits_of(&who, &account_info))
+				.filter(|deposit| !deposit.is_zero())
+				.and_then(|deposit| Self::partial_account_state(&who, deposit))
+				.unwrap_or((AccountState::Migrate, Zero::zero()))
+		};
+		// `obtain_rc_accounts` unreserves what is missing for the existential deposits.
+		account_info.data.free.saturating_accrue(missing_free);
+		account_info.data.reserved.saturating_reduce(missing_free);
+		let data = &account_info.data;
+
+		if account_state.is_preserve() {
+			preview.kind = AccountMigrationKind::Preserved;
+			preview.rc_free = data.free;
+			preview.rc_reserved = data.reserved;
+			return preview;
+		}
+		if !Self::can_migrate_account(&who, &account_info) {
+			preview.kind = AccountMigrationKind::BelowExistentialDeposit;
+			preview.rc_free = data.free;
+			preview.rc_reserved = data.reserved;
+			return preview;
+		}
+
+		preview.rc_free = account_state.get_rc_free().min(data.free);
+		preview.rc_reserved = account_state.get_rc_reserved().min(data.reserved);
+		preview.migrated_free = data.free.saturating_sub(preview.rc_free);
+		preview.migrated_reserved = data.reserved.saturating_sub(preview.rc_reserved);
+		let holds: Vec<_> = pallet_balances::Holds::<T>::get(&who).into();
+		let freezes = pallet_balances::Freezes::<T>::get(&who).into_inner();
+		let Some(translated) = Self::translate_reasons(&holds, &freezes) else {
+			preview.kind = AccountMigrationKind::Refused;
+			preview.rc_free = data.free;
+			preview.rc_reserved = data.reserved;
+			preview.migrated_free = Zero::zero();
+			preview.migrated_reserved = Zero::zero();
+			return preview;
+		};
+		let total_hold = holds.iter().map(|hold| hold.amount).sum::<T::Balance>();
+		let unnamed_reserve = preview.migrated_reserved.saturating_sub(total_hold);
+		(preview.migrated_free, preview.migrated_reserved, preview.unnamed_reserve) = translated
+			.apply_unmapped_reasons(
+				preview.migrated_free,
+				preview.migrated_reserved,
+				unnamed_reserve,
+			);
+		preview.holds = translated.holds;
+		preview.freezes = translated.freezes;
+		preview.locks = pallet_balances::Locks::<T>::get(&who).into_inner();
+
+		preview.kind = if preview.migrated_free.is_zero() && preview.migrated_reserved.is_zero() {
+			AccountMigrationKind::NothingToMigrate
+		} else if matches!(account_state, AccountState::Part { .. }) {
+			AccountMigrationKind::PartiallyMigrated
+		} else {
+			AccountMigrationKind::Migrated
+		};
+
+		preview
+	}
+
+	/// Translate the reasons of `holds` and `freezes` with the configured registries.
+	///
+	/// Returns `None` if the [`UnmappedReasonPolicy`] of any unmapped reason refuses to migrate
+	/// the account.
+	fn translate_reasons(
+		holds: &[IdAmount<<T as pallet_balances::Config>::RuntimeHoldReason, T::Balance>],
+		freezes: &[IdAmount<<T as pallet::Config>::RuntimeFreezeReason, T::Balance>],
+	) -> Option<TranslatedReasons<T::Balance>> {
+		let mut translated = TranslatedReasons {
+			holds: Vec::with_capacity(holds.len()),
+			freezes: Vec::with_capacity(freezes.len()),
+			held_to_free: Zero::zero(),
+			held_to_reserve: Zero::zero(),
+			frozen_to_reserve: Zero::zero(),
+		};
+
+		for IdAmount { id, amount } in holds {
+			match T::HoldReasonRegistry::translate(id) {
+				Ok(id) => translated.holds.push(IdAmount { id, amount: *amount }),
+				Err(UnmappedReasonPolicy::Release) =>
+					translated.held_to_free.saturating_accrue(*amount),
+				Err(UnmappedReasonPolicy::ConvertToReserve) =>
+					translated.held_to_reserve.saturating_accrue(*amount),
+				Err(UnmappedReasonPolicy::Refuse) => {
+					log::debug!(target: LOG_TARGET, "Unmapped hold reason: {:?}", id);
+					return None;
+				},
+			}
+		}
+		for IdAmount { id, amount } in freezes {
+			match T::FreezeReasonRegistry::translate(id) {
+				Ok(id) => translated.freezes.push(IdAmount { id, amount: *amount }),
+				Err(UnmappedReasonPolicy::Release) => {},
+				Err(UnmappedReasonPolicy::ConvertToReserve) =>
+					translated.frozen_to_reserve = translated.frozen_to_reserve.max(*amount),
+				Err(UnmappedReasonPolicy::Refuse) => {
+					log::debug!(target: LOG_TARGET, "Unmapped freeze reason: {:?}", id);
+					return None;
+				},
+			}
+		}
+
+		Some(translated)
+	}
+
+	/// The number of accounts with a hold or freeze reason whose [`UnmappedReasonPolicy`]
+	/// refuses their migration.
+	///
+	/// The migration does not start while there are such accounts, see
+	/// [`UnmappedReasonPolicy::Refuse`].
+	pub fn count_refused_accounts() -> (u32, Weight) {
+		let mut weight = Weight::zero();
+		let mut refused = BTreeSet::new();
+
+		for (who, holds) in pallet_balances::Holds::<T>::iter() {
+			weight += T::DbWeight::get().reads(1);
+			let refuse = holds.iter().any(|hold| {
+				matches!(
+					T::HoldReasonRegistry::translate(&hold.id),
+					Err(UnmappedReasonPolicy::Refuse)
+				)
+			});
+			if refuse {
+				refused.insert(who);
+			}
+		}
+		for (who, freezes) in pallet_balances::Freezes::<T>::iter() {
+			weight += T::DbWeight::get().reads(1);
+			let refuse = freezes.iter().any(|freeze| {
+				matches!(
+					T::FreezeReasonRegistry::translate(&freeze.id),
+					Err(UnmappedReasonPolicy::Refuse)
+				)
+			});
+			if refuse {
+				refused.insert(who);
+			}
+		}
+
+		(refused.len() as u32, weight)
+	}
+
 	/// Actions to be done after the accounts migration is finished.
 	pub fn finish_balances_migration() {
 		pallet_balances::InactiveIssuance::<T>::put(0);
@@ -775,80 +1117,24 @@ impl<T: Config> AccountsMigrator<T> {
 			return T::DbWeight::get().reads(1);
 		}
 
-		let mut weight = Weight::zero();
-		let mut reserves = sp_std::collections::btree_map::BTreeMap::new();
-		let mut update_reserves = |id, deposit| {
-			if deposit == 0 {
-				return;
-			}
-			reserves.entry(id).and_modify(|e| *e += deposit).or_insert(deposit);
-		};
-
-		for (channel_id, info) in hrmp::HrmpChannels::<T>::iter() {
-			weight += T::DbWeight::get().reads(1);
-			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1475
-			let sender: T::AccountId = channel_id.sender.into_account_truncating();
-			update_reserves(sender, info.sender_deposit);
-
-			let recipient: T::AccountId = channel_id.recipient.into_account_truncating();
-			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1539
-			update_reserves(recipient, info.recipient_deposit);
-		}
-
-		for (channel_id, info) in hrmp::HrmpOpenChannelRequests::<T>::iter() {
-			weight += T::DbWeight::get().reads(1);
-			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1475
-			let sender: T::AccountId = channel_id.sender.into_account_truncating();
-			update_reserves(sender, info.sender_deposit);
-		}
-
-		for (_, info) in Paras::<T>::iter() {
-			weight += T::DbWeight::get().reads(1);
-			update_reserves(info.manager, info.deposit);
-		}
+		let (reserves, mut weight) = Self::rc_deposits();
 
 		for (id, expected_rc_reserved) in reserves {
 			weight += T::DbWeight::get().reads_writes(6, 1);
-			let free = <T as Config>::Currency::balance(&id);
-			let total_reserved = <T as Config>::Currency::reserved_balance(&id);
-			let total_hold = pallet_balances::Holds::<T>::get(&id)
-				.into_iter()
-				// we do not expect more holds
-				.take(5)
-				.map(|h| h.amount)
-				.sum::<T::Balance>();
-
-			let rc_ed = <T as Config>::Currency::minimum_balance();
-			let ah_ed = T::AhExistentialDeposit::get();
-
-			defensive_assert!(total_reserved >= total_hold, "total_reserved >= total_hold");
-
-			// We need to keep rc_ed free balance on the relay chain and migrate at least ah_ed free
-			// balance to the asset hub.
-			let missing_free = (rc_ed + ah_ed).saturating_sub(free);
-			// we prioritize the named holds over the unnamed reserve. If the account to preserve
-			// has any named holds, we will send them to the AH and keep up to the unnamed reserves
-			// `rc_reserved` on the RC.
-			let actual_rc_reserved = (expected_rc_reserved
-				.min(total_reserved.saturating_sub(total_hold)))
-			.saturating_sub(missing_free);
-
-			if actual_rc_reserved == 0 {
+			let Some((state, missing_free)) = Self::partial_account_state(&id, expected_rc_reserved)
+			else {
 				log::debug!(
 					target: LOG_TARGET,
 					"Account doesn't have enough reserved balance to keep on RC. account: {:?}.",
 					id.to_ss58check(),
 				);
 				continue;
-			}
+			};
 
-			if missing_free == 0 {
-				RcAccounts::<T>::insert(
-					&id,
-					// one consumer reference of reserved balance.
-					AccountState::Part { free: rc_ed, reserved: actual_rc_reserved, consumers: 1 },
-				);
-			} else {
+			if missing_free != 0 {
+				let free = <T as Config>::Currency::balance(&id);
+				let rc_ed = <T as Config>::Currency::minimum_balance();
+				let ah_ed = T::AhExistentialDeposit::get();
 				log::warn!(
 					target: LOG_TARGET,
 					"Account {:?} has less free balance {} than the existential deposits {} + {} (RC ed + AH ed)",
@@ -869,13 +1155,9 @@ impl<T: Config> AccountsMigrator<T> {
 						id.to_ss58check()
 					)
 				}
-
-				RcAccounts::<T>::insert(
-					&id,
-					// one consumer reference of reserved balance.
-					AccountState::Part { free: rc_ed, reserved: actual_rc_reserved, consumers: 1 },
-				);
 			}
+
+			RcAccounts::<T>::insert(&id, state);
 		}
 
 		// Keep the on-demand pallet account on the RC.
@@ -891,6 +1173,132 @@ impl<T: Config> AccountsMigrator<T> {
 
 		weight
 	}
+
+	/// The deposits per account of the pallets that stay on the Relay Chain: HRMP channels and
+	/// parachain registrations.
+	fn rc_deposits() -> (BTreeMap<T::AccountId, T::Balance>, Weight) {
+		let mut weight = Weight::zero();
+		let mut reserves = BTreeMap::new();
+		let mut update_reserves = |id, deposit| {
+			if deposit == 0 {
+				return;
+			}
+			reserves.entry(id).and_modify(|e| *e += deposit).or_insert(deposit);
+		};
+
+		for (channel_id, info) in hrmp::HrmpChannels::<T>::iter() {
+			weight += T::DbWeight::get().reads(1);
+			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1475
+			let sender: T::AccountId = channel_id.sender.into_account_truncating();
+			update_reserves(sender, info.sender_deposit);
+
+			let recipient: T::AccountId = channel_id.recipient.into_account_truncating();
+			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1539
+			update_reserves(recipient, info.recipient_deposit);
+		}
+
+		for (channel_id, info) in hrmp::HrmpOpenChannelRequests::<T>::iter() {
+			weight += T::DbWeight::get().reads(1);
+			// source: https://github.com/paritytech/polkadot-sdk/blob/3dc3a11cd68762c2e5feb0beba0b61f448c4fc92/polkadot/runtime/parachains/src/hrmp.rs#L1475
+			let sender: T::AccountId = channel_id.sender.into_account_truncating();
+			update_reserves(sender, info.sender_deposit);
+		}
+
+		for (_, info) in Paras::<T>::iter() {
+			weight += T::DbWeight::get().reads(1);
+			update_reserves(info.manager, info.deposit);
+		}
+
+		(reserves, weight)
+	}
+
+	/// The deposits of `who` in the pallets that stay on the Relay Chain, as [`Self::rc_deposits`]
+	/// would count them.
+	///
+	/// Only reads the HRMP channels of the parachain whose sovereign account `who` is. The
+	/// parachain registrations are not indexed by manager, so they are only read if `who` has a
+	/// reserved balance.
+	fn rc_deposits_of(who: &T::AccountId, account_info: &AccountInfoFor<T>) -> T::Balance {
+		if account_info.data.reserved.is_zero() {
+			return Zero::zero();
+		}
+		let mut deposit: T::Balance = Zero::zero();
+
+		if let Some(para_id) = sovereign_para_id(who) {
+			for recipient in hrmp::HrmpEgressChannelsIndex::<T>::get(para_id) {
+				let channel_id = HrmpChannelId { sender: para_id, recipient };
+				if let Some(info) = hrmp::HrmpChannels::<T>::get(&channel_id) {
+					deposit.saturating_accrue(info.sender_deposit);
+				}
+			}
+			for sender in hrmp::HrmpIngressChannelsIndex::<T>::get(para_id) {
+				let channel_id = HrmpChannelId { sender, recipient: para_id };
+				if let Some(info) = hrmp::HrmpChannels::<T>::get(&channel_id) {
+					deposit.saturating_accrue(info.recipient_deposit);
+				}
+			}
+			for channel_id in hrmp::HrmpOpenChannelRequestsList::<T>::get() {
+				if channel_id.sender != para_id {
+					continue;
+				}
+				if let Some(info) = hrmp::HrmpOpenChannelRequests::<T>::get(&channel_id) {
+					deposit.saturating_accrue(info.sender_deposit);
+				}
+			}
+		}
+
+		for (_, info) in Paras::<T>::iter() {
+			if &info.manager == who {
+				deposit.saturating_accrue(info.deposit);
+			}
+		}
+
+		deposit
+	}
+
+	/// The state of an account that has `expected_rc_reserved` deposits on the Relay Chain.
+	///
+	/// Returns `None` if nothing of the account is kept on the Relay Chain. Otherwise returns the
+	/// state and the reserved balance that must be unreserved to keep the existential deposits
+	/// of both chains free.
+	fn partial_account_state(
+		id: &T::AccountId,
+		expected_rc_reserved: T::Balance,
+	) -> Option<(AccountStateFor<T>, T::Balance)> {
+		let free = <T as Config>::Currency::balance(id);
+		let total_reserved = <T as Config>::Currency::reserved_balance(id);
+		let total_hold = pallet_balances::Holds::<T>::get(id)
+			.into_iter()
+			// we do not expect more holds
+			.take(5)
+			.map(|h| h.amount)
+			.sum::<T::Balance>();
+
+		let rc_ed = <T as Config>::Currency::minimum_balance();
+		let ah_ed = T::AhExistentialDeposit::get();
+
+		defensive_assert!(total_reserved >= total_hold, "total_reserved >= total_hold");
+
+		// We need to keep rc_ed free balance on the relay chain and migrate at least ah_ed free
+		// balance to the asset hub.
+		let missing_free = (rc_ed + ah_ed).saturating_sub(free);
+		// we prioritize the named holds over the unnamed reserve. If the account to preserve
+		// has any named holds, we will send them to the AH and keep up to the unnamed reserves
+		// `rc_reserved` on the RC.
+		let actual_rc_reserved = (expected_rc_reserved
+			.min(total_reserved.saturating_sub(total_hold)))
+		.saturating_sub(missing_free);
+
+		if actual_rc_reserved == 0 {
+			return None;
+		}
+
+		// one consumer reference of reserved balance.
+		Some((
+			AccountState::Part { free: rc_ed, reserved: actual_rc_reserved, consumers: 1 },
+			missing_free,
+		))
+	}
 }
 
 // Only used for testing.
@@ -1305,3 +1713,52 @@ pub mod tests {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn translated(
+		held_to_free: u128,
+		held_to_reserve: u128,
+		frozen_to_reserve: u128,
+	) -> TranslatedReasons<u128> {
+		TranslatedReasons {
+			holds: Vec::new(),
+			freezes: Vec::new(),
+			held_to_free,
+			held_to_reserve,
+			frozen_to_reserve,
+		}
+	}
+
+	#[test]
+	fn mapped_reasons_keep_the_balances() {
+		assert_eq!(translated(0, 0, 0).apply_unmapped_reasons(100, 50, 10), (100, 50, 10));
+	}
+
+	#[test]
+	fn released_holds_are_migrated_as_free_balance() {
+		assert_eq!(translated(30, 0, 0).apply_unmapped_reasons(100, 50, 10), (130, 20, 10));
+		// no more than the reserved balance is released.
+		assert_eq!(translated(80, 0, 0).apply_unmapped_reasons(100, 50, 10), (150, 0, 10));
+	}
+
+	#[test]
+	fn converted_holds_are_migrated_as_unnamed_reserve() {
+		// the held amount is part of the reserved balance already.
+		assert_eq!(translated(0, 30, 0).apply_unmapped_reasons(100, 50, 10), (100, 50, 40));
+	}
+
+	#[test]
+	fn converted_freezes_move_free_balance_to_the_unnamed_reserve() {
+		assert_eq!(translated(0, 0, 30).apply_unmapped_reasons(100, 50, 10), (70, 80, 40));
+		// no more than the free balance is reserved.
+		assert_eq!(translated(0, 0, 130).apply_unmapped_reasons(100, 50, 10), (0, 150, 110));
+	}
+
+	#[test]
+	fn released_holds_can_be_reserved_for_converted_freezes() {
+		assert_eq!(translated(50, 0, 120).apply_unmapped_reasons(100, 50, 0), (30, 120, 120));
+	}
+}
@@ -74,11 +74,11 @@ impl<T: Config> PalletMigration for AssetRateMigrator<T> {
 				break;
 			}
 
-			if messages.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					messages.batch_count()
 				);
 				break;
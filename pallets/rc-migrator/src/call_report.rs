@@ -0,0 +1,130 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Report of the calls of referendums and scheduled tasks that are translated on the Asset Hub.
+//!
+//! The Asset Hub translates the calls and origins of ongoing referendums and scheduled tasks with
+//! its `RcToAhCall` and `RcToAhPalletsOrigin` converters. A referendum that can not be translated
+//! is cancelled and a scheduled task is dropped. To find these before the migration, the
+//! `migrated_calls` view function of the Relay Chain collects all calls and the
+//! `call_translation_report` view function of the Asset Hub dry runs their translation.
+
+use crate::{scheduler::SchedulerBlockNumberFor, *};
+use frame_support::traits::{Bounded, QueryPreimage};
+use pallet_referenda::{ReferendumInfo, ReferendumInfoFor};
+
+/// Where a translated call comes from.
+#[derive(
+	Encode, DecodeWithMemTracking, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo,
+)]
+pub enum CallSource<BlockNumber> {
+	/// The proposal of an ongoing referendum. It is cancelled if the call can not be translated.
+	Referendum {
+		/// The index of the referendum.
+		index: u32,
+	},
+	/// A scheduled task. It is dropped if the call can not be translated.
+	ScheduledTask {
+		/// The block of the agenda.
+		block: BlockNumber,
+		/// The position of the task in the agenda.
+		index: u32,
+	},
+}
+
+/// A call of the Relay Chain that the Asset Hub translates.
+#[derive(
+	Encode, DecodeWithMemTracking, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo,
+)]
+pub struct MigratedCall<BlockNumber> {
+	/// Where the call comes from.
+	pub source: CallSource<BlockNumber>,
+	/// The encoded origin that dispatches the call.
+	pub origin: Vec<u8>,
+	/// The encoded call or `None` if its preimage is not available.
+	pub call: Option<Vec<u8>>,
+}
+
+pub type MigratedCallOf<T> = MigratedCall<SchedulerBlockNumberFor<T>>;
+
+/// Why a call can not be translated.
+#[derive(
+	Encode, DecodeWithMemTracking, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo,
+)]
+pub enum CallTranslationError {
+	/// The preimage of the call is not available.
+	PreimageMissing,
+	/// The origin has no counterpart on the Asset Hub.
+	UnsupportedOrigin,
+	/// The call has no counterpart on the Asset Hub.
+	UnsupportedCall,
+}
+
+/// Outcome of the dry run translation of a [`MigratedCall`].
+#[derive(
+	Encode, DecodeWithMemTracking, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo,
+)]
+pub struct CallTranslation<BlockNumber> {
+	/// Where the call comes from.
+	pub source: CallSource<BlockNumber>,
+	/// `Ok` if the call is migrated, otherwise why its referendum is cancelled or task dropped.
+	pub result: Result<(), CallTranslationError>,
+}
+
+/// The encoded call of `bounded`, fetching its preimage if needed.
+pub fn fetch_call<T: pallet_preimage::Config<Hash = H256>, Call>(
+	bounded: &Bounded<Call, <T as frame_system::Config>::Hashing>,
+) -> Option<Vec<u8>> {
+	match bounded {
+		Bounded::Inline(call) => Some(call.clone().into_inner()),
+		Bounded::Lookup { hash, len } =>
+			<pallet_preimage::Pallet<T> as QueryPreimage>::fetch(hash, Some(*len))
+				.ok()
+				.map(|preimage| preimage.into_owned()),
+		Bounded::Legacy { hash, .. } =>
+			<pallet_preimage::Pallet<T> as QueryPreimage>::fetch(hash, None)
+				.ok()
+				.map(|preimage| preimage.into_owned()),
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// All calls of ongoing referendums and scheduled tasks that are translated on the Asset Hub.
+	pub fn collect_migrated_calls() -> Vec<MigratedCallOf<T>> {
+		let referendums =
+			ReferendumInfoFor::<T, ()>::iter().filter_map(|(index, info)| match info {
+				ReferendumInfo::Ongoing(status) => Some(MigratedCall {
+					source: CallSource::Referendum { index },
+					origin: status.origin.encode(),
+					call: fetch_call::<T, _>(&status.proposal),
+				}),
+				_ => None,
+			});
+		let tasks = scheduler::alias::Agenda::<T>::iter().flat_map(|(block, agenda)| {
+			agenda.into_iter().enumerate().filter_map(move |(index, task)| {
+				let task = task?;
+				Some(MigratedCall {
+					source: CallSource::ScheduledTask { block, index: index as u32 },
+					origin: task.origin.encode(),
+					call: fetch_call::<T, _>(&task.call),
+				})
+			})
+		});
+
+		referendums.chain(tasks).collect()
+	}
+}
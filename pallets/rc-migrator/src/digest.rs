@@ -0,0 +1,153 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content commitment of the data sent to the Asset Hub.
+//!
+//! Every item sent by [`Pallet::send_chunked_xcm_and_track`] is recorded into the
+//! [`MigrationDigest`] of the pallet that is being migrated. Once the migration of a pallet is
+//! done, the digest is sent to the Asset Hub with
+//! [`types::AhMigratorCall::ReceiveMigrationDigest`], tracked like the batches. The Asset Hub
+//! records the same digest over the items it received and compares the two.
+//!
+//! The digest does not depend on the order in which the items arrive, since resent messages may
+//! overtake others. It is an additive set hash: the sum modulo 2^256 of the hashes of the items.
+//! Unlike an XOR of the hashes, an item that is received twice does not cancel out.
+
+use crate::*;
+
+/// Order independent digest over a set of SCALE encoded items.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct MigrationDigest {
+	/// Number of recorded items.
+	pub items: u32,
+	/// Sum modulo 2^256 of the `blake2_256` hashes of the encoded items, as little endian
+	/// integers.
+	pub hash: H256,
+}
+
+impl MigrationDigest {
+	/// Record a single item.
+	pub fn record<E: Encode>(&mut self, item: &E) {
+		let item_hash = item.using_encoded(sp_io::hashing::blake2_256);
+		self.add_hash(&item_hash);
+		self.items.saturating_inc();
+	}
+
+	/// Record all `items`.
+	pub fn record_all<E: Encode>(&mut self, items: &[E]) {
+		items.iter().for_each(|item| self.record(item));
+	}
+
+	/// Combine with the digest of another set of items.
+	pub fn merge(&mut self, other: &Self) {
+		self.add_hash(other.hash.as_fixed_bytes());
+		self.items.saturating_accrue(other.items);
+	}
+
+	/// Add `hash` to the sum of hashes, wrapping around at 2^256.
+	fn add_hash(&mut self, hash: &[u8; 32]) {
+		let mut carry = 0u16;
+		for (byte, other_byte) in self.hash.as_bytes_mut().iter_mut().zip(hash) {
+			let sum = *byte as u16 + *other_byte as u16 + carry;
+			*byte = sum as u8;
+			carry = sum >> 8;
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Finish the migration of `pallet`: commit to the sent data and enter the next pallet's stage.
+	///
+	/// The digest closes the ingestion window of `pallet` on the Asset Hub, which rejects the
+	/// batches of the next pallet until then. It is tracked and resent like the batches, so that a
+	/// lost digest does not stall the migration.
+	pub fn complete_pallet_migration(pallet: MigratedPallet) {
+		let digest = MigrationDigests::<T>::get(pallet);
+		let call = types::AhMigratorCall::<T>::ReceiveMigrationDigest { pallet, digest };
+		if let Err(err) = Self::send_tracked_xcm(call) {
+			// The Asset Hub can not verify the pallet without the digest, but this is not a reason
+			// to stop the migration.
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to send the migration digest of {pallet:?}: {err:?}"
+			);
+		}
+		Self::deposit_event(Event::<T>::MigrationDigestSent { pallet, digest });
+
+		Self::transition(Self::next_pallet_stage(Some(pallet)));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn migration_digest_is_order_independent() {
+		let mut a = MigrationDigest::default();
+		a.record_all(&[1u32, 2, 3]);
+
+		let mut b = MigrationDigest::default();
+		b.record_all(&[3u32]);
+		let mut c = MigrationDigest::default();
+		c.record_all(&[2u32, 1]);
+		b.merge(&c);
+
+		assert_eq!(a, b);
+		assert_eq!(a.items, 3);
+
+		let mut d = MigrationDigest::default();
+		d.record_all(&[1u32, 2, 3, 3]);
+		assert_ne!(a, d);
+	}
+
+	#[test]
+	fn migration_digest_does_not_cancel_duplicates() {
+		let mut a = MigrationDigest::default();
+		a.record_all(&[1u32, 1, 2, 2]);
+
+		let mut b = MigrationDigest::default();
+		b.record_all(&[3u32, 3, 4, 4]);
+
+		// With an XOR of the hashes both would be zero.
+		assert_eq!(a.items, b.items);
+		assert_ne!(a.hash, b.hash);
+		assert_ne!(a.hash, H256::zero());
+	}
+
+	#[test]
+	fn migration_digest_sum_wraps_around() {
+		let mut digest = MigrationDigest { items: 0, hash: H256::repeat_byte(0xff) };
+		digest.add_hash(&[1; 32]);
+
+		let mut expected = [1u8; 32];
+		expected[0] = 0;
+		assert_eq!(digest.hash, H256(expected));
+	}
+}
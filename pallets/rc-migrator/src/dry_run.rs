@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dry-run of the data migration.
+//!
+//! Drives the migration state machine block by block against the current state inside
+//! `hypothetically!` and reports, per stage, how many items and XCM messages would be sent to the
+//! Asset Hub, the Relay Chain weight consumed and the number of blocks spent in the stage.
+//!
+//! The sent data is read back from [`PendingXcmMessages`] and [`MigrationDigests`] within the same
+//! `hypothetically!` scope, so that the dry-run leaves no trace in storage.
+//!
+//! The dry-run assumes that the Asset Hub confirms every message instantly. In reality the
+//! migration pauses whenever more than [`Config::UnprocessedMsgBuffer`] messages are unconfirmed,
+//! so the reported block counts are a lower bound.
+
+use crate::*;
+use core::mem::discriminant;
+
+/// XCM data sent by [`Pallet::send_chunked_xcm_and_track`] during a dry-run.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct XcmTally {
+	/// Number of items sent.
+	pub items: u32,
+	/// Number of XCM messages sent.
+	pub messages: u32,
+	/// Total encoded size of the sent XCM messages.
+	pub bytes: u64,
+}
+
+impl XcmTally {
+	/// Add the data of `other`.
+	pub fn accrue(&mut self, other: &Self) {
+		self.items.saturating_accrue(other.items);
+		self.messages.saturating_accrue(other.messages);
+		self.bytes.saturating_accrue(other.bytes);
+	}
+}
+
+/// Dry-run report of a single migration stage.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StageDryRunReport<Stage> {
+	/// The stage as it was entered.
+	pub stage: Stage,
+	/// XCM data sent to the Asset Hub during the stage.
+	pub xcm: XcmTally,
+	/// Relay Chain weight consumed by `on_initialize` during the stage.
+	pub weight: Weight,
+	/// Number of blocks spent in the stage.
+	pub blocks: u32,
+}
+
+/// Dry-run report of the data migration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DryRunReport<Stage> {
+	/// Per stage reports in the order the stages were entered.
+	pub stages: Vec<StageDryRunReport<Stage>>,
+	/// Total number of simulated blocks.
+	pub blocks: u32,
+	/// Whether the dry-run reached [`MigrationStage::SignalMigrationFinish`].
+	///
+	/// `false` if it ran out of `max_blocks` or got stuck in a stage that does not progress on
+	/// its own.
+	pub completed: bool,
+}
+
+impl<T: Config> Pallet<T>
+	where
+	crate::BalanceOf<T>:
+		From<<<T as polkadot_runtime_common::slots::Config>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+	crate::BalanceOf<T>:
+		From<<<<T as polkadot_runtime_common::crowdloan::Config>::Auctioneer as polkadot_runtime_common::traits::Auctioneer<<<<T as frame_system::Config>::Block as sp_runtime::traits::Block>::Header as sp_runtime::traits::Header>::Number>>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+	<<T as pallet_treasury::Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber: Into<u32>
+{
+	/// Simulate the data migration against the current state without persisting any changes.
+	///
+	/// ### Parameters:
+	/// - `start`: The stage to start from. Defaults to [`MigrationStage::Starting`].
+	/// - `max_blocks`: The maximum number of blocks to simulate.
+	pub fn dry_run_migration(
+		start: Option<MigrationStageOf<T>>,
+		max_blocks: u32,
+	) -> DryRunReport<MigrationStageOf<T>> {
+		types::hypothetical_fn(|| Self::do_dry_run_migration(start, max_blocks))
+	}
+
+	fn do_dry_run_migration(
+		start: Option<MigrationStageOf<T>>,
+		max_blocks: u32,
+	) -> DryRunReport<MigrationStageOf<T>> {
+		RcMigrationStage::<T>::put(start.unwrap_or(MigrationStage::Starting));
+
+		let mut report = DryRunReport { stages: Vec::new(), blocks: 0, completed: false };
+		let mut now = frame_system::Pallet::<T>::block_number();
+
+		while report.blocks < max_blocks {
+			let stage = RcMigrationStage::<T>::get();
+			match stage {
+				MigrationStage::SignalMigrationFinish => {
+					report.completed = true;
+					break;
+				},
+				// Stages that do not progress without an external trigger or that come after the
+				// data migration.
+				MigrationStage::Pending |
+				MigrationStage::MigrationPaused |
+				MigrationStage::Scheduled { .. } |
+				MigrationStage::WaitingForAh |
+				MigrationStage::WarmUp { .. } |
+				MigrationStage::CoolOff { .. } |
+				MigrationStage::MigrationDone => break,
+				_ => {},
+			}
+
+			// Pretend that the Asset Hub confirmed every message sent so far.
+			let _ = PendingXcmMessages::<T>::clear(u32::MAX, None);
+			let _ = PendingXcmQueries::<T>::clear(u32::MAX, None);
+			let _ = PendingXcmRetries::<T>::clear(u32::MAX, None);
+			let items_before = Self::digested_items();
+
+			now.saturating_inc();
+			frame_system::Pallet::<T>::set_block_number(now);
+			let weight = <Self as Hooks<BlockNumberFor<T>>>::on_initialize(now);
+			let xcm = Self::sent_xcm(items_before);
+			report.blocks.saturating_inc();
+
+			match report.stages.last_mut() {
+				Some(last) if discriminant(&last.stage) == discriminant(&stage) => {
+					last.xcm.accrue(&xcm);
+					last.weight.saturating_accrue(weight);
+					last.blocks.saturating_inc();
+				},
+				_ => report.stages.push(StageDryRunReport { stage, xcm, weight, blocks: 1 }),
+			}
+		}
+
+		report
+	}
+
+	/// The number of items recorded into the digests of all pallets.
+	fn digested_items() -> u32 {
+		MigrationDigests::<T>::iter_values()
+			.fold(0u32, |items, digest| items.saturating_add(digest.items))
+	}
+
+	/// The XCM data sent since the pending messages were cleared and the digests held
+	/// `items_before` items.
+	fn sent_xcm(items_before: u32) -> XcmTally {
+		let mut tally = XcmTally {
+			items: Self::digested_items().saturating_sub(items_before),
+			..Default::default()
+		};
+		for message in PendingXcmMessages::<T>::iter_values() {
+			tally.messages.saturating_inc();
+			tally.bytes.saturating_accrue(message.encoded_size() as u64);
+		}
+		tally
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn xcm_tally_accrues() {
+		let mut tally = XcmTally { items: 10, messages: 1, bytes: 1_000 };
+		tally.accrue(&XcmTally { items: 5, messages: 1, bytes: 500 });
+
+		assert_eq!(tally, XcmTally { items: 15, messages: 2, bytes: 1_500 });
+	}
+}
@@ -0,0 +1,216 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Drains the fast-unstake pallet.
+//!
+//! The Asset Hub has no fast-unstake pallet, so pending requests can not be moved over. Instead,
+//! the migration stops the pallet by setting `ErasToCheckPerBlock` to zero and removes the request
+//! in `Head` as well as every request in `Queue`. The stashes stay bonded and are migrated by the
+//! staking migration like any other stash. Their deposits arrive on the Asset Hub as unnamed
+//! reserves with the account migration and are unreserved there, except for the deposits of
+//! accounts that are preserved on the Relay Chain, which are unreserved on the Relay Chain.
+
+use crate::{types::AccountIdOf, *};
+
+pub struct FastUnstakeMigrator<T> {
+	_marker: sp_std::marker::PhantomData<T>,
+}
+
+/// A fast-unstake request that was removed from the Relay Chain.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+	Clone,
+	PartialEq,
+	Eq,
+)]
+pub struct RcFastUnstakeRequest<AccountId, Balance> {
+	/// The stash that requested to be unstaked.
+	pub stash: AccountId,
+	/// The deposit reserved from the stash for the request.
+	pub deposit: Balance,
+}
+
+pub type RcFastUnstakeRequestOf<T> = RcFastUnstakeRequest<AccountIdOf<T>, BalanceOf<T>>;
+
+impl<T: Config> PalletMigration for FastUnstakeMigrator<T> {
+	type Key = ();
+	type Error = Error<T>;
+
+	fn migrate_many(
+		current_key: Option<Self::Key>,
+		weight_counter: &mut WeightMeter,
+	) -> Result<Option<Self::Key>, Self::Error> {
+		let mut inner_key = current_key;
+		let mut messages = XcmBatchAndMeter::new_from_config::<T>();
+
+		if current_key.is_none() {
+			// Stop processing the head in `on_idle` and drain it at once, it holds at most
+			// `BatchSize` requests.
+			let stashes = pallet_fast_unstake::Head::<T>::get()
+				.map(|head| head.stashes.into_inner())
+				.unwrap_or_default();
+			let len = stashes.len() as u64;
+			if weight_counter
+				.try_consume(T::DbWeight::get().reads_writes(1 + len, 2 + len))
+				.is_err()
+			{
+				return Err(Error::OutOfWeight);
+			}
+			pallet_fast_unstake::ErasToCheckPerBlock::<T>::put(0);
+			pallet_fast_unstake::Head::<T>::kill();
+			for (stash, deposit) in stashes {
+				log::debug!(target: LOG_TARGET, "Migrating fast-unstake head request");
+				Self::drain_request(stash, deposit, &mut messages);
+			}
+			inner_key = Some(());
+		}
+
+		loop {
+			if weight_counter.try_consume(T::DbWeight::get().reads_writes(2, 2)).is_err() ||
+				weight_counter.try_consume(messages.consume_weight()).is_err()
+			{
+				log::info!(
+					target: LOG_TARGET,
+					"RC weight limit reached at batch length {}, stopping",
+					messages.len()
+				);
+				if messages.is_empty() {
+					return Err(Error::OutOfWeight);
+				} else {
+					break;
+				}
+			}
+			if T::MaxAhWeight::get()
+				.any_lt(T::AhWeightInfo::receive_fast_unstake_messages(messages.len() + 1))
+			{
+				log::info!(
+					target: LOG_TARGET,
+					"AH weight limit reached at batch length {}, stopping",
+					messages.len()
+				);
+				if messages.is_empty() {
+					return Err(Error::OutOfWeight);
+				} else {
+					break;
+				}
+			}
+
+			if messages.len() > max_items_per_block::<T>() {
+				log::info!(
+					target: LOG_TARGET,
+					"Maximum number of items ({:?}) to migrate per block reached, current batch size: {}",
+					max_items_per_block::<T>(),
+					messages.len()
+				);
+				break;
+			}
+
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
+				log::info!(
+					target: LOG_TARGET,
+					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
+					max_xcm_msg_per_block::<T>(),
+					messages.batch_count()
+				);
+				break;
+			}
+
+			match pallet_fast_unstake::Queue::<T>::iter().next() {
+				Some((stash, deposit)) => {
+					pallet_fast_unstake::Queue::<T>::remove(&stash);
+					log::debug!(target: LOG_TARGET, "Migrating fast-unstake queue request");
+					Self::drain_request(stash, deposit, &mut messages);
+				},
+				None => {
+					inner_key = None;
+					break;
+				},
+			}
+		}
+
+		if !messages.is_empty() {
+			Pallet::<T>::send_chunked_xcm_and_track(messages, |messages| {
+				types::AhMigratorCall::<T>::ReceiveFastUnstakeMessages { messages }
+			})?;
+		}
+
+		Ok(inner_key)
+	}
+}
+
+impl<T: Config> FastUnstakeMigrator<T> {
+	/// Release the deposit of a removed request.
+	///
+	/// The deposit is unreserved on the Relay Chain if the stash is preserved there, otherwise the
+	/// request is sent to the Asset Hub, where the deposit arrived with the account.
+	fn drain_request(
+		stash: AccountIdOf<T>,
+		deposit: BalanceOf<T>,
+		messages: &mut XcmBatchAndMeter<RcFastUnstakeRequestOf<T>>,
+	) {
+		if !RcAccounts::<T>::get(&stash).is_some_and(|state| state.is_preserve()) {
+			messages.push(RcFastUnstakeRequest { stash, deposit });
+			return;
+		}
+
+		let missing = <T as Config>::Currency::unreserve(&stash, deposit);
+		if !missing.is_zero() {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to unreserve fast-unstake deposit of preserved account {:?}: missing {:?}",
+				stash,
+				missing
+			);
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> crate::types::RcMigrationCheck for FastUnstakeMigrator<T> {
+	type RcPrePayload = Vec<RcFastUnstakeRequestOf<T>>;
+
+	fn pre_check() -> Self::RcPrePayload {
+		pallet_fast_unstake::Head::<T>::get()
+			.into_iter()
+			.flat_map(|head| head.stashes)
+			.chain(pallet_fast_unstake::Queue::<T>::iter())
+			.map(|(stash, deposit)| RcFastUnstakeRequest { stash, deposit })
+			.collect()
+	}
+
+	fn post_check(_: Self::RcPrePayload) {
+		assert!(
+			pallet_fast_unstake::Head::<T>::get().is_none(),
+			"Assert storage 'FastUnstake::Head::rc_post::empty'"
+		);
+		assert_eq!(
+			pallet_fast_unstake::Queue::<T>::count(),
+			0,
+			"Assert storage 'FastUnstake::Queue::rc_post::empty'"
+		);
+		assert_eq!(
+			pallet_fast_unstake::ErasToCheckPerBlock::<T>::get(),
+			0,
+			"Assert storage 'FastUnstake::ErasToCheckPerBlock::rc_post::zero'"
+		);
+	}
+}
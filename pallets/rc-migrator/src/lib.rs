@@ -32,13 +32,24 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod accounts;
+pub mod call_report;
 pub mod claims;
 pub mod crowdloan;
+pub mod digest;
+pub mod dry_run;
+pub mod fast_unstake;
 pub mod indices;
+pub mod manager_multisig;
+#[cfg(feature = "std")]
+pub mod message_log;
+pub mod migrations;
 pub mod multisig;
+pub mod para_reserves;
 pub mod preimage;
 pub mod proxy;
 pub mod referenda;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod staking;
 pub mod types;
 pub mod vesting;
@@ -54,8 +65,11 @@ pub mod conviction_voting;
 #[cfg(feature = "kusama-ahm")]
 pub mod recovery;
 pub mod scheduler;
+pub mod sequence;
 #[cfg(feature = "kusama-ahm")]
 pub mod society;
+pub mod sovereign_account_translation;
+pub mod status;
 pub mod treasury;
 pub mod xcm_config;
 
@@ -97,6 +111,7 @@ use preimage::{
 };
 use proxy::*;
 use referenda::ReferendaStage;
+use sequence::MigratedPallet;
 use runtime_parachains::{
 	hrmp,
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
@@ -104,7 +119,7 @@ use runtime_parachains::{
 use sp_core::{crypto::Ss58Codec, H256};
 use sp_runtime::{
 	traits::{BadOrigin, BlockNumberProvider, Dispatchable, Hash, IdentifyAccount, One, Zero},
-	AccountId32, Saturating,
+	AccountId32, Perbill, Saturating,
 };
 use sp_std::prelude::*;
 use staking::{
@@ -348,6 +363,7 @@ pub enum MigrationStage<
 		last_key: Option<crowdloan::CrowdloanStage>,
 	},
 	CrowdloanMigrationDone,
+
 	TreasuryMigrationInit,
 	TreasuryMigrationOngoing {
 		last_key: Option<treasury::TreasuryStage>,
@@ -384,6 +400,19 @@ pub enum MigrationStage<
 	},
 	SignalMigrationFinish,
 	MigrationDone,
+
+	// Stages of pallets that were added to the migration later. They are appended to keep the
+	// encoding of the stages above, the order of the migration is defined by the sequence.
+	FastUnstakeMigrationInit,
+	FastUnstakeMigrationOngoing {
+		last_key: Option<()>,
+	},
+	FastUnstakeMigrationDone,
+	ParaReservesMigrationInit,
+	ParaReservesMigrationOngoing {
+		last_key: Option<para_reserves::ParaReservesStage>,
+	},
+	ParaReservesMigrationDone,
 }
 
 impl<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlockNumber>
@@ -427,6 +456,7 @@ impl<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlo
 		Ok(match s {
 			"skip-accounts" => MigrationStage::AccountsMigrationDone,
 			"crowdloan" => MigrationStage::CrowdloanMigrationInit,
+			"para_reserves" => MigrationStage::ParaReservesMigrationInit,
 			"preimage" => MigrationStage::PreimageMigrationInit,
 			"referenda" => MigrationStage::ReferendaMigrationInit,
 			"multisig" => MigrationStage::MultisigMigrationInit,
@@ -438,6 +468,7 @@ impl<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlo
 			"proxy" => MigrationStage::ProxyMigrationInit,
 			"nom_pools" => MigrationStage::NomPoolsMigrationInit,
 			"scheduler" => MigrationStage::SchedulerMigrationInit,
+			"fast_unstake" => MigrationStage::FastUnstakeMigrationInit,
 			"staking" => MigrationStage::StakingMigrationInit,
 			#[cfg(feature = "kusama-ahm")]
 			"society" => MigrationStage::SocietyMigrationInit,
@@ -462,24 +493,160 @@ pub struct MigrationSettings {
 	///
 	/// Overrides [MAX_ITEMS_PER_BLOCK] if set.
 	pub max_items_per_block: Option<u32>,
+	/// Limits for the stages of individual pallets.
+	///
+	/// Take precedence over the global limits above while the pallet is being migrated.
+	pub stage_limits: BoundedVec<(MigratedPallet, StageLimits), ConstU32<MAX_STAGE_LIMITS>>,
+}
+
+impl MigrationSettings {
+	/// Whether no limit is set to zero items or messages, which would stall the migration.
+	pub fn is_valid(&self) -> bool {
+		let non_zero = |limit: Option<u32>| limit != Some(0);
+		non_zero(self.max_accounts_per_block) &&
+			non_zero(self.max_items_per_block) &&
+			self.stage_limits.iter().all(|(_, limits)| {
+				non_zero(limits.max_items_per_block) && non_zero(limits.max_xcm_msg_per_block)
+			})
+	}
+}
+
+/// The maximum number of entries in [`MigrationSettings::stage_limits`].
+pub const MAX_STAGE_LIMITS: u32 = 32;
+
+/// Per block limits of the stages of a single pallet.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Default,
+	Debug,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+)]
+pub struct StageLimits {
+	/// The maximum number of items that can be extracted and migrated in a single block.
+	///
+	/// Overrides [MigrationSettings::max_items_per_block] and
+	/// [MigrationSettings::max_accounts_per_block] if set.
+	pub max_items_per_block: Option<u32>,
+	/// The maximum number of XCM messages that can be sent in a single block.
+	///
+	/// Overrides [MAX_XCM_MSG_PER_BLOCK] if set.
+	pub max_xcm_msg_per_block: Option<u32>,
+	/// The fraction of [`Config::MaxRcWeight`] that can be used in a single block.
+	pub weight_fraction: Option<Perbill>,
+}
+
+/// The limits of the migration in the current block.
+///
+/// Computed from [`Settings`] and the current stage once at the beginning of the block and kept in
+/// [`BlockLimits`], since the migrators look them up for every item. A stage entered during the
+/// block is subject to its limits from the next block on.
+#[derive(
+	Encode, DecodeWithMemTracking, Decode, Clone, Copy, Debug, TypeInfo, MaxEncodedLen, PartialEq,
+)]
+pub struct EffectiveLimits {
+	/// See [`stage_max_items_per_block`].
+	pub stage_max_items_per_block: Option<u32>,
+	/// See [`max_items_per_block`].
+	pub max_items_per_block: u32,
+	/// See [`max_accounts_per_block`].
+	pub max_accounts_per_block: u32,
+	/// See [`max_xcm_msg_per_block`].
+	pub max_xcm_msg_per_block: u32,
+	/// See [`max_rc_weight`].
+	pub max_rc_weight: Weight,
+}
+
+impl EffectiveLimits {
+	/// Compute the limits from the settings in storage and the current stage.
+	pub fn compute<T: Config>() -> Self {
+		let settings = Settings::<T>::get();
+		let stage_limits = RcMigrationStage::<T>::get()
+			.pallet()
+			.and_then(|pallet| {
+				settings.as_ref().and_then(|settings| {
+					settings
+						.stage_limits
+						.iter()
+						.find_map(|(limited, limits)| (*limited == pallet).then(|| limits.clone()))
+				})
+			})
+			.unwrap_or_default();
+
+		let max_items_per_block = stage_limits
+			.max_items_per_block
+			.or_else(|| settings.as_ref().and_then(|settings| settings.max_items_per_block))
+			.unwrap_or(MAX_ITEMS_PER_BLOCK);
+		let max_accounts_per_block = stage_limits
+			.max_items_per_block
+			.or_else(|| settings.as_ref().and_then(|settings| settings.max_accounts_per_block))
+			.unwrap_or(max_items_per_block);
+		let max_rc_weight = {
+			let max_weight = T::MaxRcWeight::get();
+			match stage_limits.weight_fraction {
+				Some(fraction) => Weight::from_parts(
+					fraction * max_weight.ref_time(),
+					fraction * max_weight.proof_size(),
+				),
+				None => max_weight,
+			}
+		};
+
+		Self {
+			stage_max_items_per_block: stage_limits.max_items_per_block,
+			max_items_per_block,
+			max_accounts_per_block,
+			max_xcm_msg_per_block: stage_limits
+				.max_xcm_msg_per_block
+				.unwrap_or(MAX_XCM_MSG_PER_BLOCK),
+			max_rc_weight,
+		}
+	}
+}
+
+/// The limits of the current block, see [`EffectiveLimits`].
+///
+/// Computed on the fly outside of `on_initialize`, e.g. in benchmarks.
+fn limits<T: Config>() -> EffectiveLimits {
+	BlockLimits::<T>::get().unwrap_or_else(EffectiveLimits::compute::<T>)
+}
+
+/// The maximum number of items of the current pallet that can be migrated in a single block, if
+/// set in [`MigrationSettings::stage_limits`].
+pub fn stage_max_items_per_block<T: Config>() -> Option<u32> {
+	limits::<T>().stage_max_items_per_block
 }
 
 /// The maximum number of items that can be extracted and migrated in a single block.
 ///
 /// Returns constant [MAX_ITEMS_PER_BLOCK] if no settings are set in storage by the manager.
 pub fn max_items_per_block<T: Config>() -> u32 {
-	Settings::<T>::get()
-		.and_then(|settings| settings.max_items_per_block)
-		.unwrap_or(MAX_ITEMS_PER_BLOCK)
+	limits::<T>().max_items_per_block
 }
 
 /// The maximum number of accounts that can be extracted and migrated in a single block.
 ///
 /// Returns constant [MAX_ITEMS_PER_BLOCK] if no settings are set in storage by the manager.
 pub fn max_accounts_per_block<T: Config>() -> u32 {
-	Settings::<T>::get()
-		.and_then(|settings| settings.max_accounts_per_block)
-		.unwrap_or(max_items_per_block::<T>())
+	limits::<T>().max_accounts_per_block
+}
+
+/// The maximum number of XCM messages that can be sent in a single block.
+///
+/// Returns constant [MAX_XCM_MSG_PER_BLOCK] if no settings are set in storage by the manager.
+pub fn max_xcm_msg_per_block<T: Config>() -> u32 {
+	limits::<T>().max_xcm_msg_per_block
+}
+
+/// The maximum Relay Chain weight that the migration can use in a single block.
+///
+/// Returns [`Config::MaxRcWeight`] if no settings are set in storage by the manager.
+pub fn max_rc_weight<T: Config>() -> Weight {
+	limits::<T>().max_rc_weight
 }
 
 #[frame_support::pallet]
@@ -539,9 +706,15 @@ pub mod pallet {
 			+ Member
 			+ Parameter;
 		/// The runtime hold reasons.
-		type RuntimeHoldReason: Parameter
-			+ VariantCount
-			+ IntoPortable<Portable = types::PortableHoldReason>;
+		type RuntimeHoldReason: Parameter + VariantCount;
+		/// Translates the hold reasons of the migrated accounts into their portable format.
+		///
+		/// Holds with an unmapped reason are handled according to the registry's
+		/// [`types::UnmappedReasonPolicy`].
+		type HoldReasonRegistry: types::ReasonRegistry<
+			<Self as Config>::RuntimeHoldReason,
+			Target = types::PortableHoldReason,
+		>;
 
 		/// Config for pallets that are only on Kusama.
 		#[cfg(feature = "kusama-ahm")]
@@ -582,9 +755,15 @@ pub mod pallet {
 		type SessionDuration: Get<u64>;
 
 		/// The runtime freeze reasons.
-		type RuntimeFreezeReason: Parameter
-			+ VariantCount
-			+ IntoPortable<Portable = types::PortableFreezeReason>;
+		type RuntimeFreezeReason: Parameter + VariantCount;
+		/// Translates the freeze reasons of the migrated accounts into their portable format.
+		///
+		/// Freezes with an unmapped reason are handled according to the registry's
+		/// [`types::UnmappedReasonPolicy`].
+		type FreezeReasonRegistry: types::ReasonRegistry<
+			<Self as Config>::RuntimeFreezeReason,
+			Target = types::PortableFreezeReason,
+		>;
 
 		/// The overarching event type.
 		#[allow(deprecated)]
@@ -637,8 +816,26 @@ pub mod pallet {
 		type UnprocessedMsgBuffer: Get<u32>;
 
 		/// The timeout for the XCM response.
+		///
+		/// A data message without a response after this many blocks is reported with
+		/// [`Event::XcmResponseTimedOut`] and the migration is paused.
 		type XcmResponseTimeout: Get<BlockNumberFor<Self>>;
 
+		/// Number of blocks after a failed response from the Asset Hub after which a data message
+		/// is resent automatically.
+		///
+		/// Doubles with every retry of the same message. Messages without a response are never
+		/// resent automatically, since they might still be processed and the Asset Hub does not
+		/// deduplicate them. Once their [`Config::XcmResponseTimeout`] passes the migration is
+		/// paused instead, so that they can be resent via [`Pallet::resend_xcm`].
+		type XcmRetryInterval: Get<BlockNumberFor<Self>>;
+
+		/// Maximum number of automatic resends of a single data message.
+		///
+		/// Once a message exceeds it, the migration is paused and the message must be resent
+		/// manually via [`Pallet::resend_xcm`].
+		type MaxXcmRetries: Get<u32>;
+
 		/// Means to force a next queue within the UMPs from different parachains.
 		type MessageQueue: ForceSetHead<AggregateMessageOrigin>
 			+ EnqueueMessage<AggregateMessageOrigin>;
@@ -665,6 +862,11 @@ pub mod pallet {
 
 		/// Limit the number of votes of each participant per round.
 		type MultisigMaxVotesPerRound: Get<u32>;
+
+		/// The pallets to migrate and the order to migrate them in.
+		///
+		/// Use [`sequence::DefaultMigrationSequence`] to migrate every pallet.
+		type MigrationSequence: sequence::MigrationSequence;
 	}
 
 	#[pallet::error]
@@ -707,6 +909,20 @@ pub mod pallet {
 		InvalidStageTransition,
 		/// Unsigned validation failed.
 		UnsignedValidationFailed,
+		/// The pallet is not part of [`Config::MigrationSequence`].
+		PalletNotInSequence,
+		/// The pallet migration was already skipped.
+		PalletAlreadySkipped,
+		/// There is no paused stage to resume from.
+		NotPaused,
+		/// The pallet is mandatory and can not be skipped.
+		PalletMandatory,
+		/// The pallet is being migrated or was already migrated.
+		PalletMigrationStarted,
+		/// A pallet that is still to be migrated depends on the pallet.
+		PalletIsDependency,
+		/// XCM messages sent before the pause are still unconfirmed by the Asset Hub.
+		PendingXcmMessagesOnResume,
 	}
 
 	#[pallet::event]
@@ -746,6 +962,45 @@ pub mod pallet {
 			/// The error message.
 			send_error: Option<SendError>,
 		},
+		/// The digest of the data sent for a pallet was sent to the Asset Hub for verification.
+		MigrationDigestSent {
+			/// The migrated pallet.
+			pallet: MigratedPallet,
+			/// The digest of the sent items.
+			digest: digest::MigrationDigest,
+		},
+		/// An XCM message that failed on the Asset Hub was resent automatically.
+		XcmRetryAttempt {
+			/// The query ID of the message before the resend.
+			query_id: u64,
+			/// The query ID of the resent message.
+			new_query_id: u64,
+			/// The number of automatic resends of the message so far.
+			attempt: u32,
+			/// The error message.
+			send_error: Option<SendError>,
+		},
+		/// An XCM message failed after [`Config::MaxXcmRetries`] resends and the migration was
+		/// paused.
+		XcmRetryGaveUp {
+			/// The query ID of the message.
+			query_id: u64,
+			/// The number of automatic resends of the message.
+			attempts: u32,
+		},
+		/// An XCM message got no response within [`Config::XcmResponseTimeout`] and the
+		/// migration was paused.
+		XcmResponseTimedOut {
+			/// The query ID of the message.
+			query_id: u64,
+		},
+		/// An XCM message that was resent automatically was confirmed by the Asset Hub.
+		XcmRetryRecovered {
+			/// The query ID of the message.
+			query_id: u64,
+			/// The number of automatic resends of the message.
+			attempts: u32,
+		},
 		/// The unprocessed message buffer size has been set.
 		UnprocessedMsgBufferSet {
 			/// The new size.
@@ -803,6 +1058,13 @@ pub mod pallet {
 			/// The stage at which the migration was paused.
 			pause_stage: MigrationStageOf<T>,
 		},
+		/// The migration was resumed.
+		MigrationResumed {
+			/// The stage at which the migration was resumed.
+			resume_stage: MigrationStageOf<T>,
+			/// The number of unconfirmed XCM messages at the time of resuming.
+			pending_xcm_messages: u32,
+		},
 		/// The migration was cancelled.
 		MigrationCancelled,
 		/// Some pure accounts were indexed for possibly receiving free `Any` proxies.
@@ -814,6 +1076,18 @@ pub mod pallet {
 		ManagerMultisigDispatched { res: DispatchResult },
 		/// The manager multisig received a vote.
 		ManagerMultisigVoted { votes: u32 },
+		/// A member withdrew their vote from a manager multisig proposal.
+		ManagerMultisigVoteWithdrawn {
+			/// The hash of the proposed call.
+			call_hash: T::Hash,
+			/// The number of remaining votes.
+			votes: u32,
+		},
+		/// An expired manager multisig proposal was removed or replaced by a new one.
+		ManagerMultisigProposalExpired {
+			/// The hash of the proposed call.
+			call_hash: T::Hash,
+		},
 		/// The migration settings were set.
 		MigrationSettingsSet {
 			/// The old migration settings.
@@ -821,6 +1095,17 @@ pub mod pallet {
 			/// The new migration settings.
 			new: Option<MigrationSettings>,
 		},
+		/// The migration of a pallet was skipped.
+		PalletMigrationSkipped {
+			/// The skipped pallet.
+			pallet: MigratedPallet,
+		},
+		/// The migration was paused at its start since accounts have a hold or freeze reason
+		/// whose [`types::UnmappedReasonPolicy`] refuses their migration.
+		AccountsRefused {
+			/// The number of refused accounts.
+			accounts: u32,
+		},
 	}
 
 	/// The Relay Chain migration state.
@@ -853,6 +1138,13 @@ pub mod pallet {
 	pub type RcMigratedBalanceArchive<T: Config> =
 		StorageValue<_, MigratedBalances<T::Balance>, ValueQuery>;
 
+	/// Totals of the holds and vesting schedules sent to the Asset Hub.
+	///
+	/// Sent to the Asset Hub together with the `RcMigratedBalance` to reconcile the migration.
+	#[pallet::storage]
+	pub type RcMigratedTotals<T: Config> =
+		StorageValue<_, types::MigratedTotals<types::PortableHoldReason, T::Balance>, ValueQuery>;
+
 	/// The pending XCM messages.
 	///
 	/// Contains data messages that have been sent to the Asset Hub but not yet confirmed.
@@ -879,7 +1171,27 @@ pub mod pallet {
 	/// response.
 	#[pallet::storage]
 	pub type PendingXcmQueries<T: Config> =
-		StorageMap<_, Twox64Concat, QueryId, T::Hash, OptionQuery>;
+		CountedStorageMap<_, Twox64Concat, QueryId, T::Hash, OptionQuery>;
+
+	/// Digest of the items sent to the Asset Hub per migrated pallet.
+	///
+	/// See [`digest`] for details.
+	#[pallet::storage]
+	pub type MigrationDigests<T: Config> =
+		StorageMap<_, Twox64Concat, MigratedPallet, digest::MigrationDigest, ValueQuery>;
+
+	/// Automatic retry state of the messages in [`PendingXcmMessages`] that failed on the Asset
+	/// Hub or were resent, keyed by message hash.
+	#[pallet::storage]
+	pub type PendingXcmRetries<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, types::XcmRetry<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The queries of [`PendingXcmQueries`] by the block at which their
+	/// [`Config::XcmResponseTimeout`] passes.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type XcmResponseDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<QueryId>, ValueQuery>;
 
 	/// Manual override for `type UnprocessedMsgBuffer: Get<u32>`. Look there for docs.
 	#[pallet::storage]
@@ -943,6 +1255,30 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Settings<T: Config> = StorageValue<_, MigrationSettings, OptionQuery>;
 
+	/// The limits of the migration in the current block, see [`EffectiveLimits`].
+	///
+	/// Set in `on_initialize` and removed in `on_finalize`.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type BlockLimits<T: Config> = StorageValue<_, EffectiveLimits, OptionQuery>;
+
+	/// Number of items sent to the Asset Hub since the current stage was entered.
+	///
+	/// Reset whenever the migration moves on to a different stage.
+	#[pallet::storage]
+	pub type StageMigratedItems<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The stage at which the migration was paused, including its cursor.
+	///
+	/// Set by [`Pallet::pause_migration`] and taken by [`Pallet::resume_migration`].
+	#[pallet::storage]
+	pub type PausedStage<T: Config> = StorageValue<_, MigrationStageOf<T>, OptionQuery>;
+
+	/// Pallets whose migration was skipped and the block at which they were skipped.
+	#[pallet::storage]
+	pub type SkippedPallets<T: Config> =
+		StorageMap<_, Twox64Concat, MigratedPallet, BlockNumberFor<T>, OptionQuery>;
+
 	/// Alias for `Paras` from `paras_registrar`.
 	///
 	/// The fields of the type stored in the original storage item are private, so we define the
@@ -958,7 +1294,11 @@ pub mod pallet {
 		>,
 	>;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::call]
@@ -1087,11 +1427,25 @@ pub mod pallet {
 				);
 				PendingXcmMessages::<T>::remove((query_id, message_hash));
 				PendingXcmQueries::<T>::remove(query_id);
+				if let Some(retry) = PendingXcmRetries::<T>::take(message_hash) {
+					if retry.query_id != query_id {
+						// A late response to an earlier send, the resent message is obsolete.
+						PendingXcmMessages::<T>::remove((retry.query_id, message_hash));
+						PendingXcmQueries::<T>::remove(retry.query_id);
+					}
+					if retry.attempts > 0 {
+						Self::deposit_event(Event::<T>::XcmRetryRecovered {
+							query_id,
+							attempts: retry.attempts,
+						});
+					}
+				}
 			} else {
 				log::error!(
 					target: LOG_TARGET,
 					"Received error response for query id: {query_id}; response: {response:?}"
 				);
+				Self::schedule_xcm_retry(query_id, message_hash);
 			}
 
 			Self::deposit_event(Event::<T>::QueryResponseReceived { query_id, response });
@@ -1105,49 +1459,8 @@ pub mod pallet {
 		pub fn resend_xcm(origin: OriginFor<T>, query_id: u64) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 
-			let message_hash =
-				PendingXcmQueries::<T>::get(query_id).ok_or(Error::<T>::QueryNotFound)?;
-			let xcm = PendingXcmMessages::<T>::get((query_id, message_hash))
-				.ok_or(Error::<T>::QueryNotFound)?;
-
-			let asset_hub_location = Location::new(0, Parachain(1000));
-			let receive_notification_call =
-				Call::<T>::receive_query_response { query_id: 0, response: Default::default() };
-
-			let new_query_id = pallet_xcm::Pallet::<T>::new_notify_query(
-				asset_hub_location.clone(),
-				<T as Config>::RuntimeCall::from(receive_notification_call),
-				frame_system::Pallet::<T>::block_number() + T::XcmResponseTimeout::get(),
-				Location::here(),
-			);
-
-			let xcm_with_report = {
-				let mut xcm = xcm.clone();
-				xcm.inner_mut().push(SetAppendix(Xcm(vec![ReportTransactStatus(
-					QueryResponseInfo {
-						destination: Location::parent(),
-						query_id: new_query_id,
-						max_weight: T::RcWeightInfo::receive_query_response(),
-					},
-				)])));
-				xcm
-			};
-
-			if let Err(err) = send_xcm::<T::SendXcm>(asset_hub_location, xcm_with_report) {
-				log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
-				Self::deposit_event(Event::<T>::XcmResendAttempt {
-					query_id: new_query_id,
-					send_error: Some(err),
-				});
-			} else {
-				PendingXcmMessages::<T>::remove((query_id, message_hash));
-				PendingXcmMessages::<T>::insert((new_query_id, message_hash), xcm);
-				PendingXcmQueries::<T>::insert(new_query_id, message_hash);
-				Self::deposit_event(Event::<T>::XcmResendAttempt {
-					query_id: new_query_id,
-					send_error: None,
-				});
-			}
+			let (query_id, send_error) = Self::do_resend_xcm(query_id, false)?;
+			Self::deposit_event(Event::<T>::XcmResendAttempt { query_id, send_error });
 
 			Ok(Pays::No.into())
 		}
@@ -1322,9 +1635,7 @@ pub mod pallet {
 		pub fn pause_migration(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			Self::ensure_admin_or_manager(origin.clone())?;
 
-			let pause_stage = RcMigrationStage::<T>::get();
-			Self::transition(MigrationStage::MigrationPaused);
-			Self::deposit_event(Event::MigrationPaused { pause_stage });
+			Self::do_pause_migration()?;
 
 			Ok(Pays::No.into())
 		}
@@ -1353,11 +1664,14 @@ pub mod pallet {
 		///
 		/// Unsigned extrinsic, requiring the `payload` to be signed.
 		///
-		/// Upon each call, a new entry is created in `ManagerMultisigs` map the `payload.call` to
-		/// be dispatched. Once `MultisigThreshold` is reached, the entire map is deleted, and we
-		/// move on to the next round.
+		/// The first vote for `payload.call` opens a proposal in `ManagerMultisigs` that expires
+		/// at `payload.expires_at`; further votes must carry the same expiry. Once
+		/// `MultisigThreshold` is reached, the call is dispatched, only its proposal is removed and
+		/// we move on to the next round. Votes on other proposals stay in place.
 		///
 		/// The round system ensures that signatures from older round cannot be reused.
+		/// `payload.nonce` must equal the number of votes of the member in the current round, so
+		/// that the signature can not be replayed after the member withdrew the vote.
 		#[pallet::call_index(13)]
 		#[pallet::weight({ Weight::from_parts(10_000_000, 1000) })]
 		pub fn vote_manager_multisig(
@@ -1373,20 +1687,36 @@ pub mod pallet {
 
 			ensure!(ManagerMultisigRound::<T>::get() == payload.round, "RoundStale");
 			let num_votes = ManagerVotesInCurrentRound::<T>::get(&who);
+			ensure!(num_votes == payload.nonce, "BadNonce");
 			ensure!(num_votes < T::MultisigMaxVotesPerRound::get(), "MaxVotesPerRound");
 			ManagerVotesInCurrentRound::<T>::insert(&who, num_votes.saturating_add(1));
 
-			let mut votes_for_call = ManagerMultisigs::<T>::get(&payload.call);
-			ensure!(!votes_for_call.contains(&who), "Duplicate");
-			votes_for_call.push(who);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(payload.expires_at > now, "Expired");
+			let mut proposal = match ManagerMultisigs::<T>::get(&payload.call) {
+				Some(proposal) if !proposal.is_expired(&now) => {
+					ensure!(proposal.expires_at == payload.expires_at, "ExpiryMismatch");
+					proposal
+				},
+				expired => {
+					if expired.is_some() {
+						Self::deposit_event(Event::ManagerMultisigProposalExpired {
+							call_hash: T::Hashing::hash_of(&payload.call),
+						});
+					}
+					manager_multisig::ManagerMultisigProposal::new(payload.expires_at)
+				},
+			};
+			let votes = proposal.vote(who)?;
 
-			if votes_for_call.len() >= T::MultisigThreshold::get() as usize {
+			if votes >= T::MultisigThreshold::get() {
 				let origin: <T as Config>::RuntimeOrigin =
 					frame_system::RawOrigin::Signed(Self::manager_multisig_id()).into();
 				let call = payload.call.clone();
 				let res = call.dispatch(origin);
-				let _ = ManagerMultisigs::<T>::clear(u32::MAX, None);
+				ManagerMultisigs::<T>::remove(&payload.call);
 				let _ = ManagerVotesInCurrentRound::<T>::clear(u32::MAX, None);
+				let _ = ManagerWithdrawalsInCurrentRound::<T>::clear(u32::MAX, None);
 				debug_assert!(ManagerVotesInCurrentRound::<T>::iter_keys().next().is_none());
 
 				Self::deposit_event(Event::ManagerMultisigDispatched {
@@ -1394,16 +1724,58 @@ pub mod pallet {
 				});
 				ManagerMultisigRound::<T>::mutate(|r| *r += 1);
 			} else {
-				Self::deposit_event(Event::ManagerMultisigVoted {
-					votes: votes_for_call.len() as u32,
-				});
-				ManagerMultisigs::<T>::insert(payload.call, votes_for_call);
+				Self::deposit_event(Event::ManagerMultisigVoted { votes });
+				ManagerMultisigs::<T>::insert(payload.call, proposal);
+			}
+
+			Ok(())
+		}
+
+		/// Withdraw the vote of any of the members in `MultisigMembers` from a proposal.
+		///
+		/// Unsigned extrinsic, requiring the `payload` to be signed.
+		///
+		/// `payload.nonce` must equal the number of withdrawals of the member in the current round,
+		/// so that the signature can not be replayed after the member voted again. Withdrawals
+		/// do not count towards `MultisigMaxVotesPerRound`, but are limited to the same number
+		/// per round. The proposal is removed once its last vote is withdrawn.
+		#[pallet::call_index(17)]
+		#[pallet::weight({ Weight::from_parts(10_000_000, 1000) })]
+		pub fn withdraw_manager_vote(
+			origin: OriginFor<T>,
+			payload: Box<ManagerMultisigWithdrawal<T>>,
+			sig: sp_runtime::MultiSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Self::do_validate_unsigned_withdrawal(&payload, &sig)
+				.map_err(|_| Error::<T>::UnsignedValidationFailed)?;
+			let who = payload.who.into_account();
+
+			ensure!(ManagerMultisigRound::<T>::get() == payload.round, "RoundStale");
+			let num_withdrawals = ManagerWithdrawalsInCurrentRound::<T>::get(&who);
+			ensure!(num_withdrawals == payload.nonce, "BadNonce");
+			ensure!(num_withdrawals < T::MultisigMaxVotesPerRound::get(), "MaxWithdrawalsPerRound");
+			ManagerWithdrawalsInCurrentRound::<T>::insert(&who, num_withdrawals.saturating_add(1));
+
+			let mut proposal = ManagerMultisigs::<T>::get(&payload.call).ok_or("NoProposal")?;
+			let votes = proposal.withdraw(&who)?;
+			if votes.is_zero() {
+				ManagerMultisigs::<T>::remove(&payload.call);
+			} else {
+				ManagerMultisigs::<T>::insert(&payload.call, proposal);
 			}
+			Self::deposit_event(Event::ManagerMultisigVoteWithdrawn {
+				call_hash: T::Hashing::hash_of(&payload.call),
+				votes,
+			});
 
 			Ok(())
 		}
 
 		/// Set the migration settings. Can only be done by admin or manager.
+		///
+		/// Limits of zero items or zero XCM messages per block are rejected.
 		#[pallet::call_index(14)]
 		#[pallet::weight({ Weight::from_parts(10_000_000, 1000) })]
 		pub fn set_settings(
@@ -1411,11 +1783,72 @@ pub mod pallet {
 			settings: Option<MigrationSettings>,
 		) -> DispatchResult {
 			Self::ensure_admin_or_manager(origin)?;
+			ensure!(
+				settings.as_ref().is_none_or(MigrationSettings::is_valid),
+				Error::<T>::InvalidParameter
+			);
 			let old = Settings::<T>::get();
 			Settings::<T>::set(settings.clone());
 			Self::deposit_event(Event::MigrationSettingsSet { old, new: settings });
 			Ok(())
 		}
+
+		/// Resume the migration at the stage, and cursor, at which it was paused.
+		///
+		/// Messages sent before the pause may still be unconfirmed by the Asset Hub. Unless
+		/// `force` is set, resuming fails until all of them are confirmed, so that the Asset Hub
+		/// state matches the cursor of the resumed stage. With `force`, unconfirmed messages stay
+		/// in [`PendingXcmMessages`] and can be resent via [`Pallet::resend_xcm`].
+		#[pallet::call_index(16)]
+		#[pallet::weight({ Weight::from_parts(10_000_000, 1000) })]
+		pub fn resume_migration(origin: OriginFor<T>, force: bool) -> DispatchResultWithPostInfo {
+			Self::ensure_admin_or_manager(origin)?;
+			ensure!(
+				RcMigrationStage::<T>::get() == MigrationStage::MigrationPaused,
+				Error::<T>::NotPaused
+			);
+			let resume_stage = PausedStage::<T>::get().ok_or(Error::<T>::NotPaused)?;
+
+			let pending_xcm_messages = PendingXcmMessages::<T>::count();
+			ensure!(
+				force || pending_xcm_messages == 0,
+				Error::<T>::PendingXcmMessagesOnResume
+			);
+
+			Self::transition(resume_stage.clone());
+			Self::deposit_event(Event::MigrationResumed { resume_stage, pending_xcm_messages });
+
+			Ok(Pays::No.into())
+		}
+
+		/// Skip the migration of a pallet that is still to be migrated.
+		///
+		/// Mandatory pallets, pallets whose migration already started and pallets that another
+		/// pallet still to be migrated depends on can not be skipped.
+		#[pallet::call_index(15)]
+		#[pallet::weight({ Weight::from_parts(10_000_000, 1000) })]
+		pub fn skip_pallet_migration(
+			origin: OriginFor<T>,
+			pallet: MigratedPallet,
+		) -> DispatchResult {
+			Self::ensure_admin_or_manager(origin)?;
+			ensure!(
+				T::MigrationSequence::pallets().contains(&pallet),
+				Error::<T>::PalletNotInSequence
+			);
+			ensure!(!SkippedPallets::<T>::contains_key(pallet), Error::<T>::PalletAlreadySkipped);
+			ensure!(!pallet.is_mandatory(), Error::<T>::PalletMandatory);
+			ensure!(Self::pallet_migration_pending(pallet), Error::<T>::PalletMigrationStarted);
+			ensure!(
+				!Self::remaining_pallets()
+					.any(|remaining| remaining.dependencies().contains(&pallet)),
+				Error::<T>::PalletIsDependency
+			);
+
+			SkippedPallets::<T>::insert(pallet, frame_system::Pallet::<T>::block_number());
+			Self::deposit_event(Event::PalletMigrationSkipped { pallet });
+			Ok(())
+		}
 	}
 
 	impl<T: frame_system::Config> Pallet<T> {
@@ -1440,6 +1873,8 @@ pub mod pallet {
 		who: sp_runtime::MultiSigner,
 		call: <T as Config>::RuntimeCall,
 		round: u32,
+		expires_at: BlockNumberFor<T>,
+		nonce: u32,
 	}
 
 	impl<T: Config> ManagerMultisigVote<T> {
@@ -1447,8 +1882,10 @@ pub mod pallet {
 			who: sp_runtime::MultiSigner,
 			call: <T as Config>::RuntimeCall,
 			round: u32,
+			expires_at: BlockNumberFor<T>,
+			nonce: u32,
 		) -> Self {
-			Self { who, call, round }
+			Self { who, call, round, expires_at, nonce }
 		}
 
 		pub fn encode_with_bytes_wrapper(&self) -> Vec<u8> {
@@ -1456,11 +1893,65 @@ pub mod pallet {
 		}
 	}
 
-	/// The multisig AccountIDs that votes to execute a specific call.
+	#[derive(
+		Encode,
+		Decode,
+		DebugNoBound,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		TypeInfo,
+		sp_core::DecodeWithMemTracking,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ManagerMultisigWithdrawal<T: Config> {
+		who: sp_runtime::MultiSigner,
+		call: <T as Config>::RuntimeCall,
+		round: u32,
+		nonce: u32,
+	}
+
+	impl<T: Config> ManagerMultisigWithdrawal<T> {
+		pub fn new(
+			who: sp_runtime::MultiSigner,
+			call: <T as Config>::RuntimeCall,
+			round: u32,
+			nonce: u32,
+		) -> Self {
+			Self { who, call, round, nonce }
+		}
+
+		pub fn encode_with_bytes_wrapper(&self) -> Vec<u8> {
+			(b"<Bytes>", b"withdraw", self, b"</Bytes>").encode()
+		}
+	}
+
+	/// The open proposals of the manager multisig, keyed by the call to be dispatched.
+	///
+	/// See [`manager_multisig`].
 	#[pallet::storage]
 	#[pallet::unbounded]
-	pub type ManagerMultisigs<T: Config> =
-		StorageMap<_, Twox64Concat, <T as Config>::RuntimeCall, Vec<AccountId32>, ValueQuery>;
+	pub type ManagerMultisigs<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		<T as Config>::RuntimeCall,
+		manager_multisig::ManagerMultisigProposalOf<T>,
+		OptionQuery,
+	>;
+
+	/// An open proposal of the manager multisig as returned by
+	/// [`Pallet::open_manager_proposals`].
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ManagerMultisigProposalInfo<Call, BlockNumber> {
+		/// The call to be dispatched.
+		pub call: Call,
+		/// The number of votes.
+		pub votes: u32,
+		/// The members that voted.
+		pub voters: Vec<AccountId32>,
+		/// The block from which on the proposal is expired.
+		pub expires_at: BlockNumber,
+	}
 
 	/// The current round of the multisig voting.
 	///
@@ -1470,20 +1961,29 @@ pub mod pallet {
 
 	/// How often each participant voted in the current round.
 	///
-	/// Will be cleared at the end of each round.
+	/// The nonce of the next vote. Will be cleared at the end of each round.
 	#[pallet::storage]
 	pub type ManagerVotesInCurrentRound<T: Config> =
 		StorageMap<_, Blake2_128Concat, AccountId32, u32, ValueQuery>;
 
+	/// How often each participant withdrew a vote in the current round.
+	///
+	/// The nonce of the next withdrawal. Will be cleared at the end of each round.
+	#[pallet::storage]
+	pub type ManagerWithdrawalsInCurrentRound<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountId32, u32, ValueQuery>;
+
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
 
 		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			if let Call::vote_manager_multisig { payload, sig } = call {
-				Self::do_validate_unsigned(payload, sig)
-			} else {
-				InvalidTransaction::Call.into()
+			match call {
+				Call::vote_manager_multisig { payload, sig } =>
+					Self::do_validate_unsigned(payload, sig),
+				Call::withdraw_manager_vote { payload, sig } =>
+					Self::do_validate_unsigned_withdrawal(payload, sig),
+				_ => InvalidTransaction::Call.into(),
 			}
 		}
 	}
@@ -1505,52 +2005,119 @@ pub mod pallet {
 			if ManagerMultisigRound::<T>::get() != payload.round {
 				return InvalidTransaction::Stale.into()
 			}
-			if ManagerVotesInCurrentRound::<T>::get(&account) >= T::MultisigMaxVotesPerRound::get()
-			{
+			let num_votes = ManagerVotesInCurrentRound::<T>::get(&account);
+			if num_votes != payload.nonce || num_votes >= T::MultisigMaxVotesPerRound::get() {
+				return InvalidTransaction::Stale.into()
+			}
+			if payload.expires_at <= frame_system::Pallet::<T>::block_number() {
 				return InvalidTransaction::Stale.into()
 			}
 
 			ValidTransaction::with_tag_prefix("AhmMultisig")
 				.priority(sp_runtime::traits::Bounded::max_value())
-				.and_provides(vec![("ahm_multi", account).encode()])
+				.and_provides(manager_multisig::vote_tag(&account, payload.nonce))
 				.propagate(true)
 				.longevity(30)
 				.build()
 		}
-	}
-
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
-		where
-		crate::BalanceOf<T>:
-			From<<<T as polkadot_runtime_common::slots::Config>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
-		crate::BalanceOf<T>:
-			From<<<<T as polkadot_runtime_common::crowdloan::Config>::Auctioneer as polkadot_runtime_common::traits::Auctioneer<<<<T as frame_system::Config>::Block as sp_runtime::traits::Block>::Header as sp_runtime::traits::Header>::Number>>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
-		<<T as pallet_treasury::Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber: Into<u32>
-	{
-		fn on_runtime_upgrade() -> Weight {
-			// We set the round to 100 if it does not exist to ensure that the Polkadot Multisigs
-			// do not overlap with the Kusama Multisigs.
-			if !ManagerMultisigRound::<T>::exists() {
-				ManagerMultisigRound::<T>::put(100);
-			}
 
-			T::DbWeight::get().reads_writes(1, 1)
+		fn do_validate_unsigned_withdrawal(
+			payload: &ManagerMultisigWithdrawal<T>,
+			sig: &sp_runtime::MultiSignature,
+		) -> TransactionValidity {
+			use sp_runtime::traits::Verify;
+			let account = payload.who.clone().into_account();
+
+			if !T::MultisigMembers::get().contains(&account) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if !sig.verify(&payload.encode_with_bytes_wrapper()[..], &account) {
+				return InvalidTransaction::BadProof.into()
+			}
+			if ManagerMultisigRound::<T>::get() != payload.round {
+				return InvalidTransaction::Stale.into()
+			}
+			let num_withdrawals = ManagerWithdrawalsInCurrentRound::<T>::get(&account);
+			if num_withdrawals != payload.nonce ||
+				num_withdrawals >= T::MultisigMaxVotesPerRound::get()
+			{
+				return InvalidTransaction::Stale.into()
+			}
+
+			ValidTransaction::with_tag_prefix("AhmMultisig")
+				.priority(sp_runtime::traits::Bounded::max_value())
+				.and_provides(manager_multisig::withdrawal_tag(&account, payload.nonce))
+				.propagate(true)
+				.longevity(30)
+				.build()
+		}
+
+		/// The open, not yet expired, proposals of the manager multisig.
+		pub fn open_manager_proposals(
+		) -> Vec<ManagerMultisigProposalInfo<<T as Config>::RuntimeCall, BlockNumberFor<T>>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			ManagerMultisigs::<T>::iter()
+				.filter(|(_, proposal)| !proposal.is_expired(&now))
+				.map(|(call, proposal)| ManagerMultisigProposalInfo {
+					call,
+					votes: proposal.votes.len() as u32,
+					voters: proposal.votes,
+					expires_at: proposal.expires_at,
+				})
+				.collect()
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+		where
+		crate::BalanceOf<T>:
+			From<<<T as polkadot_runtime_common::slots::Config>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+		crate::BalanceOf<T>:
+			From<<<<T as polkadot_runtime_common::crowdloan::Config>::Auctioneer as polkadot_runtime_common::traits::Auctioneer<<<<T as frame_system::Config>::Block as sp_runtime::traits::Block>::Header as sp_runtime::traits::Header>::Number>>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+		<<T as pallet_treasury::Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber: Into<u32>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			// We set the round to 100 if it does not exist to ensure that the Polkadot Multisigs
+			// do not overlap with the Kusama Multisigs.
+			if !ManagerMultisigRound::<T>::exists() {
+				ManagerMultisigRound::<T>::put(100);
+			}
+
+			T::DbWeight::get().reads_writes(1, 1)
 		}
 
 		fn integrity_test() {
 			let (ah_ump_priority_blocks, _) = T::AhUmpQueuePriorityPattern::get();
 			assert!(!ah_ump_priority_blocks.is_zero(), "the `ah_ump_priority_blocks` should be non-zero");
+			if let Err(err) = sequence::validate_sequence(&T::MigrationSequence::pallets()) {
+				panic!("Invalid `MigrationSequence`: {err:?}");
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::check_stored_stages()
+		}
+
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(remaining_weight);
+			Self::prune_expired_manager_proposals(&mut meter);
+			meter.consumed()
 		}
 
 		fn on_finalize(now: BlockNumberFor<T>) {
+			BlockLimits::<T>::kill();
 			if Self::is_ongoing() {
 				Self::force_ah_ump_queue_priority(now);
 			}
 		}
 
 		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
-			let mut weight_counter = WeightMeter::with_limit(T::MaxRcWeight::get());
+			let limits = EffectiveLimits::compute::<T>();
+			BlockLimits::<T>::put(limits);
+			let mut weight_counter = WeightMeter::with_limit(limits.max_rc_weight);
+			weight_counter.consume(T::DbWeight::get().reads(1));
 
 			let stage = RcMigrationStage::<T>::get();
 			weight_counter.consume(T::DbWeight::get().reads(1));
@@ -1560,6 +2127,14 @@ pub mod pallet {
 				weight_counter.consume(T::RcWeightInfo::force_ah_ump_queue_priority());
 			}
 
+			if stage.is_ongoing() {
+				Self::check_xcm_response_timeouts(now, &mut weight_counter);
+			}
+
+			if stage.is_ongoing() && stage != MigrationStage::MigrationPaused {
+				Self::retry_failed_xcm(now, &mut weight_counter);
+			}
+
 			if Self::has_excess_unconfirmed_dmp(&stage) {
 				log::info!(
 					target: LOG_TARGET,
@@ -1617,6 +2192,17 @@ pub mod pallet {
 					return weight_counter.consumed();
 				},
 				MigrationStage::Starting => {
+					// Refuse accounts before any pallet migrates them, so that no account is split
+					// between the chains.
+					let (refused, weight) = AccountsMigrator::<T>::count_refused_accounts();
+					weight_counter.consume(weight);
+					if refused > 0 {
+						log::warn!(target: LOG_TARGET, "{} accounts refused, pausing", refused);
+						Self::deposit_event(Event::AccountsRefused { accounts: refused });
+						defensive_assert!(Self::do_pause_migration().is_ok());
+						return weight_counter.consumed();
+					}
+
 					log::info!(target: LOG_TARGET, "Starting the migration");
 					pallet_staking_async_ah_client::Pallet::<T>::on_migration_start();
 
@@ -1631,7 +2217,7 @@ pub mod pallet {
 						Self::deposit_event(Event::PureAccountsIndexed { num_pure_accounts });
 					}
 
-					Self::transition(MigrationStage::AccountsMigrationInit);
+					Self::transition(Self::next_pallet_stage(None));
 				},
 				MigrationStage::AccountsMigrationInit => {
 					let weight = AccountsMigrator::<T>::obtain_rc_accounts();
@@ -1678,8 +2264,7 @@ pub mod pallet {
 				},
 				MigrationStage::AccountsMigrationDone => {
 					AccountsMigrator::<T>::finish_balances_migration();
-					// Note: swap this out for faster testing to skip some migrations
-					Self::transition(MigrationStage::MultisigMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Accounts);
 				},
 				MigrationStage::MultisigMigrationInit => {
 					Self::transition(MigrationStage::MultisigMigrationOngoing { last_key: None });
@@ -1712,7 +2297,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::MultisigMigrationDone => {
-					Self::transition(MigrationStage::ClaimsMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Multisig);
 				},
 				MigrationStage::ClaimsMigrationInit => {
 					Self::transition(MigrationStage::ClaimsMigrationOngoing { current_key: None });
@@ -1740,7 +2325,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::ClaimsMigrationDone => {
-					Self::transition(MigrationStage::ProxyMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Claims);
 				},
 				MigrationStage::ProxyMigrationInit => {
 					Self::transition(MigrationStage::ProxyMigrationProxies { last_key: None });
@@ -1796,7 +2381,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::ProxyMigrationDone => {
-					Self::transition(MigrationStage::PreimageMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Proxy);
 				},
 				MigrationStage::PreimageMigrationInit => {
 					Self::transition(MigrationStage::PreimageMigrationChunksOngoing {
@@ -1904,7 +2489,7 @@ pub mod pallet {
 					Self::transition(MigrationStage::PreimageMigrationDone);
 				},
 				MigrationStage::PreimageMigrationDone => {
-					Self::transition(MigrationStage::NomPoolsMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Preimage);
 				},
 				MigrationStage::NomPoolsMigrationInit => {
 					Self::transition(MigrationStage::NomPoolsMigrationOngoing { next_key: None });
@@ -1932,7 +2517,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::NomPoolsMigrationDone => {
-					Self::transition(MigrationStage::VestingMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::NomPools);
 				},
 
 				MigrationStage::VestingMigrationInit => {
@@ -1961,7 +2546,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::VestingMigrationDone => {
-					Self::transition(MigrationStage::DelegatedStakingMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Vesting);
 				},
 				MigrationStage::DelegatedStakingMigrationInit => {
 					Self::transition(MigrationStage::DelegatedStakingMigrationOngoing {
@@ -1992,7 +2577,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::DelegatedStakingMigrationDone => {
-					Self::transition(MigrationStage::IndicesMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::DelegatedStaking);
 				},
 				MigrationStage::IndicesMigrationInit => {
 					Self::transition(MigrationStage::IndicesMigrationOngoing {
@@ -2022,7 +2607,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::IndicesMigrationDone => {
-					Self::transition(MigrationStage::ReferendaMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Indices);
 				},
 				MigrationStage::ReferendaMigrationInit => {
 					Self::transition(MigrationStage::ReferendaMigrationOngoing {
@@ -2056,7 +2641,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::ReferendaMigrationDone => {
-					Self::transition(MigrationStage::BagsListMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Referenda);
 				},
 				MigrationStage::BagsListMigrationInit => {
 					Self::transition(MigrationStage::BagsListMigrationOngoing { next_key: None });
@@ -2084,7 +2669,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::BagsListMigrationDone => {
-					Self::transition(MigrationStage::SchedulerMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::BagsList);
 				},
 				MigrationStage::SchedulerMigrationInit => {
 					Self::transition(MigrationStage::SchedulerMigrationOngoing { last_key: None });
@@ -2140,7 +2725,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::SchedulerMigrationDone => {
-					Self::transition(MigrationStage::ConvictionVotingMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Scheduler);
 				},
 				MigrationStage::ConvictionVotingMigrationInit => {
 					Self::transition(MigrationStage::ConvictionVotingMigrationOngoing {
@@ -2173,7 +2758,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::ConvictionVotingMigrationDone => {
-					Self::transition(MigrationStage::BountiesMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::ConvictionVoting);
 				},
 				MigrationStage::BountiesMigrationInit => {
 					Self::transition(MigrationStage::BountiesMigrationOngoing { last_key: None });
@@ -2204,7 +2789,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::BountiesMigrationDone => {
-					Self::transition(MigrationStage::ChildBountiesMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Bounties);
 				},
 				MigrationStage::ChildBountiesMigrationInit => {
 					Self::transition(MigrationStage::ChildBountiesMigrationOngoing { last_key: None });
@@ -2235,7 +2820,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::ChildBountiesMigrationDone => {
-					Self::transition(MigrationStage::AssetRateMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::ChildBounties);
 				},
 				MigrationStage::AssetRateMigrationInit => {
 					Self::transition(MigrationStage::AssetRateMigrationOngoing { last_key: None });
@@ -2266,7 +2851,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::AssetRateMigrationDone => {
-					Self::transition(MigrationStage::CrowdloanMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::AssetRate);
 				},
 				MigrationStage::CrowdloanMigrationInit => {
 					Self::transition(MigrationStage::CrowdloanMigrationOngoing { last_key: None });
@@ -2297,7 +2882,38 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::CrowdloanMigrationDone => {
-					Self::transition(MigrationStage::TreasuryMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Crowdloan);
+				},
+				MigrationStage::ParaReservesMigrationInit => {
+					Self::transition(MigrationStage::ParaReservesMigrationOngoing { last_key: None });
+				},
+				MigrationStage::ParaReservesMigrationOngoing { last_key } => {
+					let res = with_transaction_opaque_err::<Option<_>, Error<T>, _>(|| {
+						match para_reserves::ParaReservesMigrator::<T>::migrate_many(
+							last_key,
+							&mut weight_counter,
+						) {
+							Ok(last_key) => TransactionOutcome::Commit(Ok(last_key)),
+							Err(e) => TransactionOutcome::Rollback(Err(e)),
+						}
+					});
+
+					match res {
+						Ok(Ok(None)) => {
+							Self::transition(MigrationStage::ParaReservesMigrationDone);
+						},
+						Ok(Ok(Some(last_key))) => {
+							Self::transition(MigrationStage::ParaReservesMigrationOngoing {
+								last_key: Some(last_key),
+							});
+						},
+						e => {
+							defensive!("Error while migrating para reserves: {:?}", e);
+						},
+					}
+				},
+				MigrationStage::ParaReservesMigrationDone => {
+					Self::complete_pallet_migration(MigratedPallet::ParaReserves);
 				},
 				MigrationStage::TreasuryMigrationInit => {
 					Self::transition(MigrationStage::TreasuryMigrationOngoing { last_key: None });
@@ -2328,10 +2944,7 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::TreasuryMigrationDone => {
-					#[cfg(feature = "kusama-ahm")]
-					Self::transition(MigrationStage::RecoveryMigrationInit);
-					#[cfg(not(feature = "kusama-ahm"))]
-					Self::transition(MigrationStage::StakingMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Treasury);
 				},
 				#[cfg(feature = "kusama-ahm")]
 				MigrationStage::RecoveryMigrationInit => {
@@ -2365,7 +2978,7 @@ pub mod pallet {
 				},
 				#[cfg(feature = "kusama-ahm")]
 				MigrationStage::RecoveryMigrationDone => {
-					Self::transition(MigrationStage::SocietyMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Recovery);
 				},
 				#[cfg(feature = "kusama-ahm")]
 				MigrationStage::SocietyMigrationInit => {
@@ -2399,7 +3012,38 @@ pub mod pallet {
 				},
 				#[cfg(feature = "kusama-ahm")]
 				MigrationStage::SocietyMigrationDone => {
-					Self::transition(MigrationStage::StakingMigrationInit);
+					Self::complete_pallet_migration(MigratedPallet::Society);
+				},
+				MigrationStage::FastUnstakeMigrationInit => {
+					Self::transition(MigrationStage::FastUnstakeMigrationOngoing { last_key: None });
+				},
+				MigrationStage::FastUnstakeMigrationOngoing { last_key } => {
+					let res = with_transaction_opaque_err::<Option<_>, Error<T>, _>(|| {
+						match fast_unstake::FastUnstakeMigrator::<T>::migrate_many(
+							last_key,
+							&mut weight_counter,
+						) {
+							Ok(last_key) => TransactionOutcome::Commit(Ok(last_key)),
+							Err(e) => TransactionOutcome::Rollback(Err(e)),
+						}
+					});
+
+					match res {
+						Ok(Ok(None)) => {
+							Self::transition(MigrationStage::FastUnstakeMigrationDone);
+						},
+						Ok(Ok(Some(last_key))) => {
+							Self::transition(MigrationStage::FastUnstakeMigrationOngoing {
+								last_key: Some(last_key),
+							});
+						},
+						e => {
+							defensive!("Error while migrating fast-unstake: {:?}", e);
+						},
+					}
+				},
+				MigrationStage::FastUnstakeMigrationDone => {
+					Self::complete_pallet_migration(MigratedPallet::FastUnstake);
 				},
 				MigrationStage::StakingMigrationInit => {
 					Self::transition(MigrationStage::StakingMigrationOngoing { next_key: None });
@@ -2428,14 +3072,15 @@ pub mod pallet {
 					}
 				},
 				MigrationStage::StakingMigrationDone => {
-					Self::transition(MigrationStage::SignalMigrationFinish);
+					Self::complete_pallet_migration(MigratedPallet::Staking);
 				},
 				MigrationStage::SignalMigrationFinish => {
 					weight_counter.consume(
 						// 1 read and 1 write for `RcMigratedBalance` storage item;
+						// 1 read and 1 write for `RcMigratedTotals` storage item;
 						// one xcm send;
 						// 1 read `CoolOffPeriod` storage item;
-						T::DbWeight::get().reads_writes(2, 1)
+						T::DbWeight::get().reads_writes(3, 2)
 							.saturating_add(T::RcWeightInfo::send_chunked_xcm_and_track())
 					);
 
@@ -2450,6 +3095,8 @@ pub mod pallet {
 						});
 						Some(MigrationFinishedData {
 							rc_balance_kept: tracker.kept,
+							rc_balance_migrated: tracker.migrated,
+							rc_totals: RcMigratedTotals::<T>::take(),
 						})
 					} else {
 						None
@@ -2495,6 +3142,32 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Ensure that the stored migration stages decode to the same stages.
+		///
+		/// Catches changes to the encoding of [`MigrationStage`], e.g. variants inserted before
+		/// existing ones, that would resume an ongoing migration at a different stage.
+		#[cfg(feature = "try-runtime")]
+		pub fn check_stored_stages() -> Result<(), sp_runtime::TryRuntimeError> {
+			let sequence = T::MigrationSequence::pallets();
+			for key in [RcMigrationStage::<T>::hashed_key(), PausedStage::<T>::hashed_key()] {
+				let Some(raw) = sp_io::storage::get(&key) else { continue };
+				let mut input = &raw[..];
+				let stage = MigrationStageOf::<T>::decode(&mut input)
+					.map_err(|_| "The stored migration stage does not decode")?;
+				ensure!(
+					input.is_empty() && stage.encode() == raw[..],
+					"The stored migration stage does not decode to the same stage"
+				);
+				if let Some(pallet) = stage.pallet() {
+					ensure!(
+						sequence.contains(&pallet) && !SkippedPallets::<T>::contains_key(pallet),
+						"The stored migration stage belongs to a pallet that is not migrated"
+					);
+				}
+			}
+			Ok(())
+		}
+
 		pub fn do_schedule_migration(
 			start: DispatchTime<BlockNumberFor<T>>,
 			warm_up: DispatchTime<BlockNumberFor<T>>,
@@ -2585,6 +3258,214 @@ pub mod pallet {
 			}
 		}
 
+		/// Pause the migration and remember the stage it was paused at.
+		fn do_pause_migration() -> DispatchResult {
+			let pause_stage = RcMigrationStage::<T>::get();
+			ensure!(
+				pause_stage != MigrationStage::MigrationPaused,
+				Error::<T>::InvalidStageTransition
+			);
+			Self::transition(MigrationStage::MigrationPaused);
+			PausedStage::<T>::put(&pause_stage);
+			Self::deposit_event(Event::MigrationPaused { pause_stage });
+			Ok(())
+		}
+
+		/// The number of blocks to wait before the next automatic resend of a message that has been
+		/// resent `attempts` times.
+		fn xcm_retry_backoff(attempts: u32) -> BlockNumberFor<T> {
+			T::XcmRetryInterval::get()
+				.saturating_mul(BlockNumberFor::<T>::from(1u32 << attempts.min(16)))
+		}
+
+		/// Schedule the automatic resend of the message that failed under `query_id`.
+		///
+		/// Failed responses to an earlier send of a message that was resent since are ignored.
+		/// Pauses the migration if the message already exceeded [`Config::MaxXcmRetries`].
+		fn schedule_xcm_retry(query_id: QueryId, message_hash: T::Hash) {
+			let mut retry = PendingXcmRetries::<T>::get(message_hash)
+				.unwrap_or(types::XcmRetry { query_id, attempts: 0, retry_at: None });
+			if retry.query_id != query_id {
+				log::info!(
+					target: LOG_TARGET,
+					"Ignoring error response for query id {query_id}, the message was resent with query id {}",
+					retry.query_id
+				);
+				return;
+			}
+
+			if retry.attempts >= T::MaxXcmRetries::get() {
+				log::error!(
+					target: LOG_TARGET,
+					"XCM message with query id {query_id} failed after {} retries, pausing the migration",
+					retry.attempts
+				);
+				PendingXcmRetries::<T>::remove(message_hash);
+				Self::deposit_event(Event::<T>::XcmRetryGaveUp {
+					query_id,
+					attempts: retry.attempts,
+				});
+				if let Err(err) = Self::do_pause_migration() {
+					defensive!("Failed to pause the migration: {:?}", err);
+				}
+				return;
+			}
+
+			retry.retry_at = Some(
+				frame_system::Pallet::<T>::block_number()
+					.saturating_add(Self::xcm_retry_backoff(retry.attempts)),
+			);
+			PendingXcmRetries::<T>::insert(message_hash, retry);
+		}
+
+		/// Resend the failed messages whose retry is due.
+		///
+		/// Only iterates [`PendingXcmRetries`], which holds the messages that failed on the Asset
+		/// Hub or were resent, and resends at most [`max_xcm_msg_per_block`] messages per block.
+		fn retry_failed_xcm(now: BlockNumberFor<T>, weight_counter: &mut WeightMeter) {
+			let mut due = Vec::new();
+			for retry in PendingXcmRetries::<T>::iter_values() {
+				if due.len() as u32 >= max_xcm_msg_per_block::<T>() ||
+					weight_counter.try_consume(T::DbWeight::get().reads(1)).is_err()
+				{
+					break;
+				}
+				if retry.retry_at.is_some_and(|retry_at| retry_at <= now) {
+					due.push((retry.query_id, retry.attempts));
+				}
+			}
+
+			for (query_id, attempts) in due {
+				if weight_counter.try_consume(T::RcWeightInfo::resend_xcm()).is_err() {
+					break;
+				}
+				match Self::do_resend_xcm(query_id, true) {
+					Ok((new_query_id, send_error)) => {
+						Self::deposit_event(Event::<T>::XcmRetryAttempt {
+							query_id,
+							new_query_id,
+							attempt: attempts.saturating_add(1),
+							send_error,
+						});
+					},
+					Err(err) => {
+						defensive!("Failed to resend XCM message: {:?}", err);
+					},
+				}
+			}
+		}
+
+		/// Report the queries whose [`Config::XcmResponseTimeout`] passes at `now` and are still
+		/// unanswered, and pause the migration if there are any.
+		///
+		/// Queries that were answered, or whose message was resent since, are skipped.
+		fn check_xcm_response_timeouts(now: BlockNumberFor<T>, weight_counter: &mut WeightMeter) {
+			weight_counter.consume(T::DbWeight::get().reads_writes(1, 1));
+			let mut timed_out = false;
+			for query_id in XcmResponseDeadlines::<T>::take(now) {
+				weight_counter.consume(T::DbWeight::get().reads(2));
+				let Some(message_hash) = PendingXcmQueries::<T>::get(query_id) else {
+					continue;
+				};
+				let unanswered = PendingXcmRetries::<T>::get(message_hash)
+					.map_or(true, |retry| retry.query_id == query_id && retry.retry_at.is_none());
+				if !unanswered {
+					continue;
+				}
+				log::error!(
+					target: LOG_TARGET,
+					"No response for the XCM message with query id {query_id}"
+				);
+				Self::deposit_event(Event::<T>::XcmResponseTimedOut { query_id });
+				timed_out = true;
+			}
+
+			if timed_out && RcMigrationStage::<T>::get() != MigrationStage::MigrationPaused {
+				weight_counter.consume(T::DbWeight::get().reads_writes(1, 3));
+				if let Err(err) = Self::do_pause_migration() {
+					defensive!("Failed to pause the migration: {:?}", err);
+				}
+			}
+		}
+
+		/// Track `query_id` until its [`Config::XcmResponseTimeout`] passes.
+		fn track_xcm_response_deadline(query_id: QueryId) {
+			let deadline = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::XcmResponseTimeout::get());
+			XcmResponseDeadlines::<T>::append(deadline, query_id);
+		}
+
+		/// Resend the message tracked under `query_id` with a new query.
+		///
+		/// An `automatic` resend counts towards [`Config::MaxXcmRetries`] and follows a failed
+		/// response, so the old query is dropped. A manual resend keeps the old query, so that a
+		/// late response to it still confirms the message.
+		///
+		/// Returns the new query id and the error if sending failed. If sending failed the message
+		/// stays tracked under `query_id`.
+		fn do_resend_xcm(
+			query_id: QueryId,
+			automatic: bool,
+		) -> Result<(QueryId, Option<SendError>), Error<T>> {
+			let message_hash =
+				PendingXcmQueries::<T>::get(query_id).ok_or(Error::<T>::QueryNotFound)?;
+			let xcm = PendingXcmMessages::<T>::get((query_id, message_hash))
+				.ok_or(Error::<T>::QueryNotFound)?;
+			let mut retry = PendingXcmRetries::<T>::get(message_hash)
+				.unwrap_or(types::XcmRetry { query_id, attempts: 0, retry_at: None });
+
+			let asset_hub_location = Location::new(0, Parachain(1000));
+			let receive_notification_call =
+				Call::<T>::receive_query_response { query_id: 0, response: Default::default() };
+
+			let new_query_id = pallet_xcm::Pallet::<T>::new_notify_query(
+				asset_hub_location.clone(),
+				<T as Config>::RuntimeCall::from(receive_notification_call),
+				frame_system::Pallet::<T>::block_number() + T::XcmResponseTimeout::get(),
+				Location::here(),
+			);
+
+			let xcm_with_report = {
+				let mut xcm = xcm.clone();
+				xcm.inner_mut().push(SetAppendix(Xcm(vec![ReportTransactStatus(
+					QueryResponseInfo {
+						destination: Location::parent(),
+						query_id: new_query_id,
+						max_weight: T::RcWeightInfo::receive_query_response(),
+					},
+				)])));
+				xcm
+			};
+
+			if automatic {
+				retry.attempts.saturating_inc();
+			}
+
+			if let Err(err) = send_xcm::<T::SendXcm>(asset_hub_location, xcm_with_report) {
+				log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
+				if automatic {
+					retry.retry_at = Some(
+						frame_system::Pallet::<T>::block_number()
+							.saturating_add(Self::xcm_retry_backoff(retry.attempts)),
+					);
+					PendingXcmRetries::<T>::insert(message_hash, retry);
+				}
+				return Ok((new_query_id, Some(err)));
+			}
+
+			PendingXcmMessages::<T>::remove((query_id, message_hash));
+			if automatic {
+				PendingXcmQueries::<T>::remove(query_id);
+			}
+			PendingXcmMessages::<T>::insert((new_query_id, message_hash), xcm);
+			PendingXcmQueries::<T>::insert(new_query_id, message_hash);
+			Self::track_xcm_response_deadline(new_query_id);
+			retry.query_id = new_query_id;
+			retry.retry_at = None;
+			PendingXcmRetries::<T>::insert(message_hash, retry);
+			Ok((new_query_id, None))
+		}
+
 		/// Execute a stage transition and log it.
 		fn transition(new: MigrationStageOf<T>) {
 			let old = RcMigrationStage::<T>::get();
@@ -2607,6 +3488,15 @@ pub mod pallet {
 				Self::deposit_event(Event::AssetHubMigrationFinished);
 			}
 
+			if old == MigrationStage::MigrationPaused && new != MigrationStage::MigrationPaused {
+				// Either resumed or force set; the paused stage is stale from now on.
+				PausedStage::<T>::kill();
+			} else if core::mem::discriminant(&old) != core::mem::discriminant(&new) &&
+				new != MigrationStage::MigrationPaused
+			{
+				StageMigratedItems::<T>::kill();
+			}
+
 			RcMigrationStage::<T>::put(&new);
 			log::info!(target: LOG_TARGET, "[Block {:?}] RC Stage transition: {:?} -> {:?}", frame_system::Pallet::<T>::block_number(), &old, &new);
 			Self::deposit_event(Event::StageTransition { old, new });
@@ -2638,60 +3528,23 @@ pub mod pallet {
 				let batch_len = batch.len() as u32;
 				log::info!(target: LOG_TARGET, "Sending XCM batch of {batch_len} items");
 
-				let asset_hub_location = Location::new(0, Parachain(1000));
-
-				let receive_notification_call =
-					Call::<T>::receive_query_response { query_id: 0, response: Default::default() };
-
-				let query_id = pallet_xcm::Pallet::<T>::new_notify_query(
-					asset_hub_location.clone(),
-					<T as Config>::RuntimeCall::from(receive_notification_call),
-					frame_system::Pallet::<T>::block_number() + T::XcmResponseTimeout::get(),
-					Location::here(),
-				);
-
-				let call = types::AssetHubPalletConfig::<T>::AhmController(create_call(batch));
-				let message = vec![
-					Instruction::UnpaidExecution {
-						weight_limit: WeightLimit::Unlimited,
-						check_origin: None,
-					},
-					Instruction::Transact {
-						origin_kind: OriginKind::Superuser,
-						fallback_max_weight: None,
-						call: call.encode().into(),
-					},
-				];
-
-				let message_hash = T::Hashing::hash_of(&message);
-
-				let message_with_report = {
-					let mut m = message.clone();
-					m.push(SetAppendix(Xcm(vec![ReportTransactStatus(QueryResponseInfo {
-						destination: Location::parent(),
-						query_id,
-						max_weight: T::RcWeightInfo::receive_query_response(),
-					})])));
-					m
-				};
+				let mut batch_digest = digest::MigrationDigest::default();
+				batch_digest.record_all(&batch);
 
-				if let Err(err) =
-					send_xcm::<T::SendXcm>(asset_hub_location, Xcm(message_with_report))
-				{
-					log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
-					return Err(Error::XcmError);
-				} else {
-					PendingXcmMessages::<T>::insert((query_id, message_hash), Xcm(message));
-					PendingXcmQueries::<T>::insert(query_id, message_hash);
-					batch_count += 1;
+				Self::send_tracked_xcm(create_call(batch))?;
+				StageMigratedItems::<T>::mutate(|items| items.saturating_accrue(batch_len));
+				if let Some(pallet) = RcMigrationStage::<T>::get().pallet() {
+					MigrationDigests::<T>::mutate(pallet, |digest| digest.merge(&batch_digest));
 				}
+				batch_count += 1;
 			}
 
-			if batch_count > MAX_XCM_MSG_PER_BLOCK {
+			let max_xcm_msg_per_block = max_xcm_msg_per_block::<T>();
+			if batch_count > max_xcm_msg_per_block {
 				debug_assert!(false, "Unreachable: we always remaining len before pushing");
 				log::warn!(
 					target: LOG_TARGET,
-					"Maximum number of XCM messages ({MAX_XCM_MSG_PER_BLOCK}) to migrate per block exceeded, current msg count: {batch_count}"
+					"Maximum number of XCM messages ({max_xcm_msg_per_block}) to migrate per block exceeded, current msg count: {batch_count}"
 				);
 			}
 
@@ -2699,6 +3552,59 @@ pub mod pallet {
 			Ok(batch_count)
 		}
 
+		/// Send a single XCM message and track it until the Asset Hub reports its outcome.
+		///
+		/// The message is stored in [`PendingXcmMessages`] and resent if it fails, like the batches
+		/// of [`Pallet::send_chunked_xcm_and_track`].
+		pub fn send_tracked_xcm(call: types::AhMigratorCall<T>) -> Result<(), Error<T>> {
+			let asset_hub_location = Location::new(0, Parachain(1000));
+
+			let receive_notification_call =
+				Call::<T>::receive_query_response { query_id: 0, response: Default::default() };
+
+			let query_id = pallet_xcm::Pallet::<T>::new_notify_query(
+				asset_hub_location.clone(),
+				<T as Config>::RuntimeCall::from(receive_notification_call),
+				frame_system::Pallet::<T>::block_number() + T::XcmResponseTimeout::get(),
+				Location::here(),
+			);
+
+			let call = types::AssetHubPalletConfig::<T>::AhmController(call);
+			let message = vec![
+				Instruction::UnpaidExecution {
+					weight_limit: WeightLimit::Unlimited,
+					check_origin: None,
+				},
+				Instruction::Transact {
+					origin_kind: OriginKind::Superuser,
+					fallback_max_weight: None,
+					call: call.encode().into(),
+				},
+			];
+
+			let message_hash = T::Hashing::hash_of(&message);
+
+			let message_with_report = {
+				let mut m = message.clone();
+				m.push(SetAppendix(Xcm(vec![ReportTransactStatus(QueryResponseInfo {
+					destination: Location::parent(),
+					query_id,
+					max_weight: T::RcWeightInfo::receive_query_response(),
+				})])));
+				m
+			};
+
+			if let Err(err) = send_xcm::<T::SendXcm>(asset_hub_location, Xcm(message_with_report))
+			{
+				log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
+				return Err(Error::XcmError);
+			}
+			PendingXcmMessages::<T>::insert((query_id, message_hash), Xcm(message));
+			PendingXcmQueries::<T>::insert(query_id, message_hash);
+			Self::track_xcm_response_deadline(query_id);
+			Ok(())
+		}
+
 		/// Send a single XCM message.
 		///
 		/// ### Parameters:
@@ -2718,13 +3624,21 @@ pub mod pallet {
 				},
 			]);
 
-			if let Err(err) = send_xcm::<T::SendXcm>(
-				Location::new(0, [Junction::Parachain(1000)]),
-				message.clone(),
-			) {
-				log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
-				return Err(Error::XcmError);
+			let destination = Location::new(0, [Junction::Parachain(1000)]);
+			let message_id = match send_xcm::<T::SendXcm>(destination.clone(), message.clone()) {
+				Ok((message_id, _)) => message_id,
+				Err(err) => {
+					log::error!(target: LOG_TARGET, "Error while sending XCM message: {err:?}");
+					return Err(Error::XcmError);
+				},
 			};
+			// Untracked messages are only visible through the event, e.g. to the message log.
+			Self::deposit_event(Event::XcmSent {
+				origin: Location::here(),
+				destination,
+				message,
+				message_id,
+			});
 
 			Ok(())
 		}
@@ -2779,6 +3693,59 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T>
+		where
+		crate::BalanceOf<T>:
+			From<<<T as polkadot_runtime_common::slots::Config>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+		crate::BalanceOf<T>:
+			From<<<<T as polkadot_runtime_common::crowdloan::Config>::Auctioneer as polkadot_runtime_common::traits::Auctioneer<<<<T as frame_system::Config>::Block as sp_runtime::traits::Block>::Header as sp_runtime::traits::Header>::Number>>::Currency as frame_support::traits::Currency<sp_runtime::AccountId32>>::Balance>,
+		<<T as pallet_treasury::Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber: Into<u32>
+	{
+		/// Simulate the data migration and report per stage item and XCM message counts, weight
+		/// and blocks.
+		///
+		/// See [`Pallet::dry_run_migration`].
+		pub fn migration_dry_run(
+			start: Option<MigrationStageOf<T>>,
+			max_blocks: u32,
+		) -> dry_run::DryRunReport<MigrationStageOf<T>> {
+			Self::dry_run_migration(start, max_blocks)
+		}
+
+		/// Progress report of the migration.
+		///
+		/// See [`Pallet::migration_progress`].
+		pub fn migration_status() -> status::MigrationProgress<MigrationStageOf<T>, BlockNumberFor<T>>
+		{
+			Self::migration_progress()
+		}
+
+		/// What the accounts migration does to `who`.
+		///
+		/// See [`accounts::AccountsMigrator::preview_account`].
+		pub fn account_migration_preview(
+			who: T::AccountId,
+		) -> accounts::AccountMigrationPreviewOf<T> {
+			accounts::AccountsMigrator::<T>::preview_account(who)
+		}
+
+		/// The open proposals of the manager multisig with their votes.
+		///
+		/// See [`Pallet::open_manager_proposals`].
+		pub fn manager_multisig_proposals(
+		) -> Vec<ManagerMultisigProposalInfo<<T as Config>::RuntimeCall, BlockNumberFor<T>>> {
+			Self::open_manager_proposals()
+		}
+
+		/// The calls of ongoing referendums and scheduled tasks that the Asset Hub translates.
+		///
+		/// See [`call_report`].
+		pub fn migrated_calls() -> Vec<call_report::MigratedCallOf<T>> {
+			Self::collect_migrated_calls()
+		}
+	}
+
 	impl<T: Config> types::MigrationStatus for Pallet<T> {
 		fn is_ongoing() -> bool {
 			RcMigrationStage::<T>::get().is_ongoing()
@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proposals of the manager multisig.
+//!
+//! Members vote with [`Pallet::vote_manager_multisig`] and withdraw their votes with
+//! [`Pallet::withdraw_manager_vote`]. Every proposed call has its own
+//! [`ManagerMultisigProposal`] in [`ManagerMultisigs`] that expires independently of the others.
+//! Expired proposals are removed in `on_idle`.
+
+use crate::*;
+
+/// An open proposal of the manager multisig.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ManagerMultisigProposal<BlockNumber> {
+	/// The members that voted for the proposal.
+	pub votes: Vec<AccountId32>,
+	/// The block from which on the proposal is expired.
+	pub expires_at: BlockNumber,
+}
+
+/// [`ManagerMultisigProposal`] of a runtime.
+pub type ManagerMultisigProposalOf<T> = ManagerMultisigProposal<BlockNumberFor<T>>;
+
+impl<BlockNumber: PartialOrd> ManagerMultisigProposal<BlockNumber> {
+	/// A proposal without votes.
+	pub fn new(expires_at: BlockNumber) -> Self {
+		Self { votes: Vec::new(), expires_at }
+	}
+
+	/// Whether the proposal is expired at block `now`.
+	pub fn is_expired(&self, now: &BlockNumber) -> bool {
+		self.expires_at <= *now
+	}
+
+	/// Add the vote of `who` and return the number of votes.
+	pub fn vote(&mut self, who: AccountId32) -> Result<u32, &'static str> {
+		ensure!(!self.votes.contains(&who), "Duplicate");
+		self.votes.push(who);
+		Ok(self.votes.len() as u32)
+	}
+
+	/// Remove the vote of `who` and return the number of remaining votes.
+	pub fn withdraw(&mut self, who: &AccountId32) -> Result<u32, &'static str> {
+		let position = self.votes.iter().position(|voter| voter == who).ok_or("NotVoted")?;
+		self.votes.remove(position);
+		Ok(self.votes.len() as u32)
+	}
+}
+
+/// The transaction pool tag provided by the vote of `who` with `nonce`.
+pub fn vote_tag(who: &AccountId32, nonce: u32) -> Vec<u8> {
+	("ahm_multi_vote", who, nonce).encode()
+}
+
+/// The transaction pool tag provided by the withdrawal of `who` with `nonce`.
+pub fn withdrawal_tag(who: &AccountId32, nonce: u32) -> Vec<u8> {
+	("ahm_multi_withdraw", who, nonce).encode()
+}
+
+impl<T: Config> Pallet<T> {
+	/// Remove expired proposals from [`ManagerMultisigs`] within the limit of `meter`.
+	pub fn prune_expired_manager_proposals(meter: &mut WeightMeter) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut expired = Vec::new();
+		for (call, proposal) in ManagerMultisigs::<T>::iter() {
+			if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+				break;
+			}
+			if proposal.is_expired(&now) {
+				expired.push(call);
+			}
+		}
+
+		for call in expired {
+			let call_hash = T::Hashing::hash_of(&call);
+			ManagerMultisigs::<T>::remove(call);
+			Self::deposit_event(Event::<T>::ManagerMultisigProposalExpired { call_hash });
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALICE: AccountId32 = AccountId32::new([1; 32]);
+	const BOB: AccountId32 = AccountId32::new([2; 32]);
+
+	#[test]
+	fn votes_are_counted_once_per_member() {
+		let mut proposal = ManagerMultisigProposal::new(10u32);
+
+		assert_eq!(proposal.vote(ALICE), Ok(1));
+		assert_eq!(proposal.vote(ALICE), Err("Duplicate"));
+		assert_eq!(proposal.vote(BOB), Ok(2));
+	}
+
+	#[test]
+	fn votes_can_be_withdrawn() {
+		let mut proposal = ManagerMultisigProposal::new(10u32);
+		proposal.vote(ALICE).unwrap();
+
+		assert_eq!(proposal.withdraw(&BOB), Err("NotVoted"));
+		assert_eq!(proposal.withdraw(&ALICE), Ok(0));
+		assert_eq!(proposal.vote(ALICE), Ok(1));
+	}
+
+	#[test]
+	fn proposals_expire_at_their_block() {
+		let proposal = ManagerMultisigProposal::new(10u32);
+
+		assert!(!proposal.is_expired(&9));
+		assert!(proposal.is_expired(&10));
+	}
+
+	#[test]
+	fn votes_and_withdrawals_provide_distinct_tags() {
+		assert_ne!(vote_tag(&ALICE, 0), withdrawal_tag(&ALICE, 0));
+		assert_ne!(vote_tag(&ALICE, 0), vote_tag(&ALICE, 1));
+		assert_ne!(vote_tag(&ALICE, 0), vote_tag(&BOB, 0));
+		assert_ne!(withdrawal_tag(&ALICE, 0), withdrawal_tag(&ALICE, 1));
+	}
+}
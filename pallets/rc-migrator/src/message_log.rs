@@ -0,0 +1,194 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Log of the messages that the Relay Chain sends to the Asset Hub during the migration.
+//!
+//! A [`MessageRecorder`] reads the messages that were sent back from the state at the end of
+//! every block and appends their calls to its file: the tracked ones from [`PendingXcmMessages`]
+//! and the others from the [`Event::XcmSent`] events. This works with the native and the wasm
+//! runtime alike, e.g. in try-runtime or emulated runs. The Asset Hub replays the log into a state
+//! snapshot with `pallet_ah_migrator::replay`, without running the Relay Chain.
+//!
+//! ```ignore
+//! let mut recorder = MessageRecorder::<RcRuntime>::start("migration.ahmlog")?;
+//! rc_ext.execute_with(|| {
+//! 	for block in blocks {
+//! 		run_block(block);
+//! 		recorder.record_block()?;
+//! 	}
+//! 	Ok(())
+//! })?;
+//! recorder.finish()?;
+//! let messages = read_message_log("migration.ahmlog")?;
+//! ```
+//!
+//! Within a block, the untracked messages are recorded first and the tracked ones after them in
+//! the order of their query ids, which is the order in which they were sent. Resent messages are
+//! recorded again.
+//!
+//! The file starts with [`MAGIC`] followed by the SCALE encoded [`LoggedMessage`]s.
+
+use crate::*;
+use sp_runtime::SaturatedConversion;
+use std::{
+	collections::BTreeSet,
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::Path,
+};
+
+/// The start of a message log file, including the version of its format.
+pub const MAGIC: &[u8; 8] = b"ahmlog01";
+
+/// A call that the Relay Chain sent to the Asset Hub.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct LoggedMessage {
+	/// The Relay Chain block in which the message was sent.
+	pub rc_block: u32,
+	/// The encoded `AssetHubPalletConfig` call of the `Transact` instruction.
+	pub call: Vec<u8>,
+}
+
+/// Records the messages that the Relay Chain sends to the Asset Hub into a file.
+pub struct MessageRecorder<T: Config> {
+	writer: BufWriter<File>,
+	/// The keys of the tracked messages that are recorded and still pending.
+	recorded: BTreeSet<(QueryId, T::Hash)>,
+}
+
+impl<T: Config> MessageRecorder<T>
+where
+	<T as Config>::RuntimeEvent: TryInto<Event<T>>,
+{
+	/// Create the file at `path` and start recording into it.
+	pub fn start(path: impl AsRef<Path>) -> io::Result<Self> {
+		let mut writer = BufWriter::new(File::create(path)?);
+		writer.write_all(MAGIC)?;
+		Ok(Self { writer, recorded: BTreeSet::new() })
+	}
+
+	/// Record the messages that were sent in the current block.
+	///
+	/// Must be called in the externalities of the Relay Chain once per block, before its events
+	/// are reset.
+	pub fn record_block(&mut self) -> io::Result<()> {
+		let rc_block = frame_system::Pallet::<T>::block_number().saturated_into();
+		let asset_hub = Location::new(0, Parachain(1000));
+
+		for record in frame_system::Pallet::<T>::read_events_no_consensus() {
+			let event = <T as Config>::RuntimeEvent::from_ref(&record.event).clone();
+			if let Ok(Event::XcmSent { destination, message, .. }) = event.try_into() {
+				if destination == asset_hub {
+					write_message(&mut self.writer, rc_block, &message)?;
+				}
+			}
+		}
+
+		let mut pending = PendingXcmMessages::<T>::iter().collect::<Vec<_>>();
+		pending.sort_by_key(|((query_id, _), _)| *query_id);
+		for (key, message) in &pending {
+			if !self.recorded.contains(key) {
+				write_message(&mut self.writer, rc_block, message)?;
+			}
+		}
+		// Confirmed messages are not pending anymore and resent ones get a new query id.
+		self.recorded = pending.into_iter().map(|(key, _)| key).collect();
+
+		Ok(())
+	}
+
+	/// Stop recording and flush the file.
+	pub fn finish(mut self) -> io::Result<()> {
+		self.writer.flush()
+	}
+}
+
+/// Append the `Transact` calls of `message` to `writer`.
+fn write_message<Call>(
+	writer: &mut impl Write,
+	rc_block: u32,
+	message: &Xcm<Call>,
+) -> io::Result<()> {
+	for instruction in message.inner() {
+		if let Transact { call, .. } = instruction {
+			let logged = LoggedMessage { rc_block, call: call.clone().into_encoded() };
+			writer.write_all(&logged.encode())?;
+		}
+	}
+	Ok(())
+}
+
+/// Read the messages of the log file at `path`.
+pub fn read_message_log(path: impl AsRef<Path>) -> io::Result<Vec<LoggedMessage>> {
+	let bytes = std::fs::read(path)?;
+	let mut input = bytes
+		.strip_prefix(MAGIC.as_slice())
+		.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Not a message log"))?;
+
+	let mut messages = Vec::new();
+	while !input.is_empty() {
+		let message = LoggedMessage::decode(&mut input).map_err(|err| {
+			let error = format!("Invalid message {}: {err:?}", messages.len());
+			io::Error::new(io::ErrorKind::InvalidData, error)
+		})?;
+		messages.push(message);
+	}
+	Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn message_log_round_trips() {
+		let path = std::env::temp_dir().join(format!("message_log_{}.ahmlog", std::process::id()));
+		let transact = |call: Vec<u8>| Transact {
+			origin_kind: OriginKind::Superuser,
+			fallback_max_weight: None,
+			call: call.into(),
+		};
+
+		let mut writer = BufWriter::new(File::create(&path).unwrap());
+		writer.write_all(MAGIC).unwrap();
+		let unpaid = UnpaidExecution { weight_limit: Unlimited, check_origin: None };
+		write_message::<()>(&mut writer, 2, &Xcm(vec![unpaid])).unwrap();
+		let transacts = Xcm(vec![transact(vec![255, 1]), transact(vec![255, 2, 3])]);
+		write_message::<()>(&mut writer, 3, &transacts).unwrap();
+		drop(writer);
+
+		let messages = read_message_log(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(
+			messages,
+			vec![
+				LoggedMessage { rc_block: 3, call: vec![255, 1] },
+				LoggedMessage { rc_block: 3, call: vec![255, 2, 3] },
+			]
+		);
+	}
+
+	#[test]
+	fn other_files_are_rejected() {
+		let path = std::env::temp_dir().join(format!("not_a_log_{}.ahmlog", std::process::id()));
+		std::fs::write(&path, b"ahmlog00").unwrap();
+
+		let result = read_message_log(&path);
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+	}
+}
@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations of the pallet.
+//!
+//! [`Migrations`] contains every migration in order and must be added to the runtime's migrations.
+
+use crate::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+/// All migrations of the pallet, in order.
+pub type Migrations<T> = (MigrateToV1<T>, MigrateToV2<T>, MigrateToV3<T>);
+
+/// Count the queries in [`PendingXcmQueries`], which became a counted map.
+pub type MigrateToV1<T> = VersionedMigration<
+	0,
+	1,
+	v1::InitPendingXcmQueriesCounter<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
+
+/// Drop the manager multisig votes of the format without expiry, see [`manager_multisig`].
+pub type MigrateToV2<T> = VersionedMigration<
+	1,
+	2,
+	v2::DropManagerVotesWithoutExpiry<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
+
+/// Add the per-pallet limits to the [`MigrationSettings`].
+pub type MigrateToV3<T> = VersionedMigration<
+	2,
+	3,
+	v3::AddStageLimits<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
+
+pub mod v1 {
+	use super::*;
+
+	pub struct InitPendingXcmQueriesCounter<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InitPendingXcmQueriesCounter<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let count = PendingXcmQueries::<T>::initialize_counter() as u64;
+			T::DbWeight::get().reads_writes(count, 1)
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+	use frame_support::storage_alias;
+
+	/// The votes per proposed call before version 2.
+	#[storage_alias]
+	pub type ManagerMultisigs<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as Config>::RuntimeCall,
+		Vec<AccountId32>,
+		ValueQuery,
+	>;
+
+	/// The old votes carry no expiry and can not be translated into proposals. They are removed
+	/// and a new round is started, so that their signatures can not be reused. The members have
+	/// to vote again.
+	pub struct DropManagerVotesWithoutExpiry<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for DropManagerVotesWithoutExpiry<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let removed = ManagerMultisigs::<T>::clear(u32::MAX, None).backend as u64;
+			if removed.is_zero() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let voters = ManagerVotesInCurrentRound::<T>::clear(u32::MAX, None).backend as u64;
+			ManagerMultisigRound::<T>::mutate(|round| round.saturating_inc());
+			T::DbWeight::get().reads_writes(removed + voters + 1, removed + voters + 1)
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// The migration settings before version 3.
+	#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+	pub struct OldMigrationSettings {
+		pub max_accounts_per_block: Option<u32>,
+		pub max_items_per_block: Option<u32>,
+	}
+
+	pub struct AddStageLimits<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for AddStageLimits<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let translated = Settings::<T>::translate::<OldMigrationSettings, _>(|old| {
+				old.map(|old| MigrationSettings {
+					max_accounts_per_block: old.max_accounts_per_block,
+					max_items_per_block: old.max_items_per_block,
+					stage_limits: Default::default(),
+				})
+			});
+			if translated.is_err() {
+				log::error!(target: LOG_TARGET, "Failed to decode the old migration settings");
+			}
+
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+	}
+}
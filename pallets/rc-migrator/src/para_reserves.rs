@@ -0,0 +1,346 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unlock schedule for the reserves of the parachain pallets.
+//!
+//! The crowdloan migration moves the lease reserves, crowdloan contributions and crowdloan
+//! reserves into `pallet_ah_ops`, where they are keyed by the block after which they can be
+//! released. This migration tells the Asset Hub which paras to release at which block, so that
+//! `pallet_ah_ops` releases them in `on_idle` once the lease ended:
+//!
+//! - `slots::Leases`: the end of the last lease of every para is scheduled.
+//! - `auctions::ReservedAmounts`: bids of an auction that did not conclude before the migration
+//!   are removed and released on the Asset Hub right away.
+//! - `paras_registrar::Paras`: the deposits stay reserved on the Relay Chain together with the
+//!   registration, see [`crate::accounts::AccountState::Part`]. Deposits that are no longer
+//!   reserved on the Relay Chain, because the manager was migrated before the deposit was taken
+//!   into account, were migrated as unnamed reserve with the account. They are unreserved on the
+//!   Asset Hub and set to zero in the registration, so that deregistering the para does not
+//!   unreserve them a second time.
+//!
+//! Contributions to crowdloans without leases are not scheduled and can be withdrawn with
+//! `pallet_ah_ops::Pallet::withdraw_crowdloan_contribution`.
+
+use crate::{crowdloan::num_leases_to_ending_block, types::AccountIdOf, *};
+use sp_runtime::traits::Zero;
+
+pub struct ParaReservesMigrator<T> {
+	_marker: sp_std::marker::PhantomData<T>,
+}
+
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+	Clone,
+	PartialEq,
+	Eq,
+)]
+pub enum RcParaReservesMessage<BlockNumber, AccountId, Balance> {
+	/// Release the reserves and contributions of a para once its last lease ended.
+	LeaseUnlock {
+		/// Parachain ID of the lease slot.
+		para_id: ParaId,
+		/// The block number at which the last lease ends.
+		unlock_block: BlockNumber,
+	},
+	/// Reserve of a bid in an auction that did not conclude.
+	AuctionReserve {
+		/// The account that placed the bid.
+		bidder: AccountId,
+		/// Parachain ID that was bid for.
+		para_id: ParaId,
+		/// Amount that is reserved for the bid.
+		amount: Balance,
+	},
+	/// Registration deposit that was migrated with the account of the manager.
+	RegistrarDeposit {
+		/// The manager that placed the deposit.
+		manager: AccountId,
+		/// Parachain ID of the registration.
+		para_id: ParaId,
+		/// Amount of the deposit.
+		amount: Balance,
+	},
+}
+
+pub type RcParaReservesMessageOf<T> =
+	RcParaReservesMessage<BlockNumberFor<T>, AccountIdOf<T>, crate::BalanceOf<T>>;
+
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+	Clone,
+	PartialEq,
+	Eq,
+)]
+pub enum ParaReservesStage {
+	Leases { last_key: Option<ParaId> },
+	AuctionReserves,
+	Finished,
+	RegistrarDeposits { last_key: Option<ParaId> },
+}
+
+pub mod alias {
+	use super::*;
+
+	/// Alias for `ReservedAmounts` from `auctions`.
+	///
+	/// The `auctions` pallet is not part of the [`Config`] since it is only reachable through the
+	/// crowdloan `Auctioneer`.
+	#[frame_support::storage_alias]
+	pub type ReservedAmounts<T: Config> = StorageMap<
+		Auctions,
+		Twox64Concat,
+		(AccountIdOf<T>, ParaId),
+		crate::BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	/// Alias for `AuctionInfo` from `auctions`.
+	///
+	/// Holds the lease period and starting block of the ongoing auction.
+	#[frame_support::storage_alias]
+	pub type AuctionInfo<T: Config> =
+		StorageValue<Auctions, (BlockNumberFor<T>, BlockNumberFor<T>), OptionQuery>;
+}
+
+impl<T: Config> PalletMigration for ParaReservesMigrator<T> {
+	type Key = ParaReservesStage;
+	type Error = Error<T>;
+
+	fn migrate_many(
+		current_key: Option<Self::Key>,
+		weight_counter: &mut WeightMeter,
+	) -> Result<Option<Self::Key>, Self::Error> {
+		let mut inner_key = current_key.unwrap_or(ParaReservesStage::Leases { last_key: None });
+		let mut messages = XcmBatchAndMeter::new_from_config::<T>();
+
+		loop {
+			if weight_counter.try_consume(T::DbWeight::get().reads_writes(2, 1)).is_err() ||
+				weight_counter.try_consume(messages.consume_weight()).is_err()
+			{
+				log::info!(
+					target: LOG_TARGET,
+					"RC weight limit reached at batch length {}, stopping",
+					messages.len()
+				);
+				if messages.is_empty() {
+					return Err(Error::OutOfWeight);
+				} else {
+					break;
+				}
+			}
+			if T::MaxAhWeight::get()
+				.any_lt(T::AhWeightInfo::receive_para_reserves_messages(messages.len() + 1))
+			{
+				log::info!(
+					target: LOG_TARGET,
+					"AH weight limit reached at batch length {}, stopping",
+					messages.len()
+				);
+				if messages.is_empty() {
+					return Err(Error::OutOfWeight);
+				} else {
+					break;
+				}
+			}
+
+			if messages.len() > max_items_per_block::<T>() {
+				log::info!(
+					target: LOG_TARGET,
+					"Maximum number of items ({:?}) to migrate per block reached, current batch size: {}",
+					max_items_per_block::<T>(),
+					messages.len()
+				);
+				break;
+			}
+
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
+				log::info!(
+					target: LOG_TARGET,
+					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
+					max_xcm_msg_per_block::<T>(),
+					messages.batch_count()
+				);
+				break;
+			}
+
+			inner_key = match inner_key {
+				ParaReservesStage::Leases { last_key } => {
+					let mut iter = match last_key {
+						Some(last_key) => pallet_slots::Leases::<T>::iter_from_key(last_key),
+						None => pallet_slots::Leases::<T>::iter(),
+					};
+
+					match iter.next() {
+						Some((para_id, leases)) => {
+							inner_key = ParaReservesStage::Leases { last_key: Some(para_id) };
+
+							// Same as the crowdloan migration: nothing is reserved for these.
+							if leases.iter().flatten().all(|(_, amount)| amount.is_zero()) {
+								continue;
+							}
+							let unlock_block = num_leases_to_ending_block::<T>(leases.len() as u32)
+								.defensive()
+								.map_err(|_| Error::<T>::Unreachable)?;
+
+							log::debug!(
+								target: LOG_TARGET,
+								"Scheduling unlock of para_id: {:?} at block: {:?}",
+								&para_id,
+								&unlock_block
+							);
+							messages.push(RcParaReservesMessage::LeaseUnlock { para_id, unlock_block });
+							inner_key
+						},
+						None => ParaReservesStage::AuctionReserves,
+					}
+				},
+				ParaReservesStage::AuctionReserves => {
+					match alias::ReservedAmounts::<T>::iter().next() {
+						Some(((bidder, para_id), amount)) => {
+							if alias::AuctionInfo::<T>::exists() {
+								log::warn!(
+									target: LOG_TARGET,
+									"Migrating bid of {:?} for para_id {:?} out of an ongoing auction",
+									&bidder,
+									&para_id
+								);
+							}
+							alias::ReservedAmounts::<T>::remove((&bidder, para_id));
+
+							messages.push(RcParaReservesMessage::AuctionReserve {
+								bidder,
+								para_id,
+								amount,
+							});
+							ParaReservesStage::AuctionReserves
+						},
+						None => ParaReservesStage::RegistrarDeposits { last_key: None },
+					}
+				},
+				ParaReservesStage::RegistrarDeposits { last_key } => {
+					let mut iter = match last_key {
+						Some(last_key) => Paras::<T>::iter_from_key(last_key),
+						None => Paras::<T>::iter(),
+					};
+
+					match iter.next() {
+						Some((para_id, mut info)) => {
+							let reserved = <pallet_balances::Pallet<T> as ReservableCurrency<
+								_,
+							>>::reserved_balance(&info.manager);
+							if !info.deposit.is_zero() && reserved < info.deposit {
+								log::debug!(
+									target: LOG_TARGET,
+									"Migrating registration deposit of para_id: {:?}",
+									&para_id
+								);
+								messages.push(RcParaReservesMessage::RegistrarDeposit {
+									manager: info.manager.clone(),
+									para_id,
+									amount: info.deposit,
+								});
+								info.deposit = Zero::zero();
+								Paras::<T>::insert(para_id, info);
+							}
+							ParaReservesStage::RegistrarDeposits { last_key: Some(para_id) }
+						},
+						None => ParaReservesStage::Finished,
+					}
+				},
+				ParaReservesStage::Finished => break,
+			}
+		}
+
+		if !messages.is_empty() {
+			Pallet::<T>::send_chunked_xcm_and_track(messages, |messages| {
+				types::AhMigratorCall::<T>::ReceiveParaReservesMessages { messages }
+			})?;
+		}
+
+		if inner_key == ParaReservesStage::Finished {
+			Ok(None)
+		} else {
+			Ok(Some(inner_key))
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> crate::types::RcMigrationCheck for ParaReservesMigrator<T> {
+	type RcPrePayload = Vec<RcParaReservesMessageOf<T>>;
+
+	fn pre_check() -> Self::RcPrePayload {
+		let unlocks = pallet_slots::Leases::<T>::iter()
+			.filter(|(_, leases)| !leases.iter().flatten().all(|(_, amount)| amount.is_zero()))
+			.filter_map(|(para_id, leases)| {
+				let unlock_block = num_leases_to_ending_block::<T>(leases.len() as u32).ok()?;
+				Some(RcParaReservesMessage::LeaseUnlock { para_id, unlock_block })
+			});
+		let bids = alias::ReservedAmounts::<T>::iter().map(|((bidder, para_id), amount)| {
+			RcParaReservesMessage::AuctionReserve { bidder, para_id, amount }
+		});
+		let deposits = Paras::<T>::iter().filter_map(|(para_id, info)| {
+			let reserved = <pallet_balances::Pallet<T> as ReservableCurrency<_>>::reserved_balance(
+				&info.manager,
+			);
+			(!info.deposit.is_zero() && reserved < info.deposit).then(|| {
+				RcParaReservesMessage::RegistrarDeposit {
+					manager: info.manager,
+					para_id,
+					amount: info.deposit,
+				}
+			})
+		});
+
+		unlocks.chain(bids).chain(deposits).collect()
+	}
+
+	fn post_check(_: Self::RcPrePayload) {
+		use sp_std::collections::btree_map::BTreeMap;
+
+		assert!(
+			alias::ReservedAmounts::<T>::iter().next().is_none(),
+			"Assert storage 'Auctions::ReservedAmounts::rc_post::empty'"
+		);
+
+		// The registration deposits stay reserved on the Relay Chain.
+		let mut deposits = BTreeMap::<AccountIdOf<T>, crate::BalanceOf<T>>::new();
+		for (_, info) in Paras::<T>::iter() {
+			deposits.entry(info.manager).or_default().saturating_accrue(info.deposit);
+		}
+		for (manager, deposit) in deposits {
+			let reserved =
+				<pallet_balances::Pallet<T> as ReservableCurrency<_>>::reserved_balance(&manager);
+			// Assert storage 'Registrar::Paras::rc_post::deposit_reserved'
+			assert!(
+				reserved >= deposit,
+				"Registration deposit {deposit} of {manager:?} should stay reserved on the Relay \
+					Chain, reserved: {reserved}"
+			);
+		}
+	}
+}
@@ -22,6 +22,11 @@ use crate::{types::*, *};
 /// The -100 is to account for the encoding overhead and additional fields.
 pub const CHUNK_SIZE: u32 = MAX_XCM_SIZE - 100;
 
+/// The maximum number of preimage chunks that can be migrated in a single block.
+///
+/// Can be overridden for the preimage pallet via [`MigrationSettings::stage_limits`].
+pub const MAX_CHUNKS_PER_BLOCK: u32 = 10;
+
 /// A chunk of a preimage that was migrated out of the Relay and can be integrated into AH.
 #[derive(
 	Encode,
@@ -162,22 +167,23 @@ impl<T: Config> PalletMigration for PreimageChunkMigrator<T> {
 			// set the offset of the next_key
 			next_key = Some((next_key_inner, last_offset));
 
-			const MAX_CHUNKS_PER_BLOCK: u32 = 10;
-			if batch.len() >= MAX_CHUNKS_PER_BLOCK {
+			let max_chunks_per_block =
+				stage_max_items_per_block::<T>().unwrap_or(MAX_CHUNKS_PER_BLOCK);
+			if batch.len() >= max_chunks_per_block {
 				log::info!(
 					target: LOG_TARGET,
 					"Maximum number of items ({}) to migrate per block reached, current batch size: {}",
-					MAX_CHUNKS_PER_BLOCK,
+					max_chunks_per_block,
 					batch.len()
 				);
 				break next_key;
 			}
 
-			if batch.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if batch.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					batch.batch_count()
 				);
 				break next_key;
@@ -172,11 +172,11 @@ impl<T: Config> PalletMigration for PreimageRequestStatusMigrator<T> {
 				break next_key;
 			}
 
-			if batch.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if batch.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					batch.batch_count()
 				);
 				break next_key;
@@ -200,11 +200,11 @@ impl<T: Config> ProxyProxiesMigrator<T> {
 			return Err(OutOfWeightError);
 		}
 
-		if batch.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+		if batch.batch_count() >= max_xcm_msg_per_block::<T>() {
 			log::info!(
 				target: LOG_TARGET,
 				"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-				MAX_XCM_MSG_PER_BLOCK,
+				max_xcm_msg_per_block::<T>(),
 				batch.batch_count()
 			);
 			return Err(OutOfWeightError);
@@ -287,11 +287,11 @@ impl<T: Config> PalletMigration for ProxyAnnouncementMigrator<T> {
 				break;
 			}
 
-			if batch.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if batch.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					batch.batch_count()
 				);
 				break;
@@ -0,0 +1,493 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Off-chain migration report.
+//!
+//! Runs the [`RcMigrationCheck`] of a pallet against state snapshots taken before and after the
+//! migration and records the `pre_check` payload, the outcome of both checks and the difference
+//! of the storage of the pallet between the snapshots. The Asset Hub side is filled in by
+//! `pallet_ah_migrator::report`, which also ships the `migration-report` tool.
+//!
+//! Payloads and storage are recorded as [`PayloadEntries`]: one entry per item, compared by its
+//! SCALE encoding. The `Debug` output of an item is only used to display it.
+//!
+//! The report serialises to JSON with [`MigrationReport::to_json`] and renders a human-readable
+//! summary through its `Display` implementation.
+
+use crate::types::RcMigrationCheck;
+use codec::Encode;
+use core::fmt::{self, Debug, Display};
+use serde::Serialize;
+use sp_io::TestExternalities;
+use std::{
+	collections::BTreeMap,
+	panic::{catch_unwind, AssertUnwindSafe},
+};
+
+/// Maximum number of mismatched entries listed per pallet in the human-readable report.
+const MAX_LISTED_ENTRIES: usize = 10;
+
+/// Maximum length of an entry in the human-readable report.
+const MAX_ENTRY_LEN: usize = 120;
+
+/// The `0x` prefixed hex encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+	let mut hex = String::with_capacity(2 + 2 * bytes.len());
+	hex.push_str("0x");
+	for byte in bytes {
+		hex.push_str(&format!("{byte:02x}"));
+	}
+	hex
+}
+
+/// A single item of a payload or of the storage of a pallet.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+pub struct Entry {
+	/// The hex encoded SCALE encoding of the item, prefixed by its label if any.
+	///
+	/// Entries are compared by this field only.
+	pub encoded: String,
+	/// The item in a human-readable form.
+	pub display: String,
+}
+
+impl Entry {
+	/// An entry for `item` with an optional `label`.
+	pub fn new<I: Encode + Debug>(label: Option<&str>, item: &I) -> Self {
+		let encoded = hex(&item.encode());
+		match label {
+			Some(label) => Self {
+				encoded: format!("{label}.{encoded}"),
+				display: format!("{label}.{item:?}"),
+			},
+			None => Self { encoded, display: format!("{item:?}") },
+		}
+	}
+
+	/// An entry for a raw storage `key` and `value`.
+	pub fn raw(key: &[u8], value: &[u8]) -> Self {
+		let encoded = format!("{}={}", hex(key), hex(value));
+		Self { display: encoded.clone(), encoded }
+	}
+}
+
+/// A payload that can be split into [`Entry`]s.
+///
+/// Collections are split into their elements and tuples into the elements of their fields,
+/// labelled by the index of the field.
+pub trait PayloadItems {
+	/// Append the entries of `self` to `entries`.
+	fn push_entries(&self, label: Option<&str>, entries: &mut Vec<Entry>);
+}
+
+impl<T: Encode + Debug> PayloadItems for Vec<T> {
+	fn push_entries(&self, label: Option<&str>, entries: &mut Vec<Entry>) {
+		entries.extend(self.iter().map(|item| Entry::new(label, item)));
+	}
+}
+
+impl<K: Encode + Debug, V: Encode + Debug> PayloadItems for BTreeMap<K, V> {
+	fn push_entries(&self, label: Option<&str>, entries: &mut Vec<Entry>) {
+		entries.extend(self.iter().map(|item| Entry::new(label, &item)));
+	}
+}
+
+macro_rules! impl_payload_items_for_tuple {
+	($($index:tt: $field:ident),+) => {
+		impl<$($field: PayloadItems),+> PayloadItems for ($($field,)+) {
+			fn push_entries(&self, label: Option<&str>, entries: &mut Vec<Entry>) {
+				$(
+					let field = match label {
+						Some(label) => format!("{label}.{}", $index),
+						None => $index.to_string(),
+					};
+					self.$index.push_entries(Some(&field), entries);
+				)+
+			}
+		}
+	};
+}
+
+impl_payload_items_for_tuple!(0: A, 1: B);
+impl_payload_items_for_tuple!(0: A, 1: B, 2: C);
+impl_payload_items_for_tuple!(0: A, 1: B, 2: C, 3: D);
+
+/// The entries of a payload or of the storage of a pallet, see the module docs.
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize)]
+pub struct PayloadEntries(pub Vec<Entry>);
+
+impl PayloadEntries {
+	/// The entries of `payload`.
+	pub fn of<P: PayloadItems>(payload: &P) -> Self {
+		let mut entries = Vec::new();
+		payload.push_entries(None, &mut entries);
+		Self(entries)
+	}
+
+	/// The raw storage entries of the pallet with the given storage `prefix`.
+	///
+	/// Must be called inside the externalities of a snapshot.
+	pub fn of_pallet(prefix: &str) -> Self {
+		let prefix = sp_core::hashing::twox_128(prefix.as_bytes());
+		let mut entries = Vec::new();
+		let mut key = prefix.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if !next.starts_with(&prefix) {
+				break;
+			}
+			let value = sp_io::storage::get(&next).unwrap_or_default();
+			entries.push(Entry::raw(&next, &value));
+			key = next;
+		}
+		Self(entries)
+	}
+
+	/// The number of entries.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Whether there are no entries.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// The entries of `self` that are not in `other`, counting duplicates.
+	pub fn without(&self, other: &Self) -> Self {
+		let mut remaining = BTreeMap::<&str, usize>::new();
+		for entry in &other.0 {
+			*remaining.entry(&entry.encoded).or_default() += 1;
+		}
+		Self(
+			self.0
+				.iter()
+				.filter(|entry| match remaining.get_mut(entry.encoded.as_str()) {
+					Some(count) if *count > 0 => {
+						*count -= 1;
+						false
+					},
+					_ => true,
+				})
+				.cloned()
+				.collect(),
+		)
+	}
+}
+
+/// The difference between two [`PayloadEntries`].
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize)]
+pub struct EntriesDiff {
+	/// Number of entries before.
+	pub count_before: usize,
+	/// Number of entries after.
+	pub count_after: usize,
+	/// Entries only present before.
+	pub only_before: Vec<Entry>,
+	/// Entries only present after.
+	pub only_after: Vec<Entry>,
+}
+
+impl EntriesDiff {
+	/// Compare two sets of entries by their encoding, counting duplicates.
+	pub fn new(before: &PayloadEntries, after: &PayloadEntries) -> Self {
+		let mut only_before = before.without(after).0;
+		let mut only_after = after.without(before).0;
+		only_before.sort();
+		only_after.sort();
+		Self { count_before: before.len(), count_after: after.len(), only_before, only_after }
+	}
+
+	/// Whether both sets of entries are equal.
+	pub fn is_empty(&self) -> bool {
+		self.only_before.is_empty() && self.only_after.is_empty()
+	}
+}
+
+/// Outcome of a `pre_check` or `post_check`.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "outcome", content = "message", rename_all = "snake_case")]
+pub enum CheckOutcome {
+	/// The check passed.
+	Passed,
+	/// The check panicked with the given message.
+	Failed(String),
+	/// The check was not run.
+	Skipped,
+}
+
+impl CheckOutcome {
+	/// Run `check` inside `ext` and catch any panic.
+	pub fn run<R>(ext: &mut TestExternalities, check: impl FnOnce() -> R) -> (Self, Option<R>) {
+		match ext.execute_with(|| catch_unwind(AssertUnwindSafe(check))) {
+			Ok(result) => (Self::Passed, Some(result)),
+			Err(panic) => {
+				let message = panic
+					.downcast_ref::<&str>()
+					.map(|message| message.to_string())
+					.or_else(|| panic.downcast_ref::<String>().cloned())
+					.unwrap_or_else(|| "unknown panic".into());
+				(Self::Failed(message), None)
+			},
+		}
+	}
+}
+
+impl Display for CheckOutcome {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Passed => write!(f, "passed"),
+			Self::Failed(message) => write!(f, "FAILED: {message}"),
+			Self::Skipped => write!(f, "skipped"),
+		}
+	}
+}
+
+/// Payloads, check outcomes and storage changes of one side of the migration.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct ChainReport {
+	/// The payload of `pre_check` against the state before the migration.
+	pub pre: Option<PayloadEntries>,
+	/// The outcome of `pre_check` against the state before the migration.
+	pub pre_check: CheckOutcome,
+	/// The outcome of `post_check` against the state after the migration.
+	pub post_check: CheckOutcome,
+	/// The change of the storage of the pallet from before to after the migration.
+	pub storage: Option<EntriesDiff>,
+}
+
+impl ChainReport {
+	/// A report for a side whose checks were not run.
+	pub fn skipped() -> Self {
+		Self {
+			pre: None,
+			pre_check: CheckOutcome::Skipped,
+			post_check: CheckOutcome::Skipped,
+			storage: None,
+		}
+	}
+
+	/// A report of the storage changes of `pallet` only, without running any checks.
+	pub fn storage_only(
+		pallet: &str,
+		pre_state: &mut TestExternalities,
+		post_state: &mut TestExternalities,
+	) -> Self {
+		Self { storage: Some(storage_diff(pallet, pre_state, post_state)), ..Self::skipped() }
+	}
+}
+
+/// The change of the storage of `pallet` from `pre_state` to `post_state`.
+pub fn storage_diff(
+	pallet: &str,
+	pre_state: &mut TestExternalities,
+	post_state: &mut TestExternalities,
+) -> EntriesDiff {
+	let pre = pre_state.execute_with(|| PayloadEntries::of_pallet(pallet));
+	let post = post_state.execute_with(|| PayloadEntries::of_pallet(pallet));
+	EntriesDiff::new(&pre, &post)
+}
+
+/// Migration report of a single pallet.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct PalletReport {
+	/// The name of the pallet, which is also its storage prefix on both chains.
+	pub pallet: String,
+	/// The Relay Chain side.
+	pub rc: ChainReport,
+	/// The Asset Hub side.
+	pub ah: ChainReport,
+	/// Difference between the storage entries removed from the Relay Chain and the ones added to
+	/// Asset Hub.
+	///
+	/// Only meaningful for storage items that keep their name, hashers and encoding.
+	pub rc_removed_vs_ah_added: Option<EntriesDiff>,
+}
+
+impl PalletReport {
+	/// Report the Relay Chain side of the migration of a pallet.
+	///
+	/// Also returns the Relay Chain pre payload, which the Asset Hub checks take as input.
+	pub fn relay_chain<C>(
+		pallet: &str,
+		rc_pre_state: &mut TestExternalities,
+		rc_post_state: &mut TestExternalities,
+	) -> (Self, Option<C::RcPrePayload>)
+	where
+		C: RcMigrationCheck,
+		C::RcPrePayload: PayloadItems,
+	{
+		let (pre_check, rc_pre_payload) = CheckOutcome::run(rc_pre_state, C::pre_check);
+		let post_check = match rc_pre_payload.clone() {
+			Some(payload) => CheckOutcome::run(rc_post_state, || C::post_check(payload)).0,
+			None => CheckOutcome::Skipped,
+		};
+
+		let report = Self::new(
+			pallet,
+			ChainReport {
+				pre: rc_pre_payload.as_ref().map(PayloadEntries::of),
+				pre_check,
+				post_check,
+				storage: Some(storage_diff(pallet, rc_pre_state, rc_post_state)),
+			},
+		);
+		(report, rc_pre_payload)
+	}
+
+	/// A report with the given Relay Chain side and a skipped Asset Hub side.
+	pub fn new(pallet: &str, rc: ChainReport) -> Self {
+		Self { pallet: pallet.into(), rc, ah: ChainReport::skipped(), rc_removed_vs_ah_added: None }
+	}
+
+	/// Set the Asset Hub side of the report.
+	pub fn with_asset_hub(mut self, ah: ChainReport) -> Self {
+		self.rc_removed_vs_ah_added =
+			self.rc.storage.as_ref().zip(ah.storage.as_ref()).map(|(rc, ah)| {
+				EntriesDiff::new(
+					&PayloadEntries(rc.only_before.clone()),
+					&PayloadEntries(ah.only_after.clone()),
+				)
+			});
+		self.ah = ah;
+		self
+	}
+
+	/// Whether all checks that were run passed.
+	pub fn passed(&self) -> bool {
+		[&self.rc.pre_check, &self.rc.post_check, &self.ah.pre_check, &self.ah.post_check]
+			.iter()
+			.all(|outcome| !matches!(outcome, CheckOutcome::Failed(_)))
+	}
+}
+
+/// Migration report of all pallets.
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize)]
+pub struct MigrationReport {
+	/// The per pallet reports in the order they were added.
+	pub pallets: Vec<PalletReport>,
+}
+
+impl MigrationReport {
+	/// Add the report of a pallet.
+	pub fn push(&mut self, report: PalletReport) {
+		self.pallets.push(report);
+	}
+
+	/// Whether all checks of all pallets passed.
+	pub fn passed(&self) -> bool {
+		self.pallets.iter().all(PalletReport::passed)
+	}
+
+	/// The report as pretty printed JSON.
+	pub fn to_json(&self) -> String {
+		serde_json::to_string_pretty(self).expect("the report only contains strings and numbers")
+	}
+}
+
+fn write_diff(f: &mut fmt::Formatter<'_>, label: &str, diff: &EntriesDiff) -> fmt::Result {
+	writeln!(
+		f,
+		"  {label}: {} -> {} entries ({} only before, {} only after)",
+		diff.count_before,
+		diff.count_after,
+		diff.only_before.len(),
+		diff.only_after.len()
+	)?;
+	for (side, entries) in [("-", &diff.only_before), ("+", &diff.only_after)] {
+		for entry in entries.iter().take(MAX_LISTED_ENTRIES) {
+			let shortened: String = entry.display.chars().take(MAX_ENTRY_LEN).collect();
+			writeln!(f, "    {side} {shortened}")?;
+		}
+		if entries.len() > MAX_LISTED_ENTRIES {
+			writeln!(f, "    {side} ... {} more", entries.len() - MAX_LISTED_ENTRIES)?;
+		}
+	}
+	Ok(())
+}
+
+impl Display for MigrationReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for report in &self.pallets {
+			writeln!(f, "== {} ==", report.pallet)?;
+			for (side, chain) in [("RC", &report.rc), ("AH", &report.ah)] {
+				writeln!(
+					f,
+					"  {side} pre_check: {}, post_check: {}",
+					chain.pre_check, chain.post_check
+				)?;
+				if let Some(diff) = &chain.storage {
+					write_diff(f, &format!("{side} storage pre -> post"), diff)?;
+				}
+			}
+			if let Some(diff) = &report.rc_removed_vs_ah_added {
+				write_diff(f, "RC removed -> AH added", diff)?;
+			}
+		}
+		let failed = self.pallets.iter().filter(|report| !report.passed()).count();
+		writeln!(f, "{} pallets, {failed} with failed checks", self.pallets.len())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn encoded(entries: &[Entry]) -> Vec<&str> {
+		entries.iter().map(|entry| entry.encoded.as_str()).collect()
+	}
+
+	#[test]
+	fn payload_entries_are_compared_by_encoding() {
+		let payload = (vec![1u8, 2], vec![(3u16, true)]);
+
+		let entries = PayloadEntries::of(&payload);
+		assert_eq!(encoded(&entries.0), vec!["0.0x01", "0.0x02", "1.0x030001"]);
+		assert_eq!(entries.0[2].display, "1.(3, true)");
+	}
+
+	#[test]
+	fn entries_diff_counts_duplicates() {
+		let before = PayloadEntries::of(&vec![1u8, 2, 2]);
+		let after = PayloadEntries::of(&vec![2u8, 3]);
+
+		let diff = EntriesDiff::new(&before, &after);
+		assert_eq!(diff.count_before, 3);
+		assert_eq!(diff.count_after, 2);
+		assert_eq!(encoded(&diff.only_before), vec!["0x01", "0x02"]);
+		assert_eq!(encoded(&diff.only_after), vec!["0x03"]);
+	}
+
+	#[test]
+	fn storage_diff_only_reads_the_pallet() {
+		let prefix = sp_core::hashing::twox_128(b"Indices");
+		let key = |suffix: &[u8]| [&prefix[..], suffix].concat();
+		let mut pre = TestExternalities::default();
+		pre.execute_with(|| {
+			sp_io::storage::set(&key(b"a"), &[1]);
+			sp_io::storage::set(&key(b"b"), &[2]);
+			sp_io::storage::set(b"other", &[3]);
+		});
+		let mut post = TestExternalities::default();
+		post.execute_with(|| sp_io::storage::set(&key(b"b"), &[2]));
+
+		let diff = storage_diff("Indices", &mut pre, &mut post);
+		assert_eq!(diff.count_before, 2);
+		assert_eq!(diff.count_after, 1);
+		assert_eq!(diff.only_before, vec![Entry::raw(&key(b"a"), &[1])]);
+		assert!(diff.only_after.is_empty());
+	}
+}
@@ -106,11 +106,11 @@ impl<T: Config> PalletMigration for SchedulerMigrator<T> {
 				break;
 			}
 
-			if messages.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					messages.batch_count()
 				);
 				break;
@@ -218,11 +218,11 @@ impl<T: Config> PalletMigration for SchedulerAgendaMigrator<T> {
 				break last_key;
 			}
 
-			if messages.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					messages.batch_count()
 				);
 				break last_key;
@@ -414,27 +414,12 @@ impl<T: Config> SchedulerMigrator<T> {
 			<T as pallet_scheduler::Config>::MaxScheduledPerBlock,
 		>,
 	) -> Vec<Option<Vec<u8>>> {
-		use frame_support::traits::{Bounded, QueryPreimage};
-
 		// Convert based on Schedules existance and call type.
 		tasks
 			.into_inner()
 			.into_iter()
 			.map(|maybe_schedule| {
-				maybe_schedule.and_then(|sched| match sched.call {
-					// Inline. Grab inlined call.
-					Bounded::Inline(bounded_call) => Some(bounded_call.into_inner()),
-					// Lookup. Fetch preimage and store.
-					Bounded::Lookup { hash, len } =>
-						<pallet_preimage::Pallet<T> as QueryPreimage>::fetch(&hash, Some(len))
-							.ok()
-							.map(|preimage| preimage.into_owned()),
-					// Legacy. Fetch preimage and store.
-					Bounded::Legacy { hash, .. } =>
-						<pallet_preimage::Pallet<T> as QueryPreimage>::fetch(&hash, None)
-							.ok()
-							.map(|preimage| preimage.into_owned()),
-				})
+				maybe_schedule.and_then(|sched| crate::call_report::fetch_call::<T, _>(&sched.call))
 			})
 			.collect::<Vec<_>>()
 	}
@@ -0,0 +1,534 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configurable order of the pallet migrations.
+//!
+//! The runtime declares the pallets it wants migrated, and in which order, through
+//! [`Config::MigrationSequence`]: a tuple of the pallets' migrators, e.g.
+//! `(AccountsMigrator<Runtime>, VestingMigrator<Runtime>)`. Pallets that are not part of the
+//! sequence are not migrated. [`DefaultMigrationSequence`] contains every pallet in the order that
+//! Polkadot and Kusama use.
+//!
+//! Each pallet migration runs through its `*Init`, `*Ongoing` and `*Done` stages of
+//! [`MigrationStage`] as before; only the transition from one pallet to the next follows the
+//! sequence. Pallets can further be skipped at runtime via [`Pallet::skip_pallet_migration`].
+//!
+//! A sequence must contain every pallet at most once, start with [`MigratedPallet::Accounts`] and
+//! migrate the [dependencies](MigratedPallet::dependencies) of a pallet before it. This is checked
+//! by the pallet's `integrity_test`.
+
+use crate::*;
+
+/// A pallet whose data is migrated by one or more stages of [`MigrationStage`].
+///
+/// The indices are pinned, so that the encoding does not depend on the `kusama-ahm` feature and
+/// the Relay Chain and the Asset Hub agree on it.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum MigratedPallet {
+	#[codec(index = 0)]
+	Accounts,
+	#[codec(index = 1)]
+	Multisig,
+	#[codec(index = 2)]
+	Claims,
+	#[codec(index = 3)]
+	Proxy,
+	#[codec(index = 4)]
+	Preimage,
+	#[codec(index = 5)]
+	NomPools,
+	#[codec(index = 6)]
+	Vesting,
+	#[codec(index = 7)]
+	DelegatedStaking,
+	#[codec(index = 8)]
+	Indices,
+	#[codec(index = 9)]
+	Referenda,
+	#[codec(index = 10)]
+	BagsList,
+	#[codec(index = 11)]
+	Scheduler,
+	#[codec(index = 12)]
+	ConvictionVoting,
+	#[codec(index = 13)]
+	Bounties,
+	#[codec(index = 14)]
+	ChildBounties,
+	#[codec(index = 15)]
+	AssetRate,
+	#[codec(index = 16)]
+	Crowdloan,
+	#[codec(index = 17)]
+	Treasury,
+	#[cfg(feature = "kusama-ahm")]
+	#[codec(index = 18)]
+	Recovery,
+	#[cfg(feature = "kusama-ahm")]
+	#[codec(index = 19)]
+	Society,
+	#[codec(index = 20)]
+	Staking,
+	#[codec(index = 21)]
+	FastUnstake,
+	#[codec(index = 22)]
+	ParaReserves,
+}
+
+impl MigratedPallet {
+	/// Whether the migration can not do without this pallet.
+	///
+	/// Mandatory pallets must be part of every sequence and can not be skipped.
+	pub fn is_mandatory(self) -> bool {
+		matches!(self, MigratedPallet::Accounts)
+	}
+
+	/// The pallets that must be migrated before this one.
+	///
+	/// Every pallet needs the accounts. Beyond that, the referenda and the scheduler refer to
+	/// preimages, child bounties to their parent bounties and delegated staking to the pools.
+	pub fn dependencies(self) -> &'static [MigratedPallet] {
+		use MigratedPallet::*;
+
+		match self {
+			Accounts => &[],
+			Referenda | Scheduler => &[Accounts, Preimage],
+			ChildBounties => &[Accounts, Bounties],
+			DelegatedStaking => &[Accounts, NomPools],
+			_ => &[Accounts],
+		}
+	}
+
+	/// The stage that starts the migration of this pallet.
+	pub fn init_stage<
+		AccountId,
+		BlockNumber,
+		BagsListScore,
+		VotingClass,
+		AssetKind,
+		SchedulerBlockNumber,
+	>(
+		self,
+	) -> MigrationStage<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlockNumber>
+	{
+		match self {
+			MigratedPallet::Accounts => MigrationStage::AccountsMigrationInit,
+			MigratedPallet::Multisig => MigrationStage::MultisigMigrationInit,
+			MigratedPallet::Claims => MigrationStage::ClaimsMigrationInit,
+			MigratedPallet::Proxy => MigrationStage::ProxyMigrationInit,
+			MigratedPallet::Preimage => MigrationStage::PreimageMigrationInit,
+			MigratedPallet::NomPools => MigrationStage::NomPoolsMigrationInit,
+			MigratedPallet::Vesting => MigrationStage::VestingMigrationInit,
+			MigratedPallet::DelegatedStaking => MigrationStage::DelegatedStakingMigrationInit,
+			MigratedPallet::Indices => MigrationStage::IndicesMigrationInit,
+			MigratedPallet::Referenda => MigrationStage::ReferendaMigrationInit,
+			MigratedPallet::BagsList => MigrationStage::BagsListMigrationInit,
+			MigratedPallet::Scheduler => MigrationStage::SchedulerMigrationInit,
+			MigratedPallet::ConvictionVoting => MigrationStage::ConvictionVotingMigrationInit,
+			MigratedPallet::Bounties => MigrationStage::BountiesMigrationInit,
+			MigratedPallet::ChildBounties => MigrationStage::ChildBountiesMigrationInit,
+			MigratedPallet::AssetRate => MigrationStage::AssetRateMigrationInit,
+			MigratedPallet::Crowdloan => MigrationStage::CrowdloanMigrationInit,
+			MigratedPallet::ParaReserves => MigrationStage::ParaReservesMigrationInit,
+			MigratedPallet::Treasury => MigrationStage::TreasuryMigrationInit,
+			#[cfg(feature = "kusama-ahm")]
+			MigratedPallet::Recovery => MigrationStage::RecoveryMigrationInit,
+			#[cfg(feature = "kusama-ahm")]
+			MigratedPallet::Society => MigrationStage::SocietyMigrationInit,
+			MigratedPallet::FastUnstake => MigrationStage::FastUnstakeMigrationInit,
+			MigratedPallet::Staking => MigrationStage::StakingMigrationInit,
+		}
+	}
+}
+
+impl<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlockNumber>
+	MigrationStage<AccountId, BlockNumber, BagsListScore, VotingClass, AssetKind, SchedulerBlockNumber>
+{
+	/// The pallet that this stage migrates, if any.
+	pub fn pallet(&self) -> Option<MigratedPallet> {
+		use MigrationStage::*;
+
+		Some(match self {
+			AccountsMigrationInit | AccountsMigrationOngoing { .. } | AccountsMigrationDone =>
+				MigratedPallet::Accounts,
+			MultisigMigrationInit | MultisigMigrationOngoing { .. } | MultisigMigrationDone =>
+				MigratedPallet::Multisig,
+			ClaimsMigrationInit | ClaimsMigrationOngoing { .. } | ClaimsMigrationDone =>
+				MigratedPallet::Claims,
+			ProxyMigrationInit |
+			ProxyMigrationProxies { .. } |
+			ProxyMigrationAnnouncements { .. } |
+			ProxyMigrationDone => MigratedPallet::Proxy,
+			PreimageMigrationInit |
+			PreimageMigrationChunksOngoing { .. } |
+			PreimageMigrationChunksDone |
+			PreimageMigrationRequestStatusOngoing { .. } |
+			PreimageMigrationRequestStatusDone |
+			PreimageMigrationLegacyRequestStatusInit |
+			PreimageMigrationLegacyRequestStatusOngoing { .. } |
+			PreimageMigrationLegacyRequestStatusDone |
+			PreimageMigrationDone => MigratedPallet::Preimage,
+			NomPoolsMigrationInit | NomPoolsMigrationOngoing { .. } | NomPoolsMigrationDone =>
+				MigratedPallet::NomPools,
+			VestingMigrationInit | VestingMigrationOngoing { .. } | VestingMigrationDone =>
+				MigratedPallet::Vesting,
+			DelegatedStakingMigrationInit |
+			DelegatedStakingMigrationOngoing { .. } |
+			DelegatedStakingMigrationDone => MigratedPallet::DelegatedStaking,
+			IndicesMigrationInit | IndicesMigrationOngoing { .. } | IndicesMigrationDone =>
+				MigratedPallet::Indices,
+			ReferendaMigrationInit | ReferendaMigrationOngoing { .. } | ReferendaMigrationDone =>
+				MigratedPallet::Referenda,
+			BagsListMigrationInit | BagsListMigrationOngoing { .. } | BagsListMigrationDone =>
+				MigratedPallet::BagsList,
+			SchedulerMigrationInit |
+			SchedulerMigrationOngoing { .. } |
+			SchedulerAgendaMigrationOngoing { .. } |
+			SchedulerMigrationDone => MigratedPallet::Scheduler,
+			ConvictionVotingMigrationInit |
+			ConvictionVotingMigrationOngoing { .. } |
+			ConvictionVotingMigrationDone => MigratedPallet::ConvictionVoting,
+			BountiesMigrationInit | BountiesMigrationOngoing { .. } | BountiesMigrationDone =>
+				MigratedPallet::Bounties,
+			ChildBountiesMigrationInit |
+			ChildBountiesMigrationOngoing { .. } |
+			ChildBountiesMigrationDone => MigratedPallet::ChildBounties,
+			AssetRateMigrationInit | AssetRateMigrationOngoing { .. } | AssetRateMigrationDone =>
+				MigratedPallet::AssetRate,
+			CrowdloanMigrationInit | CrowdloanMigrationOngoing { .. } | CrowdloanMigrationDone =>
+				MigratedPallet::Crowdloan,
+			ParaReservesMigrationInit |
+			ParaReservesMigrationOngoing { .. } |
+			ParaReservesMigrationDone => MigratedPallet::ParaReserves,
+			TreasuryMigrationInit | TreasuryMigrationOngoing { .. } | TreasuryMigrationDone =>
+				MigratedPallet::Treasury,
+			#[cfg(feature = "kusama-ahm")]
+			RecoveryMigrationInit | RecoveryMigrationOngoing { .. } | RecoveryMigrationDone =>
+				MigratedPallet::Recovery,
+			#[cfg(feature = "kusama-ahm")]
+			SocietyMigrationInit | SocietyMigrationOngoing { .. } | SocietyMigrationDone =>
+				MigratedPallet::Society,
+			FastUnstakeMigrationInit |
+			FastUnstakeMigrationOngoing { .. } |
+			FastUnstakeMigrationDone => MigratedPallet::FastUnstake,
+			StakingMigrationInit | StakingMigrationOngoing { .. } | StakingMigrationDone =>
+				MigratedPallet::Staking,
+			Pending |
+			MigrationPaused |
+			Scheduled { .. } |
+			WaitingForAh |
+			WarmUp { .. } |
+			Starting |
+			PureProxyCandidatesMigrationInit |
+			CoolOff { .. } |
+			SignalMigrationFinish |
+			MigrationDone => return None,
+		})
+	}
+}
+
+/// A [`PalletMigration`] that migrates the data of a [`MigratedPallet`].
+///
+/// Pallets that are migrated in multiple steps, like the preimage or proxy pallet, implement this
+/// for their first migrator only.
+pub trait NamedPalletMigration {
+	/// The pallet that is migrated.
+	const PALLET: MigratedPallet;
+}
+
+/// Why a [`MigrationSequence`] is invalid.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SequenceError {
+	/// The pallet appears more than once.
+	Duplicate(MigratedPallet),
+	/// The mandatory pallet is missing.
+	MissingMandatory(MigratedPallet),
+	/// The pallet is migrated without or before its dependency.
+	Dependency { pallet: MigratedPallet, dependency: MigratedPallet },
+}
+
+/// Check that `pallets` is a valid migration sequence.
+pub fn validate_sequence(pallets: &[MigratedPallet]) -> Result<(), SequenceError> {
+	if !pallets.contains(&MigratedPallet::Accounts) {
+		return Err(SequenceError::MissingMandatory(MigratedPallet::Accounts));
+	}
+
+	for (position, pallet) in pallets.iter().enumerate() {
+		let before = &pallets[..position];
+		if before.contains(pallet) {
+			return Err(SequenceError::Duplicate(*pallet));
+		}
+		if let Some(dependency) =
+			pallet.dependencies().iter().find(|dependency| !before.contains(dependency))
+		{
+			return Err(SequenceError::Dependency { pallet: *pallet, dependency: *dependency });
+		}
+	}
+	Ok(())
+}
+
+/// An ordered list of pallet migrations. Implemented for tuples of [`NamedPalletMigration`].
+pub trait MigrationSequence {
+	/// The migrated pallets in the order in which they are migrated.
+	fn pallets() -> Vec<MigratedPallet>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(32)]
+#[tuple_types_custom_trait_bound(NamedPalletMigration)]
+impl MigrationSequence for Tuple {
+	fn pallets() -> Vec<MigratedPallet> {
+		vec![for_tuples!( #( Tuple::PALLET ),* )]
+	}
+}
+
+macro_rules! impl_named_pallet_migration {
+	($( $(#[$attr:meta])* $migrator:ty => $pallet:ident ),* $(,)?) => {
+		$(
+			$(#[$attr])*
+			impl<T: Config> NamedPalletMigration for $migrator {
+				const PALLET: MigratedPallet = MigratedPallet::$pallet;
+			}
+		)*
+	};
+}
+
+impl_named_pallet_migration!(
+	AccountsMigrator<T> => Accounts,
+	MultisigMigrator<T> => Multisig,
+	ClaimsMigrator<T> => Claims,
+	ProxyProxiesMigrator<T> => Proxy,
+	PreimageChunkMigrator<T> => Preimage,
+	NomPoolsMigrator<T> => NomPools,
+	VestingMigrator<T> => Vesting,
+	DelegatedStakingMigrator<T> => DelegatedStaking,
+	IndicesMigrator<T> => Indices,
+	referenda::ReferendaMigrator<T> => Referenda,
+	BagsListMigrator<T> => BagsList,
+	scheduler::SchedulerMigrator<T> => Scheduler,
+	conviction_voting::ConvictionVotingMigrator<T> => ConvictionVoting,
+	bounties::BountiesMigrator<T> => Bounties,
+	ChildBountiesMigrator<T> => ChildBounties,
+	asset_rate::AssetRateMigrator<T> => AssetRate,
+	crowdloan::CrowdloanMigrator<T> => Crowdloan,
+	para_reserves::ParaReservesMigrator<T> => ParaReserves,
+	treasury::TreasuryMigrator<T> => Treasury,
+	#[cfg(feature = "kusama-ahm")]
+	recovery::RecoveryMigrator<T> => Recovery,
+	#[cfg(feature = "kusama-ahm")]
+	society::SocietyMigrator<T> => Society,
+	fast_unstake::FastUnstakeMigrator<T> => FastUnstake,
+	staking::StakingMigrator<T> => Staking,
+);
+
+/// Every pallet migration in the order used by Polkadot.
+#[cfg(not(feature = "kusama-ahm"))]
+pub type DefaultMigrationSequence<T> = (
+	AccountsMigrator<T>,
+	MultisigMigrator<T>,
+	ClaimsMigrator<T>,
+	ProxyProxiesMigrator<T>,
+	PreimageChunkMigrator<T>,
+	NomPoolsMigrator<T>,
+	VestingMigrator<T>,
+	DelegatedStakingMigrator<T>,
+	IndicesMigrator<T>,
+	referenda::ReferendaMigrator<T>,
+	BagsListMigrator<T>,
+	scheduler::SchedulerMigrator<T>,
+	conviction_voting::ConvictionVotingMigrator<T>,
+	bounties::BountiesMigrator<T>,
+	ChildBountiesMigrator<T>,
+	asset_rate::AssetRateMigrator<T>,
+	crowdloan::CrowdloanMigrator<T>,
+	para_reserves::ParaReservesMigrator<T>,
+	treasury::TreasuryMigrator<T>,
+	fast_unstake::FastUnstakeMigrator<T>,
+	staking::StakingMigrator<T>,
+);
+
+/// Every pallet migration in the order used by Kusama.
+#[cfg(feature = "kusama-ahm")]
+pub type DefaultMigrationSequence<T> = (
+	AccountsMigrator<T>,
+	MultisigMigrator<T>,
+	ClaimsMigrator<T>,
+	ProxyProxiesMigrator<T>,
+	PreimageChunkMigrator<T>,
+	NomPoolsMigrator<T>,
+	VestingMigrator<T>,
+	DelegatedStakingMigrator<T>,
+	IndicesMigrator<T>,
+	referenda::ReferendaMigrator<T>,
+	BagsListMigrator<T>,
+	scheduler::SchedulerMigrator<T>,
+	conviction_voting::ConvictionVotingMigrator<T>,
+	bounties::BountiesMigrator<T>,
+	ChildBountiesMigrator<T>,
+	asset_rate::AssetRateMigrator<T>,
+	crowdloan::CrowdloanMigrator<T>,
+	para_reserves::ParaReservesMigrator<T>,
+	treasury::TreasuryMigrator<T>,
+	recovery::RecoveryMigrator<T>,
+	society::SocietyMigrator<T>,
+	fast_unstake::FastUnstakeMigrator<T>,
+	staking::StakingMigrator<T>,
+);
+
+impl<T: Config> Pallet<T> {
+	/// The stage to enter after the migration of `after` is done, or the first pallet's stage if
+	/// `after` is `None`.
+	///
+	/// Follows [`Config::MigrationSequence`], leaving out pallets in [`SkippedPallets`]. Returns
+	/// [`MigrationStage::SignalMigrationFinish`] once no pallet is left.
+	pub fn next_pallet_stage(after: Option<MigratedPallet>) -> MigrationStageOf<T> {
+		let sequence = T::MigrationSequence::pallets();
+		let start = match after {
+			Some(after) => sequence
+				.iter()
+				.position(|pallet| *pallet == after)
+				.map_or(sequence.len(), |position| position.saturating_add(1)),
+			None => 0,
+		};
+
+		sequence
+			.into_iter()
+			.skip(start)
+			.find(|pallet| !SkippedPallets::<T>::contains_key(pallet))
+			.map_or(MigrationStage::SignalMigrationFinish, MigratedPallet::init_stage)
+	}
+
+	/// Whether the migration of `pallet` is still to come.
+	///
+	/// `false` once its first stage was entered, and for pallets outside of
+	/// [`Config::MigrationSequence`].
+	pub fn pallet_migration_pending(pallet: MigratedPallet) -> bool {
+		let sequence = T::MigrationSequence::pallets();
+		let Some(position) = sequence.iter().position(|p| *p == pallet) else { return false };
+
+		let stage = match RcMigrationStage::<T>::get() {
+			MigrationStage::MigrationPaused => PausedStage::<T>::get().unwrap_or_default(),
+			stage => stage,
+		};
+		match stage.pallet() {
+			Some(current) => sequence
+				.iter()
+				.position(|p| *p == current)
+				.is_some_and(|current| position > current),
+			None => !matches!(
+				stage,
+				MigrationStage::CoolOff { .. } |
+					MigrationStage::SignalMigrationFinish |
+					MigrationStage::MigrationDone
+			),
+		}
+	}
+
+	/// The pallets whose migration is still to come and that are not skipped.
+	pub fn remaining_pallets() -> impl Iterator<Item = MigratedPallet> {
+		T::MigrationSequence::pallets().into_iter().filter(|pallet| {
+			!SkippedPallets::<T>::contains_key(pallet) && Self::pallet_migration_pending(*pallet)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use MigratedPallet::*;
+
+	#[test]
+	fn appended_stages_keep_the_encoding_of_existing_stages() {
+		type Stage = MigrationStage<u64, u32, u64, u16, u32, u32>;
+		let index = |stage: Stage| stage.encode()[0];
+
+		let done = index(MigrationStage::MigrationDone);
+		assert_eq!(index(MigrationStage::FastUnstakeMigrationInit), done + 1);
+		assert_eq!(index(MigrationStage::ParaReservesMigrationInit), done + 4);
+		assert_eq!(Staking.encode()[0] + 1, FastUnstake.encode()[0]);
+		assert_eq!(FastUnstake.encode()[0] + 1, ParaReserves.encode()[0]);
+	}
+
+	#[test]
+	fn pallet_encoding_does_not_depend_on_the_kusama_pallets() {
+		assert_eq!(Treasury.encode(), vec![17]);
+		assert_eq!(Staking.encode(), vec![20]);
+		assert_eq!(ParaReserves.encode(), vec![22]);
+		assert_eq!(MigratedPallet::decode(&mut &[20u8][..]).unwrap(), Staking);
+	}
+
+	#[test]
+	fn default_sequences_are_valid() {
+		let polkadot = [
+			Accounts,
+			Multisig,
+			Claims,
+			Proxy,
+			Preimage,
+			NomPools,
+			Vesting,
+			DelegatedStaking,
+			Indices,
+			Referenda,
+			BagsList,
+			Scheduler,
+			ConvictionVoting,
+			Bounties,
+			ChildBounties,
+			AssetRate,
+			Crowdloan,
+			ParaReserves,
+			Treasury,
+			FastUnstake,
+			Staking,
+		];
+		assert_eq!(validate_sequence(&polkadot), Ok(()));
+		assert_eq!(validate_sequence(&[Accounts]), Ok(()));
+		assert_eq!(validate_sequence(&[Accounts, Preimage, Scheduler]), Ok(()));
+	}
+
+	#[test]
+	fn invalid_sequences_are_rejected() {
+		assert_eq!(
+			validate_sequence(&[Accounts, Vesting, Vesting]),
+			Err(SequenceError::Duplicate(Vesting))
+		);
+		assert_eq!(validate_sequence(&[]), Err(SequenceError::MissingMandatory(Accounts)));
+		assert_eq!(
+			validate_sequence(&[Vesting, Accounts]),
+			Err(SequenceError::Dependency { pallet: Vesting, dependency: Accounts })
+		);
+		assert_eq!(
+			validate_sequence(&[Accounts, Scheduler, Preimage]),
+			Err(SequenceError::Dependency { pallet: Scheduler, dependency: Preimage })
+		);
+		assert_eq!(
+			validate_sequence(&[Accounts, ChildBounties]),
+			Err(SequenceError::Dependency { pallet: ChildBounties, dependency: Bounties })
+		);
+	}
+}
@@ -0,0 +1,175 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured progress report of the migration, exposed through the `migration_status` view
+//! function.
+
+use crate::*;
+use sp_runtime::SaturatedConversion;
+
+/// Progress report of the migration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MigrationProgress<Stage, BlockNumber> {
+	/// The current stage.
+	pub stage: Stage,
+	/// Position of the current stage in the migration sequence.
+	///
+	/// Every pallet of [`Config::MigrationSequence`] that is not skipped counts as one stage,
+	/// regardless of how many [`MigrationStage`]s it goes through. While the migration is paused,
+	/// the position of the stage it was paused at.
+	pub stage_index: Option<u32>,
+	/// Total number of stages in the migration sequence.
+	pub total_stages: u32,
+	/// Number of items sent to the Asset Hub since the current stage was entered.
+	pub items_migrated: u32,
+	/// Number of items left to migrate in the current stage.
+	///
+	/// Only known for stages whose source is a counted map that is drained by the migration.
+	pub items_remaining: Option<u32>,
+	/// Number of items of the current stage in total, `items_migrated + items_remaining`.
+	pub items_total: Option<u32>,
+	/// Number of accounts in [`RcAccounts`].
+	pub rc_accounts: u32,
+	/// Number of messages in [`PendingXcmMessages`].
+	pub pending_xcm_messages: u32,
+	/// Number of queries in [`PendingXcmQueries`].
+	pub pending_xcm_queries: u32,
+	/// The block at which the migration started, see [`MigrationStartBlock`].
+	pub started_at: Option<BlockNumber>,
+	/// Estimated number of blocks until the data migration is finished.
+	///
+	/// Linear extrapolation of the blocks elapsed since [`MigrationStartBlock`] over the pallets
+	/// migrated so far. Includes the warm-up period and therefore overestimates early on. `None`
+	/// until the first pallet is migrated.
+	pub estimated_blocks_remaining: Option<u32>,
+}
+
+/// Number of stages before the first pallet migration: `Pending`, `Scheduled`, `WaitingForAh`,
+/// `WarmUp`, `Starting` and `PureProxyCandidatesMigrationInit`.
+const STAGES_BEFORE_PALLETS: u32 = 6;
+
+/// Number of stages after the last pallet migration: `CoolOff`, `SignalMigrationFinish` and
+/// `MigrationDone`.
+const STAGES_AFTER_PALLETS: u32 = 3;
+
+impl<T: Config> Pallet<T> {
+	/// Progress report of the migration.
+	pub fn migration_progress() -> MigrationProgress<MigrationStageOf<T>, BlockNumberFor<T>> {
+		let stage = RcMigrationStage::<T>::get();
+		let pallets = Self::sequenced_pallets();
+		let stage_index = match stage {
+			MigrationStage::MigrationPaused =>
+				PausedStage::<T>::get().and_then(|paused| Self::stage_position(&paused, &pallets)),
+			_ => Self::stage_position(&stage, &pallets),
+		};
+		let started_at = MigrationStartBlock::<T>::get();
+		let items_migrated = StageMigratedItems::<T>::get();
+		let items_remaining = Self::items_remaining(&stage);
+		let pallet_count = pallets.len() as u32;
+
+		MigrationProgress {
+			items_remaining,
+			items_total: items_remaining.map(|remaining| remaining.saturating_add(items_migrated)),
+			estimated_blocks_remaining: stage_index.and_then(|index| {
+				Self::estimate_blocks_remaining(index, pallet_count, started_at?)
+			}),
+			stage,
+			stage_index,
+			total_stages: STAGES_BEFORE_PALLETS + pallet_count + STAGES_AFTER_PALLETS,
+			items_migrated,
+			rc_accounts: RcAccounts::<T>::count(),
+			pending_xcm_messages: PendingXcmMessages::<T>::count(),
+			pending_xcm_queries: PendingXcmQueries::<T>::count(),
+			started_at,
+		}
+	}
+
+	/// The pallets of [`Config::MigrationSequence`] that are not skipped, in migration order.
+	fn sequenced_pallets() -> Vec<MigratedPallet> {
+		T::MigrationSequence::pallets()
+			.into_iter()
+			.filter(|pallet| !SkippedPallets::<T>::contains_key(pallet))
+			.collect()
+	}
+
+	/// Position of `stage` in the migration sequence of `pallets`.
+	///
+	/// [`MigrationStage::MigrationPaused`] is not part of the sequence and has no position.
+	fn stage_position(stage: &MigrationStageOf<T>, pallets: &[MigratedPallet]) -> Option<u32> {
+		let after_pallets = STAGES_BEFORE_PALLETS + pallets.len() as u32;
+		Some(match stage {
+			MigrationStage::Pending => 0,
+			MigrationStage::Scheduled { .. } => 1,
+			MigrationStage::WaitingForAh => 2,
+			MigrationStage::WarmUp { .. } => 3,
+			MigrationStage::Starting => 4,
+			MigrationStage::PureProxyCandidatesMigrationInit => 5,
+			MigrationStage::CoolOff { .. } => after_pallets,
+			MigrationStage::SignalMigrationFinish => after_pallets + 1,
+			MigrationStage::MigrationDone => after_pallets + 2,
+			MigrationStage::MigrationPaused => return None,
+			stage => {
+				let pallet = stage.pallet()?;
+				let position = pallets.iter().position(|p| *p == pallet)?;
+				STAGES_BEFORE_PALLETS + position as u32
+			},
+		})
+	}
+
+	fn items_remaining(stage: &MigrationStageOf<T>) -> Option<u32> {
+		use pallet_nomination_pools as pools;
+
+		match stage {
+			MigrationStage::NomPoolsMigrationOngoing { .. } => Some(
+				[
+					pools::PoolMembers::<T>::count(),
+					pools::BondedPools::<T>::count(),
+					pools::RewardPools::<T>::count(),
+					pools::SubPoolsStorage::<T>::count(),
+					pools::Metadata::<T>::count(),
+					pools::ReversePoolIdLookup::<T>::count(),
+				]
+				.into_iter()
+				.fold(0u32, u32::saturating_add),
+			),
+			MigrationStage::BagsListMigrationOngoing { .. } =>
+				Some(pallet_bags_list::ListNodes::<T, pallet_bags_list::Instance1>::count()),
+			MigrationStage::FastUnstakeMigrationOngoing { .. } =>
+				Some(pallet_fast_unstake::Queue::<T>::count()),
+			_ => None,
+		}
+	}
+
+	fn estimate_blocks_remaining(
+		index: u32,
+		pallet_count: u32,
+		started_at: BlockNumberFor<T>,
+	) -> Option<u32> {
+		let last = STAGES_BEFORE_PALLETS + pallet_count;
+		let done = index.checked_sub(STAGES_BEFORE_PALLETS).filter(|done| *done > 0)?;
+		let elapsed: u32 = frame_system::Pallet::<T>::block_number()
+			.saturating_sub(started_at)
+			.saturated_into();
+
+		Some(
+			(elapsed as u64)
+				.saturating_mul(last.saturating_sub(index) as u64)
+				.saturating_div(done as u64)
+				.saturated_into(),
+		)
+	}
+}
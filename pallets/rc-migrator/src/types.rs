@@ -23,7 +23,10 @@ use alloc::string::String;
 use frame_support::traits::{tokens::IdAmount, ContainsPair};
 use pallet_referenda::{ReferendumInfoOf, TrackIdOf};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Zero, FixedU128};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	FixedU128,
+};
 use sp_std::collections::vec_deque::VecDeque;
 use xcm_builder::InspectMessageQueues;
 
@@ -150,6 +153,12 @@ pub enum AhMigratorCall<T: Config> {
 	#[cfg(feature = "kusama-ahm")]
 	#[codec(index = 27)]
 	ReceiveSocietyMessages { messages: Vec<society::PortableSocietyMessage> },
+	#[codec(index = 28)]
+	ReceiveMigrationDigest { pallet: sequence::MigratedPallet, digest: digest::MigrationDigest },
+	#[codec(index = 29)]
+	ReceiveFastUnstakeMessages { messages: Vec<fast_unstake::RcFastUnstakeRequestOf<T>> },
+	#[codec(index = 30)]
+	ReceiveParaReservesMessages { messages: Vec<para_reserves::RcParaReservesMessageOf<T>> },
 	#[codec(index = 101)]
 	StartMigration,
 	#[codec(index = 110)]
@@ -164,6 +173,9 @@ pub enum AhMigratorCall<T: Config> {
 }
 
 /// Further data coming from Relay Chain alongside the signal that migration has finished.
+///
+/// Adding `rc_totals` changed the encoding of [`AhMigratorCall::FinishMigration`], the Relay Chain
+/// and the Asset Hub must be upgraded together before the migration starts.
 #[derive(
 	Encode,
 	DecodeWithMemTracking,
@@ -176,9 +188,97 @@ pub enum AhMigratorCall<T: Config> {
 	PartialEq,
 	Eq,
 )]
-pub struct MigrationFinishedData<Balance> {
+pub struct MigrationFinishedData<Balance, HoldReason = PortableHoldReason> {
 	/// Total native token balance NOT migrated from Relay Chain
 	pub rc_balance_kept: Balance,
+	/// Total native token balance migrated from Relay Chain
+	pub rc_balance_migrated: Balance,
+	/// Totals of the holds and vesting schedules sent by the Relay Chain.
+	pub rc_totals: MigratedTotals<HoldReason, Balance>,
+}
+
+/// Maximum number of distinct hold reasons in [`MigratedTotals`].
+pub const MAX_MIGRATED_HOLD_REASONS: u32 = 32;
+
+/// Totals of the migrated holds and vesting schedules.
+///
+/// Tracked by the Relay Chain for the data that it sends and by the Asset Hub for the data that it
+/// integrates, in order to reconcile both at the end of the migration.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+)]
+pub struct MigratedTotals<HoldReason, Balance> {
+	/// Total free and reserved balance of the migrated accounts.
+	pub balance: Balance,
+	/// Total amount put on hold per hold reason.
+	pub holds: BoundedVec<(HoldReason, Balance), ConstU32<MAX_MIGRATED_HOLD_REASONS>>,
+	/// Total locked amount of the vesting schedules.
+	pub vesting_locked: Balance,
+}
+
+impl<HoldReason, Balance: Zero> Default for MigratedTotals<HoldReason, Balance> {
+	fn default() -> Self {
+		Self { balance: Zero::zero(), holds: BoundedVec::new(), vesting_locked: Zero::zero() }
+	}
+}
+
+impl<HoldReason: Clone + PartialEq + core::fmt::Debug, Balance: Saturating + Zero + Copy>
+	MigratedTotals<HoldReason, Balance>
+{
+	/// The total amount put on hold for `reason`.
+	pub fn held(&self, reason: &HoldReason) -> Balance {
+		self.holds
+			.iter()
+			.find(|(id, _)| id == reason)
+			.map_or_else(Zero::zero, |(_, amount)| *amount)
+	}
+
+	/// Add `amount` to the total of `reason`.
+	pub fn add_hold(&mut self, reason: &HoldReason, amount: Balance) {
+		if let Some((_, total)) = self.holds.iter_mut().find(|(id, _)| id == reason) {
+			total.saturating_accrue(amount);
+		} else if self.holds.try_push((reason.clone(), amount)).is_err() {
+			defensive!("Too many hold reasons to track {:?}", reason);
+		}
+	}
+
+	/// Add the totals of `other`.
+	pub fn merge(&mut self, other: &Self) {
+		self.balance.saturating_accrue(other.balance);
+		other.holds.iter().for_each(|(reason, amount)| self.add_hold(reason, *amount));
+		self.vesting_locked.saturating_accrue(other.vesting_locked);
+	}
+}
+
+/// Automatic retry state of a data message that the Asset Hub failed to process.
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct XcmRetry<BlockNumber> {
+	/// The query ID of the latest send of the message.
+	pub query_id: u64,
+	/// Number of automatic resends so far.
+	pub attempts: u32,
+	/// The block from which on the message is resent.
+	///
+	/// `None` while the response to the latest send is outstanding.
+	pub retry_at: Option<BlockNumber>,
 }
 
 /// Copy of `ParaInfo` type from `paras_registrar` pallet.
@@ -687,6 +787,97 @@ impl BenchmarkingDefault for PortableFreezeReason {
 	}
 }
 
+/// What to do with a hold or freeze whose reason has no mapping in a [`ReasonRegistry`].
+#[derive(
+	Encode,
+	DecodeWithMemTracking,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum UnmappedReasonPolicy {
+	/// Release the hold or thaw the freeze; the amount is migrated as free balance.
+	Release,
+	/// Migrate the amount as unnamed reserve.
+	ConvertToReserve,
+	/// Do not migrate the account.
+	///
+	/// The Relay Chain does not start the migration while an account has such a reason, so that
+	/// none of the account's state is migrated before the reason is mapped or removed. On the
+	/// Asset Hub, where the account is already withdrawn from the Relay Chain, it is treated like
+	/// [`Self::ConvertToReserve`].
+	Refuse,
+}
+
+/// Maps a hold or freeze reason into the reason of the next chain in the migration.
+///
+/// Used for both steps of a reason's journey: Relay Chain reason to portable reason on the Relay
+/// Chain, and portable reason to Asset Hub reason on the Asset Hub. A runtime configures a tuple of
+/// mappings, the first one returning `Some` wins.
+pub trait ReasonMapping<Reason, Target> {
+	/// The reason on the next chain or `None` if this mapping does not know `reason`.
+	fn map(reason: &Reason) -> Option<Target>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(16)]
+impl<Reason, Target> ReasonMapping<Reason, Target> for Tuple {
+	fn map(reason: &Reason) -> Option<Target> {
+		for_tuples!( #(
+			if let Some(target) = Tuple::map(reason) {
+				return Some(target);
+			}
+		)* );
+		None
+	}
+}
+
+/// Maps every reason with its [`IntoPortable`] implementation.
+pub struct ViaIntoPortable;
+impl<Reason: IntoPortable + Clone> ReasonMapping<Reason, Reason::Portable> for ViaIntoPortable {
+	fn map(reason: &Reason) -> Option<Reason::Portable> {
+		Some(reason.clone().into_portable())
+	}
+}
+
+/// Maps every reason with its [`From`] implementation.
+pub struct ViaFrom;
+impl<Reason: Clone, Target: From<Reason>> ReasonMapping<Reason, Target> for ViaFrom {
+	fn map(reason: &Reason) -> Option<Target> {
+		Some(reason.clone().into())
+	}
+}
+
+/// Translates hold or freeze reasons and decides what happens to the unmapped ones.
+pub trait ReasonRegistry<Reason> {
+	/// The translated reason.
+	type Target;
+
+	/// Translate `reason` or return the policy for it if it can not be translated.
+	fn translate(reason: &Reason) -> Result<Self::Target, UnmappedReasonPolicy>;
+}
+
+/// A [`ReasonRegistry`] built from `Mappings` with the `Fallback` policy for unmapped reasons.
+pub struct MappedReasons<Target, Mappings, Fallback>(
+	sp_std::marker::PhantomData<(Target, Mappings, Fallback)>,
+);
+impl<Reason, Target, Mappings, Fallback> ReasonRegistry<Reason>
+	for MappedReasons<Target, Mappings, Fallback>
+where
+	Mappings: ReasonMapping<Reason, Target>,
+	Fallback: Get<UnmappedReasonPolicy>,
+{
+	type Target = Target;
+
+	fn translate(reason: &Reason) -> Result<Target, UnmappedReasonPolicy> {
+		Mappings::map(reason).ok_or_else(Fallback::get)
+	}
+}
+
 #[cfg(test)]
 mod xcm_batch_tests {
 	use super::*;
@@ -941,3 +1132,57 @@ mod batch_and_meter_tests {
 		assert_eq!(meter.batch_count(), 2);
 	}
 }
+
+#[cfg(test)]
+mod reason_registry_tests {
+	use super::*;
+	use frame_support::parameter_types;
+
+	#[derive(Clone, Debug, PartialEq)]
+	enum Reason {
+		Mapped,
+		Renamed,
+		Unknown,
+	}
+
+	#[derive(Debug, PartialEq)]
+	enum Target {
+		Mapped,
+		Other,
+	}
+
+	struct Known;
+	impl ReasonMapping<Reason, Target> for Known {
+		fn map(reason: &Reason) -> Option<Target> {
+			(*reason == Reason::Mapped).then_some(Target::Mapped)
+		}
+	}
+
+	struct Renamed;
+	impl ReasonMapping<Reason, Target> for Renamed {
+		fn map(reason: &Reason) -> Option<Target> {
+			matches!(reason, Reason::Mapped | Reason::Renamed).then_some(Target::Other)
+		}
+	}
+
+	parameter_types! {
+		const Refuse: UnmappedReasonPolicy = UnmappedReasonPolicy::Refuse;
+	}
+
+	type Registry = MappedReasons<Target, (Known, Renamed), Refuse>;
+
+	#[test]
+	fn first_mapping_that_knows_the_reason_wins() {
+		assert_eq!(Registry::translate(&Reason::Mapped), Ok(Target::Mapped));
+		assert_eq!(Registry::translate(&Reason::Renamed), Ok(Target::Other));
+	}
+
+	#[test]
+	fn unmapped_reasons_get_the_fallback_policy() {
+		assert_eq!(Registry::translate(&Reason::Unknown), Err(UnmappedReasonPolicy::Refuse));
+		assert_eq!(
+			MappedReasons::<Target, Renamed, Refuse>::translate(&Reason::Unknown),
+			Err(UnmappedReasonPolicy::Refuse)
+		);
+	}
+}
@@ -18,6 +18,7 @@
 use crate::*;
 use frame_support::traits::Currency;
 use pallet_vesting::MaxVestingSchedulesGet;
+use sp_runtime::SaturatedConversion;
 use sp_std::vec::Vec;
 
 pub type BalanceOf<T> = <<T as pallet_vesting::Config>::Currency as Currency<
@@ -60,6 +61,12 @@ impl<T: Config> PalletMigration for VestingMigrator<T> {
 		let mut inner_key = current_key;
 		let mut messages = XcmBatchAndMeter::new_from_config::<T>();
 
+		// 1 read and 1 write of the `RcMigratedTotals` once the batch is assembled.
+		if weight_counter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return Err(Error::OutOfWeight);
+		}
+		let mut sent = types::MigratedTotals::default();
+
 		loop {
 			if weight_counter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() ||
 				weight_counter.try_consume(messages.consume_weight()).is_err()
@@ -100,11 +107,11 @@ impl<T: Config> PalletMigration for VestingMigrator<T> {
 				break;
 			}
 
-			if messages.batch_count() >= MAX_XCM_MSG_PER_BLOCK {
+			if messages.batch_count() >= max_xcm_msg_per_block::<T>() {
 				log::info!(
 					target: LOG_TARGET,
 					"Reached the maximum number of batches ({:?}) allowed per block; current batch count: {}",
-					MAX_XCM_MSG_PER_BLOCK,
+					max_xcm_msg_per_block::<T>(),
 					messages.batch_count()
 				);
 				break;
@@ -118,6 +125,10 @@ impl<T: Config> PalletMigration for VestingMigrator<T> {
 			match iter.next() {
 				Some((who, schedules)) => {
 					pallet_vesting::Vesting::<T>::remove(&who);
+					for schedule in schedules.iter() {
+						let locked: T::Balance = schedule.locked().saturated_into();
+						sent.vesting_locked.saturating_accrue(locked);
+					}
 					messages.push(RcVestingSchedule { who: who.clone(), schedules });
 					log::debug!(target: LOG_TARGET, "Migrating vesting schedules for {who:?}");
 					inner_key = Some(who);
@@ -133,6 +144,7 @@ impl<T: Config> PalletMigration for VestingMigrator<T> {
 			Pallet::<T>::send_chunked_xcm_and_track(messages, |messages| {
 				types::AhMigratorCall::ReceiveVestingSchedules { messages }
 			})?;
+			RcMigratedTotals::<T>::mutate(|totals| totals.merge(&sent));
 		}
 
 		Ok(inner_key)
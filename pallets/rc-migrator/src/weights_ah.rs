@@ -81,6 +81,13 @@ pub trait WeightInfo {
 	fn force_dmp_queue_priority() -> Weight;
 	fn set_dmp_queue_priority() -> Weight;
 	fn set_manager() -> Weight;
+	fn record_received_items(n: u32, ) -> Weight;
+	fn receive_fast_unstake_messages(n: u32, ) -> Weight;
+	fn receive_para_reserves_messages(n: u32, ) -> Weight;
+	fn preimage_gc_mark_referendum() -> Weight;
+	fn preimage_gc_mark_agenda(n: u32, ) -> Weight;
+	fn preimage_gc_sweep() -> Weight;
+	fn audit_account_components(n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_ah_migrator` using the Substrate node and recommended hardware.
@@ -110,6 +117,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:255 w:255)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::AhMigratedTotals` (r:1 w:1)
+	/// Proof: `AhMigrator::AhMigratedTotals` (`max_values`: Some(1), `max_size`: Some(609), added: 1104, mode: `MaxEncodedLen`)
 	/// The range of component `n` is `[1, 255]`.
 	fn receive_accounts(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -120,7 +129,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 990))
 			// Standard Error: 203_205
 			.saturating_add(Weight::from_parts(155_133_604, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
 			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 3774).saturating_mul(n.into()))
 	}
@@ -192,6 +203,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
 	/// Storage: `Vesting::StorageVersion` (r:0 w:1)
 	/// Proof: `Vesting::StorageVersion` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::AhMigratedTotals` (r:1 w:1)
+	/// Proof: `AhMigrator::AhMigratedTotals` (`max_values`: Some(1), `max_size`: Some(609), added: 1104, mode: `MaxEncodedLen`)
 	/// The range of component `n` is `[1, 255]`.
 	fn receive_vesting_schedules(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -202,8 +215,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 990))
 			// Standard Error: 10_198
 			.saturating_add(Weight::from_parts(7_255_747, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes(2))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 3532).saturating_mul(n.into()))
 	}
@@ -629,4 +643,139 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `AhMigrator::PalletIngestions` (r:1 w:1)
+	/// Proof: `AhMigrator::PalletIngestions` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::IngestingPallet` (r:1 w:1)
+	/// Proof: `AhMigrator::IngestingPallet` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::ReceivedMigrationDigests` (r:1 w:1)
+	/// Proof: `AhMigrator::ReceivedMigrationDigests` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::ExpectedMigrationDigests` (r:1 w:0)
+	/// Proof: `AhMigrator::ExpectedMigrationDigests` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::DigestMismatchHalt` (r:1 w:0)
+	/// Proof: `AhMigrator::DigestMismatchHalt` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 255]`.
+	fn record_received_items(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `3510`
+		// Minimum execution time: 21_870_000 picoseconds.
+		Weight::from_parts(19_912_441, 0)
+			.saturating_add(Weight::from_parts(0, 3510))
+			// Standard Error: 1_254
+			.saturating_add(Weight::from_parts(2_741_307, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `System::Account` (r:255 w:255)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 255]`.
+	fn receive_fast_unstake_messages(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `16771 + n * (537 ±0)`
+		//  Estimated: `990 + n * (2603 ±0)`
+		// Minimum execution time: 41_310_000 picoseconds.
+		Weight::from_parts(47_902_118, 0)
+			.saturating_add(Weight::from_parts(0, 990))
+			// Standard Error: 37_415
+			.saturating_add(Weight::from_parts(21_583_904, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: `AhOps::RcLeaseReserve` (r:255 w:255)
+	/// Proof: `AhOps::RcLeaseReserve` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// Storage: `AhOps::UnlockBlocks` (r:1 w:1)
+	/// Proof: `AhOps::UnlockBlocks` (`max_values`: Some(1), `max_size`: Some(257), added: 752, mode: `MaxEncodedLen`)
+	/// Storage: `AhOps::UnlockSchedule` (r:0 w:255)
+	/// Proof: `AhOps::UnlockSchedule` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 255]`.
+	fn receive_para_reserves_messages(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `143`
+		//  Estimated: `1742 + n * (2563 ±0)`
+		// Minimum execution time: 24_710_000 picoseconds.
+		Weight::from_parts(21_806_530, 0)
+			.saturating_add(Weight::from_parts(0, 1742))
+			// Standard Error: 8_911
+			.saturating_add(Weight::from_parts(9_418_266, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2563).saturating_mul(n.into()))
+	}
+	/// Storage: `Referenda::ReferendumInfoFor` (r:1 w:0)
+	/// Proof: `Referenda::ReferendumInfoFor` (`max_values`: None, `max_size`: Some(936), added: 3411, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::MigratedPreimages` (r:1 w:0)
+	/// Proof: `AhMigrator::MigratedPreimages` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::PreimageGcMarks` (r:0 w:1)
+	/// Proof: `AhMigrator::PreimageGcMarks` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	fn preimage_gc_mark_referendum() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `4401`
+		// Minimum execution time: 14_860_000 picoseconds.
+		Weight::from_parts(15_530_000, 0)
+			.saturating_add(Weight::from_parts(0, 4401))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Scheduler::Agenda` (r:1 w:0)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(38963), added: 41438, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::MigratedPreimages` (r:50 w:0)
+	/// Proof: `AhMigrator::MigratedPreimages` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::PreimageGcMarks` (r:0 w:50)
+	/// Proof: `AhMigrator::PreimageGcMarks` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 50]`.
+	fn preimage_gc_mark_agenda(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `87 + n * (187 ±0)`
+		//  Estimated: `42428 + n * (2508 ±0)`
+		// Minimum execution time: 6_120_000 picoseconds.
+		Weight::from_parts(7_034_512, 0)
+			.saturating_add(Weight::from_parts(0, 42428))
+			// Standard Error: 3_274
+			.saturating_add(Weight::from_parts(4_671_283, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(n.into()))
+	}
+	/// Storage: `AhMigrator::PreimageGcMarks` (r:1 w:1)
+	/// Proof: `AhMigrator::PreimageGcMarks` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `AhMigrator::MigratedPreimages` (r:1 w:1)
+	/// Proof: `AhMigrator::MigratedPreimages` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Proof: `Preimage::RequestStatusFor` (`max_values`: None, `max_size`: Some(91), added: 2566, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(247), added: 2722, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	/// Proof: `Preimage::PreimageFor` (`max_values`: None, `max_size`: Some(4194344), added: 4196819, mode: `Measured`)
+	fn preimage_gc_sweep() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `521`
+		//  Estimated: `3712`
+		// Minimum execution time: 61_950_000 picoseconds.
+		Weight::from_parts(64_210_000, 0)
+			.saturating_add(Weight::from_parts(0, 3712))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: `AhMigrator::AuditDigest` (r:1 w:1)
+	/// Proof: `AhMigrator::AuditDigest` (`max_values`: Some(1), `max_size`: Some(36), added: 531, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 255]`.
+	fn audit_account_components(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `1521`
+		// Minimum execution time: 9_480_000 picoseconds.
+		Weight::from_parts(8_917_352, 0)
+			.saturating_add(Weight::from_parts(0, 1521))
+			// Standard Error: 1_102
+			.saturating_add(Weight::from_parts(6_873_519, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
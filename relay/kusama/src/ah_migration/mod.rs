@@ -18,3 +18,6 @@
 
 pub mod phase1;
 pub mod weights;
+
+#[cfg(test)]
+mod tests;
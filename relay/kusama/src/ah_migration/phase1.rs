@@ -52,6 +52,31 @@ impl Get<RuntimeHoldReason> for StakingDelegationReason {
 	}
 }
 
+parameter_types! {
+	/// All hold and freeze reasons of the runtime are mapped, this only applies to reasons that
+	/// the mappings do not know.
+	pub const ReasonFallbackPolicy: pallet_rc_migrator::types::UnmappedReasonPolicy =
+		pallet_rc_migrator::types::UnmappedReasonPolicy::Refuse;
+}
+
+/// Translates the hold reasons of the migrated accounts with [`IntoPortable`].
+///
+/// [`IntoPortable`]: pallet_rc_migrator::types::IntoPortable
+pub type HoldReasonRegistry = pallet_rc_migrator::types::MappedReasons<
+	pallet_rc_migrator::types::PortableHoldReason,
+	pallet_rc_migrator::types::ViaIntoPortable,
+	ReasonFallbackPolicy,
+>;
+
+/// Translates the freeze reasons of the migrated accounts with [`IntoPortable`].
+///
+/// [`IntoPortable`]: pallet_rc_migrator::types::IntoPortable
+pub type FreezeReasonRegistry = pallet_rc_migrator::types::MappedReasons<
+	PortableFreezeReason,
+	pallet_rc_migrator::types::ViaIntoPortable,
+	ReasonFallbackPolicy,
+>;
+
 /// Return whether a call should be enabled during and/or after the migration.
 ///
 /// Time line of the migration looks like this:
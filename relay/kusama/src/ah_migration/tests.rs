@@ -0,0 +1,935 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests of the Relay Chain side of the Asset Hub Migration.
+
+use crate::*;
+use kusama_runtime_constants::system_parachain::ASSET_HUB_ID;
+use pallet_rc_migrator::{
+	sequence::{MigratedPallet, MigrationSequence},
+	MigrationStage, PendingXcmMessages, PendingXcmQueries, PendingXcmRetries, RcMigrationStage,
+	SkippedPallets, XcmResponseDeadlines,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use xcm::latest::{MaybeErrorCode, Response};
+use pallet_rc_migrator::Error;
+use sp_runtime::BuildStorage;
+
+pub(super) const ALICE: AccountId = AccountId::new([1; 32]);
+pub(super) const BOB: AccountId = AccountId::new([2; 32]);
+
+/// Externalities with funded accounts in which messages to the Asset Hub can be sent.
+pub(super) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, 100 * UNITS), (BOB, 100 * UNITS)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let mut config = parachains_configuration::HostConfiguration::default();
+		config.max_downward_message_size = 1024 * 1024;
+		parachains_configuration::Pallet::<Runtime>::force_set_active_config(config);
+		parachains_dmp::Pallet::<Runtime>::make_parachain_reachable(ASSET_HUB_ID);
+	});
+	ext
+}
+
+#[test]
+fn dry_run_reports_sent_messages_without_persisting_them() {
+	new_test_ext().execute_with(|| {
+		let report =
+			RcMigrator::dry_run_migration(Some(MigrationStage::AccountsMigrationInit), 3);
+
+		assert_eq!(report.blocks, 3);
+		let accounts = report
+			.stages
+			.iter()
+			.find(|stage| {
+				matches!(stage.stage, MigrationStage::AccountsMigrationOngoing { .. })
+			})
+			.expect("the accounts are migrated");
+		assert!(accounts.xcm.items >= 2, "{accounts:?}");
+		assert!(accounts.xcm.messages >= 1, "{accounts:?}");
+		assert!(accounts.xcm.bytes > 0, "{accounts:?}");
+
+		// Nothing of the dry-run is persisted.
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::Pending);
+		assert_eq!(PendingXcmMessages::<Runtime>::iter().count(), 0);
+		assert_eq!(System::block_number(), 1);
+	});
+}
+
+#[test]
+fn migration_status_follows_the_migration_sequence() {
+	new_test_ext().execute_with(|| {
+		let pallets = <Runtime as pallet_rc_migrator::Config>::MigrationSequence::pallets();
+		let pallet_count = pallets.len() as u32;
+
+		let status = RcMigrator::migration_status();
+		assert_eq!(status.stage_index, Some(0));
+		assert_eq!(status.total_stages, 6 + pallet_count + 3);
+
+		// Every pallet is one stage, whichever of its stages is running.
+		RcMigrationStage::<Runtime>::put(MigrationStage::AccountsMigrationInit);
+		assert_eq!(RcMigrator::migration_status().stage_index, Some(6));
+		RcMigrationStage::<Runtime>::put(MigrationStage::AccountsMigrationDone);
+		assert_eq!(RcMigrator::migration_status().stage_index, Some(6));
+		let multisig = MigrationStage::MultisigMigrationOngoing { last_key: None };
+		RcMigrationStage::<Runtime>::put(multisig);
+		assert_eq!(RcMigrator::migration_status().stage_index, Some(7));
+		RcMigrationStage::<Runtime>::put(MigrationStage::MigrationDone);
+		assert_eq!(RcMigrator::migration_status().stage_index, Some(6 + pallet_count + 2));
+
+		// Skipped pallets are not part of the sequence.
+		SkippedPallets::<Runtime>::insert(MigratedPallet::Multisig, 1);
+		RcMigrationStage::<Runtime>::put(MigrationStage::ClaimsMigrationInit);
+		let status = RcMigrator::migration_status();
+		assert_eq!(status.stage_index, Some(7));
+		assert_eq!(status.total_stages, 6 + pallet_count - 1 + 3);
+	});
+}
+
+#[test]
+fn migration_status_estimates_the_remaining_blocks() {
+	new_test_ext().execute_with(|| {
+		let pallet_count =
+			<Runtime as pallet_rc_migrator::Config>::MigrationSequence::pallets().len() as u32;
+		pallet_rc_migrator::MigrationStartBlock::<Runtime>::put(1);
+		System::set_block_number(101);
+
+		// No estimate before the first pallet is done.
+		RcMigrationStage::<Runtime>::put(MigrationStage::AccountsMigrationInit);
+		assert_eq!(RcMigrator::migration_status().estimated_blocks_remaining, None);
+
+		// One pallet took 100 blocks.
+		RcMigrationStage::<Runtime>::put(MigrationStage::MultisigMigrationInit);
+		assert_eq!(
+			RcMigrator::migration_status().estimated_blocks_remaining,
+			Some(100 * (pallet_count - 1))
+		);
+	});
+}
+
+#[test]
+fn migration_status_counts_pending_queries() {
+	new_test_ext().execute_with(|| {
+		PendingXcmQueries::<Runtime>::insert(1, H256::repeat_byte(1));
+		PendingXcmQueries::<Runtime>::insert(2, H256::repeat_byte(2));
+		assert_eq!(RcMigrator::migration_status().pending_xcm_queries, 2);
+
+		PendingXcmQueries::<Runtime>::remove(1);
+		assert_eq!(RcMigrator::migration_status().pending_xcm_queries, 1);
+	});
+}
+
+#[test]
+fn migration_status_reports_items_of_counted_maps() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::FastUnstakeMigrationOngoing {
+			last_key: None,
+		});
+		pallet_fast_unstake::Queue::<Runtime>::insert(ALICE, UNITS);
+		pallet_fast_unstake::Queue::<Runtime>::insert(BOB, UNITS);
+		pallet_rc_migrator::StageMigratedItems::<Runtime>::put(3);
+
+		let status = RcMigrator::migration_status();
+		assert_eq!(status.items_migrated, 3);
+		assert_eq!(status.items_remaining, Some(2));
+		assert_eq!(status.items_total, Some(5));
+	});
+}
+
+#[test]
+fn skip_pallet_migration_skips_pallets_still_to_come() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::MultisigMigrationOngoing {
+			last_key: None,
+		});
+
+		assert_ok!(RcMigrator::skip_pallet_migration(
+			RuntimeOrigin::root(),
+			MigratedPallet::Claims
+		));
+
+		assert!(SkippedPallets::<Runtime>::contains_key(MigratedPallet::Claims));
+		assert_eq!(
+			RcMigrator::next_pallet_stage(Some(MigratedPallet::Multisig)),
+			MigrationStage::ProxyMigrationInit
+		);
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Claims),
+			Error::<Runtime>::PalletAlreadySkipped
+		);
+	});
+}
+
+#[test]
+fn skip_pallet_migration_rejects_mandatory_and_started_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Accounts),
+			Error::<Runtime>::PalletMandatory
+		);
+
+		// The running pallet, also while the migration is paused.
+		RcMigrationStage::<Runtime>::put(MigrationStage::VestingMigrationOngoing {
+			next_key: None,
+		});
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Vesting),
+			Error::<Runtime>::PalletMigrationStarted
+		);
+		pallet_rc_migrator::PausedStage::<Runtime>::put(RcMigrationStage::<Runtime>::get());
+		RcMigrationStage::<Runtime>::put(MigrationStage::MigrationPaused);
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Vesting),
+			Error::<Runtime>::PalletMigrationStarted
+		);
+
+		// An already migrated pallet.
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Proxy),
+			Error::<Runtime>::PalletMigrationStarted
+		);
+
+		// After the data migration.
+		RcMigrationStage::<Runtime>::put(MigrationStage::MigrationDone);
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Staking),
+			Error::<Runtime>::PalletMigrationStarted
+		);
+	});
+}
+
+#[test]
+fn skip_pallet_migration_rejects_dependencies_of_remaining_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RcMigrator::skip_pallet_migration(RuntimeOrigin::root(), MigratedPallet::Bounties),
+			Error::<Runtime>::PalletIsDependency
+		);
+
+		// Once the dependent pallet is skipped, its dependency can be skipped too.
+		assert_ok!(RcMigrator::skip_pallet_migration(
+			RuntimeOrigin::root(),
+			MigratedPallet::ChildBounties
+		));
+		assert_ok!(RcMigrator::skip_pallet_migration(
+			RuntimeOrigin::root(),
+			MigratedPallet::Bounties
+		));
+	});
+}
+
+/// Track `message` as sent to the Asset Hub under `query_id` and return its hash.
+fn track_message(query_id: u64, message: u8) -> H256 {
+	let message_hash = H256::repeat_byte(message);
+	PendingXcmMessages::<Runtime>::insert((query_id, message_hash), xcm::latest::Xcm(vec![]));
+	PendingXcmQueries::<Runtime>::insert(query_id, message_hash);
+	XcmResponseDeadlines::<Runtime>::append(
+		System::block_number() + XcmResponseTimeout::get(),
+		query_id,
+	);
+	message_hash
+}
+
+fn respond(query_id: u64, response: MaybeErrorCode) {
+	assert_ok!(RcMigrator::receive_query_response(
+		RuntimeOrigin::root(),
+		query_id,
+		Response::DispatchResult(response)
+	));
+}
+
+fn run_to_block(n: BlockNumber) {
+	System::set_block_number(n);
+	RcMigrator::on_initialize(n);
+}
+
+fn failure() -> MaybeErrorCode {
+	MaybeErrorCode::Error(Default::default())
+}
+
+#[test]
+fn messages_without_response_are_not_resent() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+
+		run_to_block(1 + 100 * XcmRetryInterval::get());
+
+		assert_eq!(PendingXcmQueries::<Runtime>::get(1_000), Some(message_hash));
+		assert!(PendingXcmRetries::<Runtime>::get(message_hash).is_none());
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::WaitingForAh);
+
+		// Once the response timed out the migration is paused, still without resending.
+		run_to_block(1 + XcmResponseTimeout::get());
+
+		System::assert_has_event(
+			pallet_rc_migrator::Event::<Runtime>::XcmResponseTimedOut { query_id: 1_000 }.into(),
+		);
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::MigrationPaused);
+		assert_eq!(
+			pallet_rc_migrator::PausedStage::<Runtime>::get(),
+			Some(MigrationStage::WaitingForAh)
+		);
+		assert_eq!(PendingXcmQueries::<Runtime>::get(1_000), Some(message_hash));
+		assert!(PendingXcmRetries::<Runtime>::get(message_hash).is_none());
+	});
+}
+
+#[test]
+fn answered_messages_do_not_time_out() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		track_message(1_000, 1);
+		let failed_hash = track_message(1_001, 2);
+
+		respond(1_000, MaybeErrorCode::Success);
+		// Failed and waiting for its automatic resend.
+		respond(1_001, failure());
+		PendingXcmRetries::<Runtime>::mutate(failed_hash, |retry| {
+			retry.as_mut().unwrap().retry_at = Some(2 * XcmResponseTimeout::get());
+		});
+
+		run_to_block(1 + XcmResponseTimeout::get());
+
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::WaitingForAh);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::RcMigrator(
+				pallet_rc_migrator::Event::<Runtime>::XcmResponseTimedOut { .. }
+			)
+		)));
+		assert!(!XcmResponseDeadlines::<Runtime>::contains_key(1 + XcmResponseTimeout::get()));
+	});
+}
+
+#[test]
+fn failed_messages_are_resent_after_the_retry_interval() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+
+		respond(1_000, failure());
+		let retry = PendingXcmRetries::<Runtime>::get(message_hash).unwrap();
+		assert_eq!((retry.query_id, retry.attempts), (1_000, 0));
+		assert_eq!(retry.retry_at, Some(1 + XcmRetryInterval::get()));
+
+		// Not due yet.
+		run_to_block(XcmRetryInterval::get());
+		assert_eq!(PendingXcmQueries::<Runtime>::get(1_000), Some(message_hash));
+
+		run_to_block(1 + XcmRetryInterval::get());
+		let retry = PendingXcmRetries::<Runtime>::get(message_hash).unwrap();
+		assert_ne!(retry.query_id, 1_000);
+		assert_eq!((retry.attempts, retry.retry_at), (1, None));
+		assert!(PendingXcmQueries::<Runtime>::get(1_000).is_none());
+		assert!(PendingXcmMessages::<Runtime>::contains_key((retry.query_id, message_hash)));
+
+		// The resent message is confirmed.
+		respond(retry.query_id, MaybeErrorCode::Success);
+		assert!(PendingXcmRetries::<Runtime>::get(message_hash).is_none());
+		assert_eq!(PendingXcmMessages::<Runtime>::iter().count(), 0);
+		System::assert_last_event(
+			pallet_rc_migrator::Event::<Runtime>::QueryResponseReceived {
+				query_id: retry.query_id,
+				response: MaybeErrorCode::Success,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn late_response_to_a_manually_resent_message_confirms_it() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+
+		assert_ok!(RcMigrator::resend_xcm(RuntimeOrigin::root(), 1_000));
+		let retry = PendingXcmRetries::<Runtime>::get(message_hash).unwrap();
+		assert_ne!(retry.query_id, 1_000);
+		assert_eq!(retry.attempts, 0);
+		// The old query is kept.
+		assert_eq!(PendingXcmQueries::<Runtime>::get(1_000), Some(message_hash));
+
+		respond(1_000, MaybeErrorCode::Success);
+		assert_eq!(PendingXcmMessages::<Runtime>::iter().count(), 0);
+		assert_eq!(PendingXcmQueries::<Runtime>::iter().count(), 0);
+		assert!(PendingXcmRetries::<Runtime>::get(message_hash).is_none());
+	});
+}
+
+#[test]
+fn failed_response_to_a_superseded_query_is_ignored() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+
+		assert_ok!(RcMigrator::resend_xcm(RuntimeOrigin::root(), 1_000));
+		respond(1_000, failure());
+
+		// The resent message is still awaiting its response.
+		assert_eq!(PendingXcmRetries::<Runtime>::get(message_hash).unwrap().retry_at, None);
+	});
+}
+
+#[test]
+fn migration_pauses_when_a_message_keeps_failing() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+
+		let mut query_id = 1_000;
+		for attempt in 1..=<Runtime as pallet_rc_migrator::Config>::MaxXcmRetries::get() {
+			respond(query_id, failure());
+			let retry_at = PendingXcmRetries::<Runtime>::get(message_hash).unwrap().retry_at;
+			run_to_block(retry_at.unwrap());
+
+			let retry = PendingXcmRetries::<Runtime>::get(message_hash).unwrap();
+			assert_eq!(retry.attempts, attempt);
+			query_id = retry.query_id;
+		}
+
+		respond(query_id, failure());
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::MigrationPaused);
+		assert!(PendingXcmRetries::<Runtime>::get(message_hash).is_none());
+		// The message can still be resent manually.
+		assert_eq!(PendingXcmQueries::<Runtime>::get(query_id), Some(message_hash));
+	});
+}
+
+#[test]
+fn resume_migration_restores_the_paused_stage_and_cursor() {
+	new_test_ext().execute_with(|| {
+		let stage = MigrationStage::MultisigMigrationOngoing { last_key: Some((ALICE, [7; 32])) };
+		RcMigrationStage::<Runtime>::put(stage.clone());
+		assert_noop!(
+			RcMigrator::resume_migration(RuntimeOrigin::root(), false),
+			Error::<Runtime>::NotPaused
+		);
+
+		assert_ok!(RcMigrator::pause_migration(RuntimeOrigin::root()));
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::MigrationPaused);
+		assert_eq!(pallet_rc_migrator::PausedStage::<Runtime>::get(), Some(stage.clone()));
+		assert_noop!(
+			RcMigrator::pause_migration(RuntimeOrigin::root()),
+			Error::<Runtime>::InvalidStageTransition
+		);
+
+		assert_ok!(RcMigrator::resume_migration(RuntimeOrigin::root(), false));
+		assert_eq!(RcMigrationStage::<Runtime>::get(), stage.clone());
+		assert!(pallet_rc_migrator::PausedStage::<Runtime>::get().is_none());
+		System::assert_last_event(
+			pallet_rc_migrator::Event::<Runtime>::MigrationResumed {
+				resume_stage: stage,
+				pending_xcm_messages: 0,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn resume_migration_with_unconfirmed_messages_requires_force() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::WaitingForAh);
+		let message_hash = track_message(1_000, 1);
+		assert_ok!(RcMigrator::pause_migration(RuntimeOrigin::root()));
+
+		assert_noop!(
+			RcMigrator::resume_migration(RuntimeOrigin::root(), false),
+			Error::<Runtime>::PendingXcmMessagesOnResume
+		);
+
+		assert_ok!(RcMigrator::resume_migration(RuntimeOrigin::root(), true));
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::WaitingForAh);
+		// The unconfirmed message stays tracked.
+		assert_eq!(PendingXcmQueries::<Runtime>::get(1_000), Some(message_hash));
+		System::assert_last_event(
+			pallet_rc_migrator::Event::<Runtime>::MigrationResumed {
+				resume_stage: MigrationStage::WaitingForAh,
+				pending_xcm_messages: 1,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn force_set_stage_out_of_a_pause_clears_the_paused_stage() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::AccountsMigrationInit);
+		assert_ok!(RcMigrator::pause_migration(RuntimeOrigin::root()));
+		assert!(pallet_rc_migrator::PausedStage::<Runtime>::get().is_some());
+
+		assert_ok!(RcMigrator::force_set_stage(
+			RuntimeOrigin::root(),
+			Box::new(MigrationStage::ProxyMigrationInit)
+		));
+
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::ProxyMigrationInit);
+		assert!(pallet_rc_migrator::PausedStage::<Runtime>::get().is_none());
+		assert_noop!(
+			RcMigrator::resume_migration(RuntimeOrigin::root(), true),
+			Error::<Runtime>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn automatic_pauses_can_be_resumed() {
+	new_test_ext().execute_with(|| {
+		// Paused after a message kept failing.
+		let stage = MigrationStage::ProxyMigrationProxies { last_key: Some(BOB) };
+		RcMigrationStage::<Runtime>::put(stage.clone());
+		let message_hash = track_message(1_000, 1);
+		PendingXcmRetries::<Runtime>::insert(
+			message_hash,
+			pallet_rc_migrator::types::XcmRetry {
+				query_id: 1_000,
+				attempts: <Runtime as pallet_rc_migrator::Config>::MaxXcmRetries::get(),
+				retry_at: None,
+			},
+		);
+		respond(1_000, failure());
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::MigrationPaused);
+
+		assert_ok!(RcMigrator::resume_migration(RuntimeOrigin::root(), true));
+		assert_eq!(RcMigrationStage::<Runtime>::get(), stage);
+
+		// Paused after a response timed out.
+		run_to_block(1 + XcmResponseTimeout::get());
+		assert_eq!(RcMigrationStage::<Runtime>::get(), MigrationStage::MigrationPaused);
+		respond(1_000, MaybeErrorCode::Success);
+
+		// All messages are confirmed now, no `force` is needed.
+		assert_ok!(RcMigrator::resume_migration(RuntimeOrigin::root(), false));
+		assert_eq!(RcMigrationStage::<Runtime>::get(), stage);
+	});
+}
+
+#[test]
+fn expired_manager_proposals_are_pruned() {
+	use pallet_rc_migrator::{manager_multisig::ManagerMultisigProposal, ManagerMultisigs};
+	use sp_runtime::traits::Hash;
+
+	new_test_ext().execute_with(|| {
+		let expired = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let open = RuntimeCall::System(frame_system::Call::remark { remark: vec![2] });
+		ManagerMultisigs::<Runtime>::insert(&expired, ManagerMultisigProposal::new(5));
+		ManagerMultisigs::<Runtime>::insert(&open, ManagerMultisigProposal::new(6));
+
+		System::set_block_number(5);
+		RcMigrator::on_idle(5, Weight::MAX);
+
+		assert!(!ManagerMultisigs::<Runtime>::contains_key(&expired));
+		assert!(ManagerMultisigs::<Runtime>::contains_key(&open));
+		System::assert_has_event(
+			pallet_rc_migrator::Event::<Runtime>::ManagerMultisigProposalExpired {
+				call_hash: <Runtime as frame_system::Config>::Hashing::hash_of(&expired),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn migration_drops_manager_votes_without_expiry() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	use pallet_rc_migrator::{
+		migrations::{v2, MigrateToV2},
+		ManagerMultisigRound, ManagerMultisigs, ManagerVotesInCurrentRound,
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<RcMigrator>();
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		v2::ManagerMultisigs::<Runtime>::insert(&call, vec![ALICE]);
+		ManagerVotesInCurrentRound::<Runtime>::insert(ALICE, 1);
+		ManagerMultisigRound::<Runtime>::put(100);
+
+		MigrateToV2::<Runtime>::on_runtime_upgrade();
+
+		assert!(ManagerMultisigs::<Runtime>::iter_keys().next().is_none());
+		assert_eq!(ManagerVotesInCurrentRound::<Runtime>::get(ALICE), 0);
+		assert_eq!(ManagerMultisigRound::<Runtime>::get(), 101);
+		assert_eq!(StorageVersion::get::<RcMigrator>(), 2);
+	});
+}
+
+#[test]
+fn set_settings_rejects_zero_limits() {
+	use pallet_rc_migrator::{MigrationSettings, StageLimits};
+
+	new_test_ext().execute_with(|| {
+		let settings = |max_items_per_block, max_xcm_msg_per_block| MigrationSettings {
+			max_accounts_per_block: None,
+			max_items_per_block: None,
+			stage_limits: vec![(
+				MigratedPallet::Proxy,
+				StageLimits { max_items_per_block, max_xcm_msg_per_block, weight_fraction: None },
+			)]
+			.try_into()
+			.unwrap(),
+		};
+
+		assert_noop!(
+			RcMigrator::set_settings(RuntimeOrigin::root(), Some(settings(Some(0), None))),
+			Error::<Runtime>::InvalidParameter
+		);
+		assert_noop!(
+			RcMigrator::set_settings(RuntimeOrigin::root(), Some(settings(None, Some(0)))),
+			Error::<Runtime>::InvalidParameter
+		);
+		assert_noop!(
+			RcMigrator::set_settings(
+				RuntimeOrigin::root(),
+				Some(MigrationSettings { max_items_per_block: Some(0), ..settings(None, None) })
+			),
+			Error::<Runtime>::InvalidParameter
+		);
+		assert_ok!(RcMigrator::set_settings(
+			RuntimeOrigin::root(),
+			Some(settings(Some(1), Some(1)))
+		));
+	});
+}
+
+#[test]
+fn limits_are_read_once_per_block() {
+	use pallet_rc_migrator::{
+		max_items_per_block, BlockLimits, MigrationSettings, Settings, MAX_ITEMS_PER_BLOCK,
+	};
+
+	new_test_ext().execute_with(|| {
+		let settings = |max_items_per_block| MigrationSettings {
+			max_accounts_per_block: None,
+			max_items_per_block: Some(max_items_per_block),
+			stage_limits: Default::default(),
+		};
+		Settings::<Runtime>::put(settings(10));
+
+		RcMigrator::on_initialize(1);
+		assert_eq!(max_items_per_block::<Runtime>(), 10);
+
+		// Changes apply from the next block on.
+		Settings::<Runtime>::put(settings(20));
+		assert_eq!(max_items_per_block::<Runtime>(), 10);
+
+		RcMigrator::on_finalize(1);
+		assert!(BlockLimits::<Runtime>::get().is_none());
+		assert_eq!(max_items_per_block::<Runtime>(), 20);
+
+		Settings::<Runtime>::kill();
+		assert_eq!(max_items_per_block::<Runtime>(), MAX_ITEMS_PER_BLOCK);
+	});
+}
+
+#[test]
+fn migration_adds_stage_limits_to_the_settings() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	use pallet_rc_migrator::{
+		migrations::{v3::OldMigrationSettings, MigrateToV3},
+		MigrationSettings, Settings,
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<RcMigrator>();
+		let old =
+			OldMigrationSettings { max_accounts_per_block: Some(5), max_items_per_block: Some(7) };
+		frame_support::storage::unhashed::put_raw(
+			&Settings::<Runtime>::hashed_key(),
+			&old.encode(),
+		);
+
+		MigrateToV3::<Runtime>::on_runtime_upgrade();
+
+		assert_eq!(
+			Settings::<Runtime>::get(),
+			Some(MigrationSettings {
+				max_accounts_per_block: Some(5),
+				max_items_per_block: Some(7),
+				stage_limits: Default::default(),
+			})
+		);
+		assert_eq!(StorageVersion::get::<RcMigrator>(), 3);
+	});
+}
+
+#[test]
+fn fast_unstake_deposits_of_preserved_accounts_are_unreserved_on_the_relay_chain() {
+	use frame_support::{traits::ReservableCurrency, weights::WeightMeter};
+	use pallet_rc_migrator::{
+		accounts::AccountState, fast_unstake::FastUnstakeMigrator, types::PalletMigration,
+		RcAccounts,
+	};
+
+	new_test_ext().execute_with(|| {
+		let deposit = <Runtime as pallet_fast_unstake::Config>::Deposit::get();
+		for who in [&ALICE, &BOB] {
+			assert_ok!(Balances::reserve(who, deposit));
+			pallet_fast_unstake::Queue::<Runtime>::insert(who, deposit);
+		}
+		RcAccounts::<Runtime>::insert(&ALICE, AccountState::Preserve);
+
+		let mut meter = WeightMeter::new();
+		assert_eq!(FastUnstakeMigrator::<Runtime>::migrate_many(None, &mut meter), Ok(None));
+
+		assert_eq!(pallet_fast_unstake::Queue::<Runtime>::count(), 0);
+		assert_eq!(pallet_fast_unstake::ErasToCheckPerBlock::<Runtime>::get(), 0);
+		// ALICE is preserved on the Relay Chain, so the deposit is released here.
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		// The deposit of BOB moved to the Asset Hub with the account and is released there.
+		assert_eq!(Balances::reserved_balance(&BOB), deposit);
+		assert_eq!(PendingXcmMessages::<Runtime>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn registration_deposits_migrated_with_the_manager_are_released_on_asset_hub() {
+	use frame_support::{traits::ReservableCurrency, weights::WeightMeter};
+	use pallet_rc_migrator::{
+		para_reserves::{ParaReservesMigrator, ParaReservesStage},
+		types::{PalletMigration, ParaInfo},
+		Paras,
+	};
+
+	new_test_ext().execute_with(|| {
+		let deposit = 10 * UNITS;
+		// The deposit of ALICE was migrated with the account, BOB still has it reserved.
+		Paras::<Runtime>::insert(
+			ParaId::from(2000),
+			ParaInfo { manager: ALICE, deposit, locked: None },
+		);
+		assert_ok!(Balances::reserve(&BOB, deposit));
+		Paras::<Runtime>::insert(
+			ParaId::from(2001),
+			ParaInfo { manager: BOB, deposit, locked: None },
+		);
+
+		let mut meter = WeightMeter::new();
+		assert_eq!(
+			ParaReservesMigrator::<Runtime>::migrate_many(
+				Some(ParaReservesStage::RegistrarDeposits { last_key: None }),
+				&mut meter,
+			),
+			Ok(None)
+		);
+
+		assert_eq!(Paras::<Runtime>::get(ParaId::from(2000)).unwrap().deposit, 0);
+		assert_eq!(Paras::<Runtime>::get(ParaId::from(2001)).unwrap().deposit, deposit);
+		assert_eq!(Balances::reserved_balance(&BOB), deposit);
+		assert_eq!(PendingXcmMessages::<Runtime>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn account_preview_translates_only_sovereign_accounts_in_the_tables() {
+	use pallet_rc_migrator::accounts::{sovereign_para_id, AccountsMigrator};
+
+	new_test_ext().execute_with(|| {
+		let listed: AccountId = ParaId::from(2048).into_account_truncating();
+		let preview = AccountsMigrator::<Runtime>::preview_account(listed.clone());
+		assert_eq!(preview.sovereign_of, Some(ParaId::from(2048)));
+		let mut sibling = [0u8; 32];
+		sibling[..4].copy_from_slice(b"sibl");
+		sibling[4..8].copy_from_slice(&2048u32.to_le_bytes());
+		assert_eq!(preview.ah_account, AccountId::new(sibling));
+
+		// Para ids beyond `u16` are recognized, but not in the tables, so not translated.
+		let unlisted: AccountId = ParaId::from(100_000).into_account_truncating();
+		assert_eq!(sovereign_para_id(&unlisted), Some(ParaId::from(100_000)));
+		let preview = AccountsMigrator::<Runtime>::preview_account(unlisted.clone());
+		assert_eq!(preview.sovereign_of, Some(ParaId::from(100_000)));
+		assert_eq!(preview.ah_account, unlisted);
+
+		assert_eq!(sovereign_para_id(&ALICE), None);
+		assert_eq!(AccountsMigrator::<Runtime>::preview_account(ALICE).ah_account, ALICE);
+	});
+}
+
+#[test]
+fn account_preview_keeps_hrmp_and_registration_deposits_on_the_relay_chain() {
+	use frame_support::traits::{Currency, ReservableCurrency};
+	use pallet_rc_migrator::{
+		accounts::{AccountMigrationKind, AccountsMigrator},
+		types::ParaInfo,
+		Paras,
+	};
+	use polkadot_primitives::HrmpChannelId;
+
+	new_test_ext().execute_with(|| {
+		let (para_id, recipient) = (ParaId::from(2001), ParaId::from(2002));
+		let sovereign: AccountId = para_id.into_account_truncating();
+		let deposit = 10 * UNITS;
+		Balances::make_free_balance_be(&sovereign, 100 * UNITS);
+		assert_ok!(Balances::reserve(&sovereign, deposit));
+		parachains_hrmp::HrmpChannels::<Runtime>::insert(
+			HrmpChannelId { sender: para_id, recipient },
+			parachains_hrmp::HrmpChannel {
+				max_capacity: 1,
+				max_total_size: 1,
+				max_message_size: 1,
+				msg_count: 0,
+				total_size: 0,
+				mqc_head: None,
+				sender_deposit: deposit,
+				recipient_deposit: 0,
+			},
+		);
+		parachains_hrmp::HrmpEgressChannelsIndex::<Runtime>::insert(para_id, vec![recipient]);
+
+		let preview = AccountsMigrator::<Runtime>::preview_account(sovereign);
+		assert_eq!(preview.kind, AccountMigrationKind::PartiallyMigrated);
+		assert_eq!(preview.rc_reserved, deposit);
+		assert_eq!(preview.migrated_reserved, 0);
+
+		// The registration deposit of a manager stays on the Relay Chain as well.
+		assert_ok!(Balances::reserve(&ALICE, deposit));
+		Paras::<Runtime>::insert(
+			ParaId::from(2003),
+			ParaInfo { manager: ALICE, deposit, locked: None },
+		);
+		let preview = AccountsMigrator::<Runtime>::preview_account(ALICE);
+		assert_eq!(preview.kind, AccountMigrationKind::PartiallyMigrated);
+		assert_eq!(preview.rc_reserved, deposit);
+
+		// Without a reserved balance, nothing stays on the Relay Chain.
+		let preview = AccountsMigrator::<Runtime>::preview_account(BOB);
+		assert_eq!(preview.kind, AccountMigrationKind::Migrated);
+		assert_eq!(preview.rc_reserved, 0);
+	});
+}
+
+#[test]
+fn migration_digests_are_tracked_and_resent() {
+	new_test_ext().execute_with(|| {
+		RcMigrationStage::<Runtime>::put(MigrationStage::MultisigMigrationDone);
+		RcMigrator::complete_pallet_migration(MigratedPallet::Multisig);
+
+		let ((query_id, message_hash), _) =
+			PendingXcmMessages::<Runtime>::iter().next().expect("the digest is tracked");
+		assert_eq!(PendingXcmQueries::<Runtime>::get(query_id), Some(message_hash));
+
+		// A lost digest is resent like a batch.
+		respond(query_id, failure());
+		run_to_block(1 + XcmRetryInterval::get());
+		let retry = PendingXcmRetries::<Runtime>::get(message_hash).unwrap();
+		assert_ne!(retry.query_id, query_id);
+		assert!(PendingXcmMessages::<Runtime>::contains_key((retry.query_id, message_hash)));
+	});
+}
+
+#[test]
+fn sent_messages_are_recorded_in_the_message_log() {
+	use pallet_rc_migrator::{
+		message_log::{read_message_log, MessageRecorder},
+		types::{AhMigratorCall, AssetHubPalletConfig},
+	};
+
+	let path = std::env::temp_dir().join(format!("rc_message_log_{}.ahmlog", std::process::id()));
+	new_test_ext().execute_with(|| {
+		let mut recorder = MessageRecorder::<Runtime>::start(&path).unwrap();
+		// An untracked message and a tracked one.
+		assert_ok!(RcMigrator::send_xcm(AhMigratorCall::StartMigration));
+		RcMigrationStage::<Runtime>::put(MigrationStage::MultisigMigrationDone);
+		RcMigrator::complete_pallet_migration(MigratedPallet::Multisig);
+		recorder.record_block().unwrap();
+
+		// The digest is resent after it failed on the Asset Hub.
+		let (query_id, _) = PendingXcmQueries::<Runtime>::iter().next().unwrap();
+		respond(query_id, failure());
+		System::reset_events();
+		run_to_block(1 + XcmRetryInterval::get());
+		recorder.record_block().unwrap();
+		recorder.finish().unwrap();
+	});
+
+	let messages = read_message_log(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+	let start = AssetHubPalletConfig::<Runtime>::AhmController(AhMigratorCall::StartMigration);
+	assert_eq!(messages[0].rc_block, 1);
+	assert_eq!(messages[0].call, start.encode());
+	// The pending digest is recorded once per send.
+	let digest = &messages[1];
+	let sent_at = messages
+		.iter()
+		.filter(|message| message.call == digest.call)
+		.map(|message| message.rc_block)
+		.collect::<Vec<_>>();
+	assert_eq!(sent_at, vec![1, 1 + XcmRetryInterval::get()]);
+}
+
+#[test]
+fn withdrawn_manager_votes_can_not_be_replayed() {
+	use pallet_rc_migrator::{
+		ManagerMultisigRound, ManagerMultisigVote, ManagerMultisigWithdrawal, ManagerMultisigs,
+	};
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::{
+		traits::{IdentifyAccount, ValidateUnsigned},
+		transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+		MultiSignature, MultiSigner,
+	};
+
+	new_test_ext().execute_with(|| {
+		let member = sr25519::Pair::from_seed(&[7; 32]);
+		let signer = MultiSigner::from(member.public());
+		ManagerMultisigMembers::set(&vec![signer.clone().into_account()]);
+		ManagerMultisigMaxVotesPerRound::set(&10);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let round = ManagerMultisigRound::<Runtime>::get();
+
+		let vote = ManagerMultisigVote::<Runtime>::new(signer.clone(), call.clone(), round, 100, 0);
+		let vote_sig = MultiSignature::from(member.sign(&vote.encode_with_bytes_wrapper()));
+		assert_ok!(RcMigrator::vote_manager_multisig(
+			RuntimeOrigin::none(),
+			Box::new(vote.clone()),
+			vote_sig.clone()
+		));
+
+		let withdrawal = ManagerMultisigWithdrawal::<Runtime>::new(signer, call.clone(), round, 0);
+		let sig = MultiSignature::from(member.sign(&withdrawal.encode_with_bytes_wrapper()));
+		assert_ok!(RcMigrator::withdraw_manager_vote(
+			RuntimeOrigin::none(),
+			Box::new(withdrawal),
+			sig
+		));
+		assert!(ManagerMultisigs::<Runtime>::get(&call).is_none());
+
+		// Resubmitting the original vote is rejected by the pool and by the call.
+		let replay = pallet_rc_migrator::Call::<Runtime>::vote_manager_multisig {
+			payload: Box::new(vote.clone()),
+			sig: vote_sig.clone(),
+		};
+		assert_eq!(
+			RcMigrator::validate_unsigned(TransactionSource::External, &replay),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Stale))
+		);
+		assert_noop!(
+			RcMigrator::vote_manager_multisig(RuntimeOrigin::none(), Box::new(vote), vote_sig),
+			Error::<Runtime>::UnsignedValidationFailed
+		);
+		assert!(ManagerMultisigs::<Runtime>::get(&call).is_none());
+	});
+}
@@ -1919,7 +1919,12 @@ parameter_types! {
 	pub RcMigratorMaxWeight: Weight = Perbill::from_percent(60) * BlockWeights::get().max_block;
 	pub AhExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT / 100;
 	pub const XcmResponseTimeout: BlockNumber = 30 * DAYS;
+	// Only messages that failed on the Asset Hub are retried, give the cause time to be resolved.
+	pub const XcmRetryInterval: BlockNumber = 6 * HOURS;
 	pub const AhUmpQueuePriorityPattern: (BlockNumber, BlockNumber) = (18, 2);
+	// The manager multisig is not used, unless its members are set in storage.
+	pub storage ManagerMultisigMembers: Vec<AccountId> = Vec::new();
+	pub storage ManagerMultisigMaxVotesPerRound: u32 = 0;
 }
 
 pub struct ProxyTypeAny;
@@ -1934,6 +1939,8 @@ impl pallet_rc_migrator::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReasonRegistry = ahm_phase1::HoldReasonRegistry;
+	type FreezeReasonRegistry = ahm_phase1::FreezeReasonRegistry;
 	type RuntimeEvent = RuntimeEvent;
 	type AdminOrigin = EitherOfDiverse<
 		EnsureRoot<AccountId>,
@@ -1956,6 +1963,8 @@ impl pallet_rc_migrator::Config for Runtime {
 	type OnDemandPalletId = OnDemandPalletId;
 	type UnprocessedMsgBuffer = ConstU32<50>;
 	type XcmResponseTimeout = XcmResponseTimeout;
+	type XcmRetryInterval = XcmRetryInterval;
+	type MaxXcmRetries = ConstU32<5>;
 	type MessageQueue = MessageQueue;
 	type AhUmpQueuePriorityPattern = AhUmpQueuePriorityPattern;
 	type SessionDuration = EpochDuration; // Session == Epoch
@@ -1963,9 +1972,10 @@ impl pallet_rc_migrator::Config for Runtime {
 	type KusamaConfig = Runtime;
 	#[cfg(feature = "kusama-ahm")]
 	type RecoveryBlockNumberProvider = System;
-	type MultisigMembers = ();
+	type MultisigMembers = ManagerMultisigMembers;
 	type MultisigThreshold = ConstU32<{ u32::MAX }>;
-	type MultisigMaxVotesPerRound = (); // defunct
+	type MultisigMaxVotesPerRound = ManagerMultisigMaxVotesPerRound;
+	type MigrationSequence = pallet_rc_migrator::sequence::DefaultMigrationSequence<Runtime>;
 }
 
 construct_runtime! {
@@ -2146,7 +2156,7 @@ pub mod migrations {
 	use super::*;
 
 	/// Unreleased migrations. Add new ones here:
-	pub type Unreleased = ();
+	pub type Unreleased = (pallet_rc_migrator::migrations::Migrations<Runtime>,);
 
 	/// Migrations/checks that do not need to be versioned and can run on every update.
 	pub type Permanent = pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>;
@@ -3340,6 +3350,56 @@ mod remote_tests {
 		ext.execute_with(|| Runtime::on_runtime_upgrade(UpgradeCheckSelect::PreAndPost));
 	}
 
+	/// Record the messages that the Asset Hub migration sends from the state of `SNAP` into the
+	/// message log at `AHM_LOG`, see [`pallet_rc_migrator::message_log`].
+	///
+	/// The Asset Hub is assumed to confirm every message instantly. The log is replayed into an
+	/// Asset Hub snapshot with the `replay_message_log` test of the Asset Hub runtime.
+	#[tokio::test]
+	#[ignore = "this test is meant to be executed manually"]
+	async fn record_migration_messages() {
+		use frame_support::traits::Hooks;
+		use pallet_rc_migrator::{
+			message_log::MessageRecorder, MigrationStage, PendingXcmMessages, PendingXcmQueries,
+			PendingXcmRetries, RcMigrationStage,
+		};
+
+		sp_tracing::try_init_simple();
+		let path = var("AHM_LOG").unwrap_or("migration.ahmlog".to_string());
+		let max_blocks: u32 =
+			var("AHM_MAX_BLOCKS").map_or(100_000, |blocks| blocks.parse().unwrap());
+		let mut ext = remote_ext_test_setup().await;
+		ext.execute_with(|| {
+			let mut recorder = MessageRecorder::<Runtime>::start(&path).unwrap();
+			let mut now = System::block_number();
+			RcMigrationStage::<Runtime>::put(MigrationStage::Scheduled { start: now + 1 });
+
+			for _ in 0..max_blocks {
+				now += 1;
+				System::reset_events();
+				System::set_block_number(now);
+				RcMigrator::on_initialize(now);
+				recorder.record_block().unwrap();
+
+				// Pretend that the Asset Hub confirmed every message.
+				let _ = PendingXcmMessages::<Runtime>::clear(u32::MAX, None);
+				let _ = PendingXcmQueries::<Runtime>::clear(u32::MAX, None);
+				let _ = PendingXcmRetries::<Runtime>::clear(u32::MAX, None);
+				match RcMigrationStage::<Runtime>::get() {
+					// Pretend that the Asset Hub is ready, without the warm-up period.
+					MigrationStage::WaitingForAh =>
+						RcMigrationStage::<Runtime>::put(MigrationStage::Starting),
+					// The `FinishMigration` message was sent.
+					MigrationStage::CoolOff { .. } => break,
+					_ => {},
+				}
+			}
+
+			recorder.finish().unwrap();
+			log::info!(target: LOG_TARGET, "Recorded the messages until block {now} into {path}");
+		});
+	}
+
 	#[tokio::test]
 	async fn next_inflation() {
 		if var("REMOTE_TESTS").is_err() {
@@ -1735,6 +1735,8 @@ parameter_types! {
 	pub RcMigratorMaxWeight: Weight = Perbill::from_percent(60) * BlockWeights::get().max_block;
 	pub AhExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT / 100;
 	pub const XcmResponseTimeout: BlockNumber = 30 * DAYS;
+	// Only messages that failed on the Asset Hub are retried, give the cause time to be resolved.
+	pub const XcmRetryInterval: BlockNumber = 6 * HOURS;
 	pub const AhUmpQueuePriorityPattern: (BlockNumber, BlockNumber) = (18, 2);
 }
 
@@ -1750,6 +1752,8 @@ impl pallet_rc_migrator::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type HoldReasonRegistry = ahm_phase1::HoldReasonRegistry;
+	type FreezeReasonRegistry = ahm_phase1::FreezeReasonRegistry;
 	type RuntimeEvent = RuntimeEvent;
 	type AdminOrigin = EitherOfDiverse<
 		EnsureRoot<AccountId>,
@@ -1776,11 +1780,14 @@ impl pallet_rc_migrator::Config for Runtime {
 	type OnDemandPalletId = OnDemandPalletId;
 	type UnprocessedMsgBuffer = ConstU32<50>;
 	type XcmResponseTimeout = XcmResponseTimeout;
+	type XcmRetryInterval = XcmRetryInterval;
+	type MaxXcmRetries = ConstU32<5>;
 	type MessageQueue = MessageQueue;
 	type AhUmpQueuePriorityPattern = AhUmpQueuePriorityPattern;
 	type MultisigMembers = (); // disabled post AHM
 	type MultisigThreshold = ConstU32<{ u32::MAX }>; // disabled
 	type MultisigMaxVotesPerRound = (); // disabled
+	type MigrationSequence = pallet_rc_migrator::sequence::DefaultMigrationSequence<Runtime>;
 }
 
 construct_runtime! {
@@ -1937,7 +1944,7 @@ pub mod migrations {
 	use pallet_rc_migrator::{MigrationStage, MigrationStartBlock, RcMigrationStage};
 
 	/// Unreleased migrations. Add new ones here:
-	pub type Unreleased = (KickOffAhm<Runtime>,);
+	pub type Unreleased = (KickOffAhm<Runtime>, pallet_rc_migrator::migrations::Migrations<Runtime>);
 
 	/// Migrations/checks that do not need to be versioned and can run on every update.
 	pub type Permanent = pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>;
@@ -14,14 +14,22 @@
 // limitations under the License.
 
 pub mod call_filter;
+#[cfg(test)]
+mod tests;
 
 extern crate alloc;
 
 use super::*;
 use alloc::boxed::Box;
-use codec::DecodeAll;
-use frame_support::pallet_prelude::TypeInfo;
-use pallet_ah_migrator::LOG_TARGET;
+use frame_support::pallet_prelude::{PalletInfoAccess, TypeInfo};
+use pallet_ah_migrator::{
+	account_translation::TranslateAccountRcToAh,
+	call_translation::{
+		MapRcCall, MultisigCalls, ProxyCalls, RcToAhCallTranslator, RemapPallets, TypedCall,
+		UtilityCalls,
+	},
+	LOG_TARGET,
+};
 use parachains_common::pay::VersionedLocatableAccount;
 use polkadot_runtime_common::impls::{LocatableAssetConverter, VersionedLocatableAsset};
 use sp_core::Get;
@@ -59,6 +67,28 @@ impl From<pallet_rc_migrator::types::PortableFreezeReason> for RuntimeFreezeReas
 	}
 }
 
+parameter_types! {
+	/// All portable hold and freeze reasons are mapped, this only applies to reasons that the
+	/// mappings do not know. The accounts are withdrawn from the Relay Chain already, so their
+	/// balance is kept reserved.
+	pub const ReasonFallbackPolicy: pallet_rc_migrator::types::UnmappedReasonPolicy =
+		pallet_rc_migrator::types::UnmappedReasonPolicy::ConvertToReserve;
+}
+
+/// Translates the portable hold reasons of the received accounts with [`From`].
+pub type HoldReasonRegistry = pallet_rc_migrator::types::MappedReasons<
+	RuntimeHoldReason,
+	pallet_rc_migrator::types::ViaFrom,
+	ReasonFallbackPolicy,
+>;
+
+/// Translates the portable freeze reasons of the received accounts with [`From`].
+pub type FreezeReasonRegistry = pallet_rc_migrator::types::MappedReasons<
+	RuntimeFreezeReason,
+	pallet_rc_migrator::types::ViaFrom,
+	ReasonFallbackPolicy,
+>;
+
 /// Treasury accounts migrating to the new treasury account address (same account address that was
 /// used on the Relay Chain).
 pub struct TreasuryAccounts;
@@ -120,23 +150,6 @@ impl TryConvert<RcPalletsOrigin, OriginCaller> for RcToAhPalletsOrigin {
 	}
 }
 
-/// Relay Chain Runtime Call.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcRuntimeCall {
-	#[codec(index = 0u8)]
-	System(frame_system::Call<Runtime>),
-	#[codec(index = 18u8)]
-	Treasury(RcTreasuryCall),
-	#[codec(index = 21u8)]
-	Referenda(pallet_referenda::Call<Runtime>),
-	#[codec(index = 24u8)]
-	Utility(RcUtilityCall),
-	#[codec(index = 35u8)]
-	Bounties(pallet_bounties::Call<Runtime>),
-	#[codec(index = 40u8)]
-	ChildBounties(pallet_child_bounties::Call<Runtime>),
-}
-
 /// Relay Chain Treasury Call obtained from cargo expand.
 #[allow(non_camel_case_types)]
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -172,86 +185,66 @@ pub enum RcTreasuryCall {
 	void_spend { index: pallet_treasury::SpendIndex },
 }
 
-/// Relay Chain Utility Call obtained from cargo expand.
-///
-/// The variants that are not generally used in Governance are not included.
-#[allow(non_camel_case_types)]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcUtilityCall {
-	/// Send a batch of dispatch calls.
-	#[codec(index = 0u8)]
-	batch { calls: Vec<RcRuntimeCall> },
-	/// Send a batch of dispatch calls and atomically execute them.
-	#[codec(index = 2u8)]
-	batch_all { calls: Vec<RcRuntimeCall> },
-	/// Dispatches a function call with a provided origin.
-	#[codec(index = 3u8)]
-	dispatch_as { as_origin: Box<RcPalletsOrigin>, call: Box<RcRuntimeCall> },
-	/// Send a batch of dispatch calls.
-	/// Unlike `batch`, it allows errors and won't interrupt.
-	#[codec(index = 4u8)]
-	force_batch { calls: Vec<RcRuntimeCall> },
+parameter_types! {
+	pub const RcTreasuryIndex: u8 = 18;
+	pub const RcUtilityIndex: u8 = 24;
+	pub const RcProxyIndex: u8 = 30;
+	pub const RcMultisigIndex: u8 = 31;
+	pub AhUtilityIndex: u8 = <Utility as PalletInfoAccess>::index() as u8;
+	pub AhProxyIndex: u8 = <Proxy as PalletInfoAccess>::index() as u8;
+	pub AhMultisigIndex: u8 = <Multisig as PalletInfoAccess>::index() as u8;
+	/// Relay Chain pallets whose calls are the same on Asset Hub, with their Asset Hub index.
+	pub RcToAhPalletIndices: Vec<(u8, u8)> = vec![
+		(0, <System as PalletInfoAccess>::index() as u8),
+		(21, <Referenda as PalletInfoAccess>::index() as u8),
+		(35, <Bounties as PalletInfoAccess>::index() as u8),
+		(40, <ChildBounties as PalletInfoAccess>::index() as u8),
+	];
 }
 
 /// Convert an encoded Relay Chain Call to a local AH one.
-pub struct RcToAhCall;
-impl<'a> TryConvert<&'a [u8], RuntimeCall> for RcToAhCall {
-	fn try_convert(mut a: &'a [u8]) -> Result<RuntimeCall, &'a [u8]> {
-		let rc_call = match RcRuntimeCall::decode_all(&mut a) {
-			Ok(rc_call) => rc_call,
-			Err(e) => {
-				log::error!(target: LOG_TARGET, "Failed to decode RC call with error: {e:?}",);
-				return Err(a)
-			},
-		};
-		Self::map(rc_call).map_err(|_| a)
-	}
-}
-impl RcToAhCall {
-	fn map(rc_call: RcRuntimeCall) -> Result<RuntimeCall, ()> {
+pub type RcToAhCall = RcToAhCallTranslator<
+	RuntimeCall,
+	(
+		UtilityCalls<
+			RcUtilityIndex,
+			AhUtilityIndex,
+			RcPalletsOrigin,
+			OriginCaller,
+			RcToAhPalletsOrigin,
+		>,
+		ProxyCalls<
+			RcProxyIndex,
+			AhProxyIndex,
+			RcProxyType,
+			ProxyType,
+			RcToProxyType,
+			TranslateAccountRcToAh<Runtime>,
+		>,
+		MultisigCalls<RcMultisigIndex, AhMultisigIndex>,
+		TypedCall<RcTreasuryIndex, RcTreasuryCall, RcToAhTreasuryCall>,
+		RemapPallets<RcToAhPalletIndices>,
+	),
+>;
+
+/// Convert a Relay Chain Treasury call to an Asset Hub one.
+pub struct RcToAhTreasuryCall;
+impl MapRcCall<RcTreasuryCall, RuntimeCall> for RcToAhTreasuryCall {
+	fn map(rc_call: RcTreasuryCall) -> Result<RuntimeCall, ()> {
 		match rc_call {
-			RcRuntimeCall::System(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
+			RcTreasuryCall::spend { asset_kind, amount, beneficiary, valid_from } => {
+				let beneficiary =
+					pallet_ah_migrator::Pallet::<Runtime>::translate_beneficiary_location(
+						*beneficiary,
+					)
+					.map_err(|err| {
 						log::error!(
 							target: LOG_TARGET,
-							"Failed to decode RC Bounties call to AH System call: {err:?}",
+							"Failed to translate RC treasury spend beneficiary: {err:?}",
 						);
 					})?;
-				Ok(RuntimeCall::System(call))
-			},
-			RcRuntimeCall::Utility(RcUtilityCall::dispatch_as { as_origin, call }) => {
-				let origin = RcToAhPalletsOrigin::try_convert(*as_origin).map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to decode RC dispatch_as origin: {err:?}",
-					);
-				})?;
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::dispatch_as {
-					as_origin: Box::new(origin),
-					call: Box::new(Self::map(*call)?),
-				}))
-			},
-			RcRuntimeCall::Utility(RcUtilityCall::batch { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::batch {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Utility(RcUtilityCall::batch_all { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::batch_all {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Utility(RcUtilityCall::force_batch { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::force_batch {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Treasury(RcTreasuryCall::spend {
-				asset_kind,
-				amount,
-				beneficiary,
-				valid_from,
-			}) => {
 				let (asset_kind, beneficiary) =
-					RcToAhTreasurySpend::convert((*asset_kind, *beneficiary))?;
+					RcToAhTreasurySpend::convert((*asset_kind, beneficiary))?;
 				Ok(RuntimeCall::Treasury(pallet_treasury::Call::<Runtime>::spend {
 					asset_kind: Box::new(asset_kind),
 					amount,
@@ -259,7 +252,7 @@ impl RcToAhCall {
 					valid_from,
 				}))
 			},
-			RcRuntimeCall::Treasury(inner_call) => {
+			inner_call => {
 				let call =
 					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
 						log::error!(
@@ -269,36 +262,6 @@ impl RcToAhCall {
 					})?;
 				Ok(RuntimeCall::Treasury(call))
 			},
-			RcRuntimeCall::Referenda(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC Referenda call to AH Referenda call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::Referenda(call))
-			},
-			RcRuntimeCall::Bounties(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC Bounties call to AH Bounties call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::Bounties(call))
-			},
-			RcRuntimeCall::ChildBounties(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC ChildBounties call to AH ChildBounties call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::ChildBounties(call))
-			},
 		}
 	}
 }
@@ -343,3 +306,65 @@ impl
 		Ok((asset_kind, beneficiary))
 	}
 }
+
+/// Preimages of whitelisted calls, which the preimage garbage collection of the migration keeps.
+pub struct WhitelistedPreimages;
+impl Contains<Hash> for WhitelistedPreimages {
+	fn contains(hash: &Hash) -> bool {
+		pallet_whitelist::WhitelistedCall::<Runtime>::contains_key(hash)
+	}
+}
+
+/// Ensures that the origin is a sibling parachain and returns its para id.
+///
+/// Sibling parachains send it over XCM with `OriginKind::Native`.
+pub struct EnsureSiblingPara;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingPara {
+	type Success = ParaId;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		let origin: Result<cumulus_pallet_xcm::Origin, RuntimeOrigin> = o.clone().into();
+		match origin {
+			Ok(cumulus_pallet_xcm::Origin::SiblingParachain(para_id)) => Ok(para_id),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(cumulus_pallet_xcm::Origin::SiblingParachain(ParaId::from(2000)).into())
+	}
+}
+
+/// Sets up the benchmarks of `pallet_ah_ops`.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AhOpsBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_ah_ops::BenchmarkHelper<RuntimeHoldReason> for AhOpsBenchmarkHelper {
+	fn hold_reasons() -> Vec<RuntimeHoldReason> {
+		use pallet_rc_migrator::types::PortableHoldReason;
+
+		// The reasons that accounts migrated from the Relay Chain can hold balance for.
+		vec![
+			PortableHoldReason::Preimage(pallet_preimage::HoldReason::Preimage),
+			PortableHoldReason::Staking(pallet_staking::HoldReason::Staking),
+			PortableHoldReason::StateTrieMigration(
+				pallet_state_trie_migration::HoldReason::SlashForMigrate,
+			),
+			PortableHoldReason::DelegatedStaking(
+				pallet_delegated_staking::HoldReason::StakingDelegation,
+			),
+			PortableHoldReason::Session(pallet_session::HoldReason::Keys),
+			PortableHoldReason::XcmPallet(pallet_xcm::HoldReason::AuthorizeAlias),
+		]
+		.into_iter()
+		.map(Into::into)
+		.collect()
+	}
+
+	fn complete_migration() {
+		pallet_ah_migrator::AhMigrationStage::<Runtime>::put(
+			pallet_ah_migrator::MigrationStage::MigrationDone,
+		);
+	}
+}
@@ -0,0 +1,1138 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests of the Asset Hub side of the Asset Hub Migration.
+
+use crate::*;
+use frame_support::{assert_noop, assert_ok};
+use pallet_ah_migrator::{
+	DigestMismatchHalt, Error, Event as AhMigratorEvent, HaltOnDigestMismatch, PalletEventName,
+};
+use pallet_rc_migrator::{digest::MigrationDigest, sequence::MigratedPallet};
+use sp_runtime::BuildStorage;
+
+pub(super) const ALICE: AccountId = AccountId::new([1; 32]);
+pub(super) const BOB: AccountId = AccountId::new([2; 32]);
+
+/// Externalities with funded accounts.
+pub(super) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(ALICE, 100 * UNITS), (BOB, 100 * UNITS)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn digest_of(items: &[u32]) -> MigrationDigest {
+	let mut digest = MigrationDigest::default();
+	digest.record_all(items);
+	digest
+}
+
+#[test]
+fn digest_mismatch_halts_the_ingestion_if_enabled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AhMigrator::set_halt_on_digest_mismatch(RuntimeOrigin::root(), true));
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32, 2]));
+
+		// The third item is still in flight.
+		assert_ok!(AhMigrator::receive_migration_digest(
+			RuntimeOrigin::root(),
+			MigratedPallet::Accounts,
+			digest_of(&[1, 2, 3]),
+		));
+		System::assert_last_event(
+			AhMigratorEvent::<Runtime>::IngestionHalted { pallet: MigratedPallet::Accounts }.into(),
+		);
+		assert_noop!(
+			AhMigrator::record_received_items(MigratedPallet::Multisig, &[4u32]),
+			Error::<Runtime>::IngestionHalted
+		);
+
+		// The resent item resolves the mismatch and lifts the halt.
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[3u32]));
+		System::assert_last_event(
+			AhMigratorEvent::<Runtime>::IngestionResumed { pallet: MigratedPallet::Accounts }
+				.into(),
+		);
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Multisig, &[4u32]));
+	});
+}
+
+#[test]
+fn digest_mismatch_does_not_halt_the_ingestion_by_default() {
+	new_test_ext().execute_with(|| {
+		assert!(!HaltOnDigestMismatch::<Runtime>::get());
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32]));
+		assert_ok!(AhMigrator::receive_migration_digest(
+			RuntimeOrigin::root(),
+			MigratedPallet::Accounts,
+			digest_of(&[2]),
+		));
+
+		assert!(DigestMismatchHalt::<Runtime>::get().is_none());
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Multisig, &[4u32]));
+	});
+}
+
+#[test]
+fn disabling_the_halt_resumes_the_ingestion() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AhMigrator::set_halt_on_digest_mismatch(RuntimeOrigin::root(), true));
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32]));
+		assert_ok!(AhMigrator::receive_migration_digest(
+			RuntimeOrigin::root(),
+			MigratedPallet::Accounts,
+			digest_of(&[2]),
+		));
+		assert_eq!(DigestMismatchHalt::<Runtime>::get(), Some(MigratedPallet::Accounts));
+
+		assert_noop!(
+			AhMigrator::set_halt_on_digest_mismatch(RuntimeOrigin::signed(ALICE), false),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(AhMigrator::set_halt_on_digest_mismatch(RuntimeOrigin::root(), false));
+		assert!(DigestMismatchHalt::<Runtime>::get().is_none());
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Multisig, &[4u32]));
+	});
+}
+
+#[test]
+fn received_items_count_twice_in_the_digest() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32, 1]));
+		assert_ok!(AhMigrator::receive_migration_digest(
+			RuntimeOrigin::root(),
+			MigratedPallet::Accounts,
+			digest_of(&[2, 2]),
+		));
+
+		System::assert_has_event(
+			AhMigratorEvent::<Runtime>::MigrationDigestMismatch {
+				pallet: MigratedPallet::Accounts,
+				expected: digest_of(&[2, 2]),
+				received: digest_of(&[1, 1]),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn batches_of_another_pallet_are_rejected_while_a_window_is_open() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32]));
+
+		assert_noop!(
+			AhMigrator::record_received_items(MigratedPallet::Multisig, &[2u32]),
+			Error::<Runtime>::BatchOutOfOrder
+		);
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[3u32]));
+	});
+}
+
+#[test]
+fn missing_digest_holds_back_the_next_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32]));
+
+		// The digest of the accounts is lost, the batches of the multisigs stay on the Relay Chain.
+		assert_noop!(
+			AhMigrator::record_received_items(MigratedPallet::Multisig, &[2u32]),
+			Error::<Runtime>::BatchOutOfOrder
+		);
+
+		// The resent digest closes the window of the accounts.
+		assert_ok!(AhMigrator::receive_migration_digest(
+			RuntimeOrigin::root(),
+			MigratedPallet::Accounts,
+			digest_of(&[1]),
+		));
+		assert_ok!(AhMigrator::record_received_items(MigratedPallet::Multisig, &[2u32]));
+
+		// The accounts are verified, late batches are rejected.
+		assert_noop!(
+			AhMigrator::record_received_items(MigratedPallet::Accounts, &[1u32]),
+			Error::<Runtime>::BatchOutOfOrder
+		);
+	});
+}
+
+#[test]
+fn fast_unstake_deposits_are_unreserved() {
+	use frame_support::traits::ReservableCurrency;
+	use pallet_rc_migrator::fast_unstake::RcFastUnstakeRequest;
+
+	new_test_ext().execute_with(|| {
+		let deposit = UNITS;
+		assert_ok!(Balances::reserve(&ALICE, deposit));
+
+		assert_ok!(AhMigrator::receive_fast_unstake_messages(
+			RuntimeOrigin::root(),
+			vec![
+				RcFastUnstakeRequest { stash: ALICE, deposit },
+				RcFastUnstakeRequest { stash: BOB, deposit },
+			],
+		));
+
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		// Nothing was reserved for BOB, which is reported but does not fail the batch.
+		System::assert_has_event(
+			AhMigratorEvent::<Runtime>::FailedToUnreserveFastUnstakeDeposit {
+				expected_amount: deposit,
+				missing_amount: deposit,
+				account: BOB,
+			}
+			.into(),
+		);
+	});
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+#[test]
+fn receive_fast_unstake_messages_benchmark_works() {
+	new_test_ext().execute_with(|| {
+		pallet_ah_migrator::benchmarking::benchmarks::test_receive_fast_unstake_messages::<Runtime>(
+			10,
+		);
+	});
+}
+
+#[test]
+fn para_reserves_messages_release_bids_and_registration_deposits() {
+	use frame_support::traits::ReservableCurrency;
+	use pallet_rc_migrator::para_reserves::RcParaReservesMessage;
+
+	new_test_ext().execute_with(|| {
+		let amount = UNITS;
+		let para_id = ParaId::from(2000);
+		assert_ok!(Balances::reserve(&ALICE, amount));
+		assert_ok!(Balances::reserve(&BOB, amount));
+
+		assert_ok!(AhMigrator::receive_para_reserves_messages(
+			RuntimeOrigin::root(),
+			vec![
+				RcParaReservesMessage::AuctionReserve { bidder: ALICE, para_id, amount },
+				RcParaReservesMessage::RegistrarDeposit { manager: BOB, para_id, amount },
+			],
+		));
+
+		// The bid is scheduled at the first block to be released right away.
+		assert_eq!(
+			pallet_ah_ops::RcLeaseReserve::<Runtime>::get((0, para_id, &ALICE)),
+			Some(amount)
+		);
+		assert!(pallet_ah_ops::UnlockSchedule::<Runtime>::contains_key(0, para_id));
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+	});
+}
+
+/// Quarantine a registration deposit of BOB that is not reserved and return its hash.
+fn quarantine_registration_deposit(amount: u128) -> sp_core::H256 {
+	use codec::Encode;
+	use pallet_rc_migrator::para_reserves::RcParaReservesMessage;
+
+	let message =
+		RcParaReservesMessage::RegistrarDeposit { manager: BOB, para_id: 2000.into(), amount };
+	assert_ok!(AhMigrator::receive_para_reserves_messages(
+		RuntimeOrigin::root(),
+		vec![message.clone()],
+	));
+	let hash = sp_core::H256(sp_io::hashing::blake2_256(&message.encode()));
+	assert!(pallet_ah_migrator::Quarantine::<Runtime>::contains_key(
+		PalletEventName::ParaReserves,
+		hash
+	));
+	hash
+}
+
+#[test]
+fn quarantined_items_are_retried_while_the_migration_is_ongoing() {
+	use frame_support::traits::ReservableCurrency;
+
+	new_test_ext().execute_with(|| {
+		let hash = quarantine_registration_deposit(UNITS);
+		assert_noop!(
+			AhMigrator::retry_quarantined(
+				RuntimeOrigin::root(),
+				PalletEventName::ParaReserves,
+				hash
+			),
+			Error::<Runtime>::MigrationNotOngoing
+		);
+
+		pallet_ah_migrator::AhMigrationStage::<Runtime>::put(
+			pallet_ah_migrator::MigrationStage::DataMigrationOngoing,
+		);
+		assert_noop!(
+			AhMigrator::retry_quarantined(
+				RuntimeOrigin::root(),
+				PalletEventName::ParaReserves,
+				hash
+			),
+			Error::<Runtime>::FailedToUnreserveDeposit
+		);
+
+		assert_ok!(Balances::reserve(&BOB, UNITS));
+		assert_ok!(AhMigrator::retry_quarantined(
+			RuntimeOrigin::root(),
+			PalletEventName::ParaReserves,
+			hash
+		));
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+		assert_eq!(pallet_ah_migrator::QuarantineCount::<Runtime>::get(), 0);
+	});
+}
+
+#[test]
+fn corrected_items_can_only_be_applied_by_the_admin() {
+	use codec::Encode;
+	use frame_support::traits::ReservableCurrency;
+	use pallet_rc_migrator::para_reserves::RcParaReservesMessage;
+
+	new_test_ext().execute_with(|| {
+		pallet_ah_migrator::AhMigrationStage::<Runtime>::put(
+			pallet_ah_migrator::MigrationStage::DataMigrationOngoing,
+		);
+		let hash = quarantine_registration_deposit(2 * UNITS);
+		assert_ok!(Balances::reserve(&BOB, UNITS));
+		let corrected = RcParaReservesMessage::RegistrarDeposit {
+			manager: BOB,
+			para_id: 2000.into(),
+			amount: UNITS,
+		};
+
+		// The manager can retry, but not replace the item.
+		pallet_ah_migrator::Manager::<Runtime>::put(ALICE);
+		assert_noop!(
+			AhMigrator::force_apply_quarantined(
+				RuntimeOrigin::signed(ALICE),
+				PalletEventName::ParaReserves,
+				hash,
+				corrected.encode(),
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(AhMigrator::force_apply_quarantined(
+			RuntimeOrigin::root(),
+			PalletEventName::ParaReserves,
+			hash,
+			corrected.encode(),
+		));
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+		System::assert_last_event(
+			AhMigratorEvent::<Runtime>::QuarantinedItemApplied {
+				pallet: PalletEventName::ParaReserves,
+				hash,
+				forced: true,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn items_of_unsupported_pallets_are_not_applied() {
+	new_test_ext().execute_with(|| {
+		pallet_ah_migrator::AhMigrationStage::<Runtime>::put(
+			pallet_ah_migrator::MigrationStage::DataMigrationOngoing,
+		);
+		assert_noop!(
+			AhMigrator::force_apply_quarantined(
+				RuntimeOrigin::root(),
+				PalletEventName::Scheduler,
+				Default::default(),
+				Vec::new(),
+			),
+			Error::<Runtime>::QuarantineNotSupported
+		);
+	});
+}
+
+#[test]
+fn quarantined_items_are_listed_in_pages() {
+	new_test_ext().execute_with(|| {
+		let hashes: Vec<_> = (1..=3).map(quarantine_registration_deposit).collect();
+
+		let first = AhMigrator::quarantined_items(None, 2);
+		assert_eq!(first.len(), 2);
+		let (pallet, hash, _) = first.last().unwrap().clone();
+		let second = AhMigrator::quarantined_items(Some((pallet, hash)), 2);
+		assert_eq!(second.len(), 1);
+
+		let mut listed: Vec<_> = first.iter().chain(&second).map(|(_, hash, _)| *hash).collect();
+		let mut expected = hashes;
+		listed.sort();
+		expected.sort();
+		assert_eq!(listed, expected);
+	});
+}
+
+#[test]
+fn failed_accounts_are_moved_into_the_quarantine() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+	use pallet_ah_migrator::migrations::{v1::RcAccounts, MigrateToV1};
+	use pallet_rc_migrator::accounts::Account;
+
+	new_test_ext().execute_with(|| {
+		let account = Account {
+			who: ALICE,
+			free: UNITS,
+			reserved: 0,
+			frozen: 0,
+			holds: Default::default(),
+			freezes: Default::default(),
+			locks: Default::default(),
+			unnamed_reserve: 0,
+			consumers: 0,
+			providers: 1,
+		};
+		RcAccounts::<Runtime>::insert(&ALICE, account);
+		StorageVersion::new(0).put::<AhMigrator>();
+
+		MigrateToV1::<Runtime>::on_runtime_upgrade();
+
+		assert_eq!(RcAccounts::<Runtime>::iter().count(), 0);
+		assert_eq!(
+			pallet_ah_migrator::Quarantine::<Runtime>::iter_prefix(PalletEventName::Balances)
+				.count(),
+			1
+		);
+		assert_eq!(AhMigrator::on_chain_storage_version(), 1);
+	});
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+#[test]
+fn claim_derived_sovereign_account_benchmark_works() {
+	use pallet_ah_ops::benchmarking::benchmarks;
+
+	new_test_ext().execute_with(|| benchmarks::test_claim_derived_sovereign_account::<Runtime>());
+}
+
+mod reconciliation {
+	use super::*;
+	use pallet_ah_migrator::AhMigratedTotals;
+	use pallet_rc_migrator::types::{MigratedTotals, MigrationFinishedData, PortableHoldReason};
+
+	type Totals = MigratedTotals<PortableHoldReason, Balance>;
+
+	fn preimage() -> PortableHoldReason {
+		PortableHoldReason::Preimage(pallet_preimage::HoldReason::Preimage)
+	}
+
+	fn totals(balance: Balance, held: &[(PortableHoldReason, Balance)]) -> Totals {
+		let mut totals = Totals { balance, vesting_locked: 10 * UNITS, ..Default::default() };
+		held.iter().for_each(|(reason, amount)| totals.add_hold(reason, *amount));
+		totals
+	}
+
+	fn finished_data(rc_totals: Totals) -> MigrationFinishedData<Balance> {
+		let rc_balance_migrated = rc_totals.balance;
+		MigrationFinishedData { rc_balance_kept: 0, rc_balance_migrated, rc_totals }
+	}
+
+	#[test]
+	fn matching_totals_ignore_the_issuance_delta() {
+		new_test_ext().execute_with(|| {
+			let held = [(preimage(), 5 * UNITS)];
+			AhMigratedTotals::<Runtime>::put(totals(1_000 * UNITS, &held));
+
+			// Fees and burns on the Asset Hub do not count as a discrepancy.
+			let data = finished_data(totals(1_000 * UNITS, &held));
+			let report = AhMigrator::reconcile_balances(&data, 900 * UNITS);
+
+			assert_eq!((report.rc_migrated, report.ah_minted), (1_000 * UNITS, 1_000 * UNITS));
+			assert_eq!(report.ah_issuance_delta, 900 * UNITS);
+			assert_eq!(report.holds.into_inner(), vec![(preimage(), 5 * UNITS, 5 * UNITS)]);
+			assert_eq!(report.max_discrepancy, 0);
+			assert!(!report.exceeds_threshold);
+			// The totals of the Asset Hub are consumed.
+			assert!(!AhMigratedTotals::<Runtime>::exists());
+		});
+	}
+
+	#[test]
+	fn missing_holds_are_reported_against_zero() {
+		new_test_ext().execute_with(|| {
+			AhMigratedTotals::<Runtime>::put(totals(1_000 * UNITS, &[]));
+
+			let data = finished_data(totals(1_000 * UNITS, &[(preimage(), 5 * UNITS)]));
+			let report = AhMigrator::reconcile_balances(&data, 1_000 * UNITS);
+
+			assert_eq!(report.holds.into_inner(), vec![(preimage(), 5 * UNITS, 0)]);
+			assert_eq!(report.max_discrepancy, 5 * UNITS);
+			assert!(report.exceeds_threshold);
+		});
+	}
+
+	#[test]
+	fn discrepancies_above_the_threshold_hold_the_cool_off() {
+		new_test_ext().execute_with(|| {
+			// A dust account failed to integrate.
+			AhMigratedTotals::<Runtime>::put(totals(1_000 * UNITS, &[]));
+			AhMigrator::do_reconcile_balances(&finished_data(totals(1_000 * UNITS + 1, &[])), 0);
+			assert!(!AhMigrator::reconciliation_blocks_completion());
+
+			// An account with more than the threshold failed to integrate.
+			AhMigratedTotals::<Runtime>::put(totals(1_000 * UNITS, &[]));
+			let data = finished_data(totals(1_002 * UNITS, &[]));
+			AhMigrator::do_reconcile_balances(&data, 0);
+			assert!(AhMigrator::reconciliation_blocks_completion());
+			let report = pallet_ah_migrator::Reconciliation::<Runtime>::get().unwrap();
+			assert_eq!(report.max_discrepancy, 2 * UNITS);
+			System::assert_last_event(
+				AhMigratorEvent::<Runtime>::ReconciliationReport { report }.into(),
+			);
+		});
+	}
+}
+
+#[test]
+fn added_translations_translate_accounts() {
+	use pallet_ah_migrator::{
+		account_translation::sovereign_translation_of, ExtraAccountTranslationsCount,
+	};
+
+	new_test_ext().execute_with(|| {
+		// Neither the para nor its derived account are in the tables.
+		let (para, sibling) = sovereign_translation_of(100_000, None);
+		let (derived, sibling_derived) = sovereign_translation_of(100_000, Some(3));
+		assert_eq!(AhMigrator::translate_account_rc_to_ah(para.clone()), para);
+
+		assert_ok!(AhMigrator::add_account_translation(RuntimeOrigin::root(), 100_000, None));
+		assert_ok!(AhMigrator::add_account_translation(RuntimeOrigin::root(), 100_000, Some(3)));
+		System::assert_last_event(
+			AhMigratorEvent::<Runtime>::AccountTranslationAdded {
+				para_id: 100_000,
+				derivation_index: Some(3),
+				from: derived.clone(),
+				to: sibling_derived.clone(),
+			}
+			.into(),
+		);
+		assert_eq!(ExtraAccountTranslationsCount::<Runtime>::get(), 2);
+
+		assert_eq!(AhMigrator::translate_account_rc_to_ah(para.clone()), sibling);
+		assert_eq!(AhMigrator::derived_translation(&derived), Some((sibling_derived, 3)));
+		// A derived translation is not a sovereign one.
+		assert_eq!(AhMigrator::sovereign_translation(&derived), None);
+	});
+}
+
+#[test]
+fn translations_can_only_be_added_once() {
+	new_test_ext().execute_with(|| {
+		// Para 2048 is in the tables.
+		assert_noop!(
+			AhMigrator::add_account_translation(RuntimeOrigin::root(), 2048, None),
+			Error::<Runtime>::AccountTranslationExists
+		);
+
+		assert_ok!(AhMigrator::add_account_translation(RuntimeOrigin::root(), 100_000, None));
+		assert_noop!(
+			AhMigrator::add_account_translation(RuntimeOrigin::root(), 100_000, None),
+			Error::<Runtime>::AccountTranslationExists
+		);
+		assert_noop!(
+			AhMigrator::add_account_translation(RuntimeOrigin::signed(ALICE), 100_001, None),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn removed_translations_do_not_translate_accounts() {
+	use pallet_ah_migrator::{
+		account_translation::sovereign_translation_of, ExtraAccountTranslations,
+		ExtraAccountTranslationsCount,
+	};
+
+	new_test_ext().execute_with(|| {
+		let (para, _) = sovereign_translation_of(100_000, None);
+		assert_ok!(AhMigrator::add_account_translation(RuntimeOrigin::root(), 100_000, None));
+
+		assert_ok!(AhMigrator::remove_account_translation(RuntimeOrigin::root(), para.clone()));
+		System::assert_last_event(
+			AhMigratorEvent::<Runtime>::AccountTranslationRemoved { from: para.clone() }.into(),
+		);
+		assert_eq!(ExtraAccountTranslationsCount::<Runtime>::get(), 0);
+		assert_eq!(AhMigrator::translate_account_rc_to_ah(para.clone()), para);
+		assert_noop!(
+			AhMigrator::remove_account_translation(RuntimeOrigin::root(), para.clone()),
+			Error::<Runtime>::AccountTranslationNotFound
+		);
+
+		// The map is not read while the count is zero.
+		ExtraAccountTranslations::<Runtime>::insert(&para, (BOB, None));
+		assert_eq!(AhMigrator::translate_account_rc_to_ah(para.clone()), para);
+	});
+}
+
+mod call_translation {
+	use super::*;
+	use crate::ah_migration::{RcPalletsOrigin, RcProxyType, RcToAhCall, RcToAhPalletIndices};
+	use codec::{Compact, Encode};
+	use frame_support::{parameter_types, traits::PalletInfoAccess, weights::Weight};
+	use pallet_ah_migrator::call_translation::{
+		RcToAhCallTranslator, RemapCalls, RemapPallets, SchedulerCalls, XcmCalls,
+	};
+	use sp_runtime::{traits::TryConvert, MultiAddress};
+	use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm};
+
+	parameter_types! {
+		pub const RcSchedulerIndex: u8 = 1;
+		pub const RcXcmIndex: u8 = 99;
+		pub AhSchedulerIndex: u8 = <Scheduler as PalletInfoAccess>::index() as u8;
+		pub AhXcmIndex: u8 = <PolkadotXcm as PalletInfoAccess>::index() as u8;
+		pub RcAhLocation: Location = Location::new(0, [Parachain(1000)]);
+		pub RcUniversalLocation: InteriorLocation = [GlobalConsensus(NetworkId::Kusama)].into();
+		pub RemappedCalls: Vec<((u8, u8), (u8, u8))> = vec![((50, 1), (0, 0))];
+	}
+
+	/// The rules that the Kusama Asset Hub does not use.
+	type OtherRules = RcToAhCallTranslator<
+		RuntimeCall,
+		(
+			SchedulerCalls<RcSchedulerIndex, AhSchedulerIndex>,
+			XcmCalls<RcXcmIndex, AhXcmIndex, RcAhLocation, RcUniversalLocation>,
+			RemapCalls<RemappedCalls>,
+			RemapPallets<RcToAhPalletIndices>,
+		),
+	>;
+
+	fn translate(encoded: &[u8]) -> Result<RuntimeCall, ()> {
+		RcToAhCall::try_convert(encoded).map_err(|_| ())
+	}
+
+	fn remark() -> RuntimeCall {
+		RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2, 3] })
+	}
+
+	/// The Relay Chain encoding of `remark`, which is the same as on the Asset Hub.
+	fn rc_remark() -> Vec<u8> {
+		remark().encode()
+	}
+
+	/// The Relay Chain call with `index` and the encoded `args` followed by the `calls`.
+	fn rc_call(index: (u8, u8), args: impl Encode, calls: &[Vec<u8>]) -> Vec<u8> {
+		let mut encoded = vec![index.0, index.1];
+		args.encode_to(&mut encoded);
+		calls.iter().for_each(|call| encoded.extend_from_slice(call));
+		encoded
+	}
+
+	/// The sovereign account of a para with the `prefix` of the Relay Chain or a sibling.
+	fn sovereign_account(prefix: &[u8; 4], para_id: u32) -> AccountId {
+		let mut raw = [0u8; 32];
+		raw[..4].copy_from_slice(prefix);
+		raw[4..8].copy_from_slice(&para_id.to_le_bytes());
+		AccountId::new(raw)
+	}
+
+	#[test]
+	fn remapped_pallet_calls_keep_their_arguments() {
+		assert_eq!(translate(&rc_remark()), Ok(remark()));
+
+		let cancel = pallet_referenda::Call::<Runtime>::cancel { index: 7 };
+		let rc_cancel = [&[21][..], &cancel.encode()].concat();
+		assert_eq!(translate(&rc_cancel), Ok(RuntimeCall::Referenda(cancel)));
+	}
+
+	#[test]
+	fn trailing_bytes_are_rejected() {
+		let mut encoded = rc_remark();
+		encoded.push(0);
+		assert_eq!(translate(&encoded), Err(()));
+
+		let batch = rc_call((24, 0), Compact(1u32), &[encoded]);
+		assert_eq!(translate(&batch), Err(()));
+	}
+
+	#[test]
+	fn unknown_calls_are_rejected() {
+		assert_eq!(translate(&rc_call((250, 0), (), &[])), Err(()));
+		assert_eq!(translate(&[]), Err(()));
+	}
+
+	#[test]
+	fn nested_batches_are_translated() {
+		let inner = rc_call((24, 2), Compact(1u32), &[rc_remark()]);
+		let outer = rc_call((24, 0), Compact(2u32), &[rc_remark(), inner]);
+
+		let expected = RuntimeCall::Utility(pallet_utility::Call::batch {
+			calls: vec![
+				remark(),
+				RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![remark()] }),
+			],
+		});
+		assert_eq!(translate(&outer), Ok(expected));
+	}
+
+	#[test]
+	fn dispatch_as_translates_the_origin() {
+		let origin = RcPalletsOrigin::system(frame_system::RawOrigin::Root);
+		let encoded = rc_call((24, 3), origin, &[rc_remark()]);
+
+		let expected = RuntimeCall::Utility(pallet_utility::Call::dispatch_as {
+			as_origin: Box::new(OriginCaller::system(frame_system::RawOrigin::Root)),
+			call: Box::new(remark()),
+		});
+		assert_eq!(translate(&encoded), Ok(expected));
+	}
+
+	#[test]
+	fn proxy_calls_translate_the_real_account_and_the_proxy_type() {
+		let real: MultiAddress<AccountId, ()> = MultiAddress::Id(sovereign_account(b"para", 2048));
+		let batch = rc_call((24, 4), Compact(1u32), &[rc_remark()]);
+		let encoded = rc_call((30, 0), (real, Some(RcProxyType::Governance)), &[batch]);
+
+		let expected = RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+			real: MultiAddress::Id(sovereign_account(b"sibl", 2048)),
+			force_proxy_type: Some(ProxyType::Governance),
+			call: Box::new(RuntimeCall::Utility(pallet_utility::Call::force_batch {
+				calls: vec![remark()],
+			})),
+		});
+		assert_eq!(translate(&encoded), Ok(expected));
+	}
+
+	#[test]
+	fn multisig_calls_keep_the_signatories() {
+		// A sovereign account in the translation tables stays as it is, since the multisig
+		// account is derived from the signatories.
+		let signatories = vec![ALICE, sovereign_account(b"para", 2048)];
+		let encoded = rc_call((31, 0), signatories.clone(), &[rc_remark()]);
+
+		let expected = RuntimeCall::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+			other_signatories: signatories.clone(),
+			call: Box::new(remark()),
+		});
+		assert_eq!(translate(&encoded), Ok(expected));
+
+		// Approvals of an operation started on the Relay Chain can not be translated.
+		let timepoint = pallet_multisig::Timepoint { height: 1u32, index: 0 };
+		let approval = rc_call((31, 1), (2u16, signatories, Some(timepoint)), &[rc_remark()]);
+		assert_eq!(translate(&[approval, Weight::zero().encode()].concat()), Err(()));
+	}
+
+	#[test]
+	fn treasury_calls_are_decoded_and_mapped() {
+		let encoded = rc_call((18, 4), Compact(3u32), &[]);
+		let expected =
+			RuntimeCall::Treasury(pallet_treasury::Call::remove_approval { proposal_id: 3 });
+		assert_eq!(translate(&encoded), Ok(expected));
+	}
+
+	#[test]
+	fn scheduled_calls_are_translated() {
+		let args = (10u32, None::<(u32, u32)>, 5u8);
+		let encoded = rc_call((1, 4), args, &[rc_remark()]);
+
+		let expected = RuntimeCall::Scheduler(pallet_scheduler::Call::schedule_after {
+			after: 10,
+			maybe_periodic: None,
+			priority: 5,
+			call: Box::new(remark()),
+		});
+		assert_eq!(OtherRules::try_convert(&encoded).map_err(|_| ()), Ok(expected));
+	}
+
+	#[test]
+	fn xcm_destinations_are_reanchored() {
+		let message = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin]));
+		let dest = VersionedLocation::from(Location::new(0, [Parachain(2000)]));
+		let encoded = rc_call((99, 0), (dest, message.clone()), &[]);
+
+		let expected = RuntimeCall::PolkadotXcm(pallet_xcm::Call::send {
+			dest: Box::new(VersionedLocation::from(Location::new(1, [Parachain(2000)]))),
+			message: Box::new(message),
+		});
+		assert_eq!(OtherRules::try_convert(&encoded).map_err(|_| ()), Ok(expected));
+	}
+
+	#[test]
+	fn remapped_calls_change_the_call_index() {
+		let encoded = rc_call((50, 1), vec![1u8, 2, 3], &[]);
+		assert_eq!(OtherRules::try_convert(&encoded).map_err(|_| ()), Ok(remark()));
+	}
+}
+
+mod preimage_gc {
+	use super::*;
+	use frame_support::traits::{schedule::DispatchTime, StorePreimage};
+	use pallet_ah_migrator::{
+		preimage_gc::{PreimageGcStage, PreimageSource},
+		AhMigrationStage, MigratedPreimages, MigrationStage, PreimageGc,
+	};
+	use sp_runtime::traits::Hash;
+
+	/// A call that is large enough to be bounded as a lookup.
+	fn large_call(byte: u8) -> RuntimeCall {
+		RuntimeCall::System(frame_system::Call::remark { remark: vec![byte; 256] })
+	}
+
+	fn hash_of(call: &RuntimeCall) -> sp_core::H256 {
+		<Runtime as frame_system::Config>::Hashing::hash_of(call)
+	}
+
+	fn finish_migration() {
+		AhMigrationStage::<Runtime>::put(MigrationStage::MigrationDone);
+	}
+
+	#[test]
+	fn preimages_are_only_collected_after_the_migration() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				AhMigrator::collect_preimages(RuntimeOrigin::signed(ALICE), 10),
+				Error::<Runtime>::MigrationNotFinished
+			);
+
+			finish_migration();
+			assert_noop!(
+				AhMigrator::collect_preimages(RuntimeOrigin::signed(ALICE), 10),
+				Error::<Runtime>::NoPreimagesToCollect
+			);
+		});
+	}
+
+	#[test]
+	fn unused_preimages_are_removed_and_their_deposit_refunded() {
+		new_test_ext().execute_with(|| {
+			finish_migration();
+			let preimage = vec![1u8; 512];
+			assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(ALICE), preimage.clone()));
+			let hash = <Runtime as frame_system::Config>::Hashing::hash(&preimage);
+			AhMigrator::track_migrated_preimage(hash, PreimageSource::RelayChain);
+			assert!(Balances::free_balance(&ALICE) < 100 * UNITS);
+
+			assert_ok!(AhMigrator::collect_preimages(RuntimeOrigin::signed(BOB), 10));
+
+			System::assert_has_event(
+				AhMigratorEvent::<Runtime>::PreimageCollected { hash, depositor: Some(ALICE) }
+					.into(),
+			);
+			System::assert_last_event(AhMigratorEvent::<Runtime>::PreimageGcRoundCompleted.into());
+			assert!(!MigratedPreimages::<Runtime>::contains_key(hash));
+			assert!(!pallet_preimage::RequestStatusFor::<Runtime>::contains_key(hash));
+			assert_eq!(Balances::free_balance(&ALICE), 100 * UNITS);
+			// Nothing is left to collect.
+			assert_noop!(
+				AhMigrator::collect_preimages(RuntimeOrigin::signed(BOB), 10),
+				Error::<Runtime>::NoPreimagesToCollect
+			);
+		});
+	}
+
+	#[test]
+	fn scheduled_preimages_are_kept() {
+		new_test_ext().execute_with(|| {
+			finish_migration();
+			let call = large_call(1);
+			assert_ok!(Scheduler::schedule(
+				RuntimeOrigin::root(),
+				100,
+				None,
+				0,
+				Box::new(call.clone())
+			));
+			let hash = hash_of(&call);
+			AhMigrator::track_migrated_preimage(hash, PreimageSource::Translation);
+
+			assert_ok!(AhMigrator::collect_preimages(RuntimeOrigin::signed(BOB), 10));
+
+			System::assert_last_event(AhMigratorEvent::<Runtime>::PreimageGcRoundCompleted.into());
+			assert!(MigratedPreimages::<Runtime>::contains_key(hash));
+			assert!(pallet_preimage::RequestStatusFor::<Runtime>::contains_key(hash));
+		});
+	}
+
+	#[test]
+	fn referendums_submitted_during_a_round_keep_their_proposal() {
+		new_test_ext().execute_with(|| {
+			finish_migration();
+			let call = large_call(2);
+			let hash = hash_of(&call);
+			let proposal = <Preimage as StorePreimage>::bound(call).unwrap();
+			AhMigrator::track_migrated_preimage(hash, PreimageSource::Translation);
+
+			// Start the round, mark the referendums and the agendas.
+			assert_ok!(AhMigrator::collect_preimages(RuntimeOrigin::signed(BOB), 3));
+			assert_eq!(PreimageGc::<Runtime>::get().stage, PreimageGcStage::Sweep { last: None });
+
+			assert_ok!(Referenda::submit(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(OriginCaller::system(frame_system::RawOrigin::Root)),
+				proposal,
+				DispatchTime::After(10),
+			));
+
+			assert_ok!(AhMigrator::collect_preimages(RuntimeOrigin::signed(BOB), 10));
+
+			System::assert_last_event(AhMigratorEvent::<Runtime>::PreimageGcRoundCompleted.into());
+			assert!(MigratedPreimages::<Runtime>::contains_key(hash));
+			assert!(pallet_preimage::RequestStatusFor::<Runtime>::contains_key(hash));
+		});
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	#[test]
+	fn preimage_gc_benchmarks_work() {
+		use pallet_ah_migrator::benchmarking::benchmarks;
+
+		new_test_ext().execute_with(|| benchmarks::test_preimage_gc_mark_referendum::<Runtime>());
+		new_test_ext().execute_with(|| benchmarks::test_preimage_gc_mark_agenda::<Runtime>(50));
+		new_test_ext().execute_with(|| benchmarks::test_preimage_gc_sweep::<Runtime>());
+	}
+}
+
+mod replay {
+	use super::*;
+	use codec::Encode;
+	use frame_support::traits::ReservableCurrency;
+	use pallet_ah_migrator::replay::{read_message_log, replay_messages, LoggedMessage};
+	use pallet_rc_migrator::fast_unstake::RcFastUnstakeRequest;
+	use sp_runtime::DispatchError;
+
+	fn logged(rc_block: u32, call: pallet_ah_migrator::Call<Runtime>) -> LoggedMessage {
+		LoggedMessage { rc_block, call: RuntimeCall::AhMigrator(call).encode() }
+	}
+
+	/// A batch, an undecodable call and a call that fails.
+	fn message_log() -> Vec<LoggedMessage> {
+		let messages = vec![RcFastUnstakeRequest { stash: ALICE, deposit: UNITS }];
+		vec![
+			logged(1, pallet_ah_migrator::Call::receive_fast_unstake_messages { messages }),
+			LoggedMessage { rc_block: 2, call: vec![255, 255] },
+			logged(3, pallet_ah_migrator::Call::collect_preimages { max_steps: 1 }),
+		]
+	}
+
+	#[test]
+	fn logged_messages_are_dispatched_in_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Balances::reserve(&ALICE, UNITS));
+
+			let replayed = replay_messages::<Runtime>(&message_log(), 0..3);
+
+			assert_eq!(Balances::reserved_balance(&ALICE), 0);
+			let outcomes = replayed
+				.iter()
+				.map(|replayed| (replayed.index, replayed.rc_block, replayed.call, replayed.result))
+				.collect::<Vec<_>>();
+			assert_eq!(
+				outcomes,
+				vec![
+					(0, 1, Some("receive_fast_unstake_messages"), Ok(())),
+					(1, 2, None, Err(DispatchError::Other("Undecodable call"))),
+					(2, 3, Some("collect_preimages"), Err(DispatchError::BadOrigin)),
+				]
+			);
+			assert_eq!(
+				replayed[0].to_string(),
+				"#0 (RC block 1) receive_fast_unstake_messages: Ok(())"
+			);
+		});
+	}
+
+	#[test]
+	fn ranges_of_the_log_are_replayed_on_their_own() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Balances::reserve(&ALICE, UNITS));
+
+			let replayed = replay_messages::<Runtime>(&message_log(), 1..3);
+
+			// The batch is not dispatched.
+			assert_eq!(Balances::reserved_balance(&ALICE), UNITS);
+			let indices = replayed.iter().map(|replayed| replayed.index).collect::<Vec<_>>();
+			assert_eq!(indices, vec![1, 2]);
+		});
+	}
+
+	/// Replay the message log at `AHM_LOG` into the state of `SNAP`, see
+	/// [`pallet_ah_migrator::replay`].
+	///
+	/// The log is recorded with the `record_migration_messages` test of the Relay Chain runtime.
+	/// `AHM_RANGE`, e.g. `100..200`, replays only a range of the log to bisect a failing
+	/// integration.
+	#[tokio::test]
+	#[ignore = "this test is meant to be executed manually"]
+	async fn replay_message_log() {
+		use remote_externalities::{Builder, Mode, OfflineConfig};
+		use std::env::var;
+
+		sp_tracing::try_init_simple();
+		let state_snapshot = var("SNAP").expect("`SNAP` is the Asset Hub snapshot").into();
+		let path = var("AHM_LOG").unwrap_or("migration.ahmlog".to_string());
+		let messages = read_message_log(&path).unwrap();
+		let range = match var("AHM_RANGE") {
+			Ok(range) => {
+				let (start, end) = range.split_once("..").expect("`AHM_RANGE` is `start..end`");
+				start.parse().unwrap()..end.parse().unwrap()
+			},
+			Err(_) => 0..messages.len(),
+		};
+
+		let mut ext = Builder::<Block>::default()
+			.mode(Mode::Offline(OfflineConfig { state_snapshot }))
+			.build()
+			.await
+			.unwrap();
+		ext.execute_with(|| {
+			let replayed = replay_messages::<Runtime>(&messages, range);
+			replayed.iter().for_each(|replayed| log::info!(target: "runtime", "{replayed}"));
+
+			let failed = replayed.iter().filter(|replayed| replayed.result.is_err()).count();
+			assert_eq!(failed, 0, "{failed} of {} messages failed", replayed.len());
+		});
+	}
+}
+
+mod audit {
+	use super::*;
+	use frame_support::{dispatch::GetDispatchInfo, traits::Hooks};
+	use pallet_ah_migrator::{
+		audit::{AccountComponent, AuditTrail},
+		AhMigrationStage, AuditDigest, MigrationStage, WeightInfo,
+	};
+	use pallet_rc_migrator::accounts::Account;
+
+	fn events() -> Vec<RuntimeEvent> {
+		System::events().into_iter().map(|record| record.event).collect()
+	}
+
+	#[test]
+	fn audit_is_disabled_by_default() {
+		new_test_ext().execute_with(|| {
+			AhMigrator::audit(&ALICE, AccountComponent::Balance { total: UNITS });
+
+			assert!(!AuditDigest::<Runtime>::exists());
+			assert!(System::events().is_empty());
+		});
+	}
+
+	#[test]
+	fn audit_events_are_indexed_by_account() {
+		new_test_ext().execute_with(|| {
+			AccountAudit::set(&true);
+			let balance = AccountComponent::Balance { total: UNITS };
+			let lock = AccountComponent::Lock { id: *b"vesting ", amount: UNITS };
+			let deposit = AccountComponent::MultisigDeposit { deposit: 2 * UNITS };
+			AhMigrator::audit(&ALICE, balance.clone());
+			AhMigrator::audit(&BOB, deposit.clone());
+			AhMigrator::audit(&ALICE, lock.clone());
+
+			let mut trail = AuditTrail::<Runtime>::default();
+			assert_eq!(trail.index_block(events()), Ok(()));
+
+			assert_eq!(trail.account(&ALICE), &[(1, balance), (1, lock)]);
+			assert_eq!(trail.account(&BOB), &[(1, deposit)]);
+			assert!(trail.account(&AccountId::new([3; 32])).is_empty());
+		});
+	}
+
+	#[test]
+	fn missing_audit_events_are_detected() {
+		new_test_ext().execute_with(|| {
+			AccountAudit::set(&true);
+			let balance = AccountComponent::Balance { total: UNITS };
+			AhMigrator::audit(&ALICE, balance.clone());
+			AhMigrator::audit(&BOB, AccountComponent::Balance { total: 2 * UNITS });
+
+			// The indexer missed the event of BOB.
+			let mut trail = AuditTrail::<Runtime>::default();
+			let events = events().into_iter().take(1);
+			let mismatch = trail.index_block(events).unwrap_err();
+
+			assert_eq!(mismatch.block, 1);
+			assert_eq!(mismatch.expected, AuditDigest::<Runtime>::get());
+			assert_ne!(mismatch.indexed, mismatch.expected);
+			// The events that were found are indexed nonetheless.
+			assert_eq!(trail.account(&ALICE), &[(1, balance)]);
+		});
+	}
+
+	#[test]
+	fn audit_digest_is_reset_every_block() {
+		new_test_ext().execute_with(|| {
+			AccountAudit::set(&true);
+			AhMigrationStage::<Runtime>::put(MigrationStage::DataMigrationOngoing);
+			AhMigrator::audit(&ALICE, AccountComponent::Balance { total: UNITS });
+			assert!(AuditDigest::<Runtime>::exists());
+
+			System::reset_events();
+			System::set_block_number(2);
+			AhMigrator::on_initialize(2);
+
+			assert!(!AuditDigest::<Runtime>::exists());
+			let mut trail = AuditTrail::<Runtime>::default();
+			assert_eq!(trail.index_block(events()), Ok(()));
+			assert!(trail.account(&ALICE).is_empty());
+
+			// Outside of the migration the digest is left alone.
+			AhMigrationStage::<Runtime>::put(MigrationStage::MigrationDone);
+			AhMigrator::audit(&ALICE, AccountComponent::Balance { total: UNITS });
+			System::set_block_number(3);
+			AhMigrator::on_initialize(3);
+			assert!(AuditDigest::<Runtime>::exists());
+		});
+	}
+
+	#[test]
+	fn audit_is_weighed_when_enabled() {
+		new_test_ext().execute_with(|| {
+			let account = |who| Account {
+				who,
+				free: UNITS,
+				reserved: 0,
+				frozen: 0,
+				holds: Default::default(),
+				freezes: Default::default(),
+				locks: Default::default(),
+				unnamed_reserve: 0,
+				consumers: 0,
+				providers: 1,
+			};
+			let call = pallet_ah_migrator::Call::<Runtime>::receive_accounts {
+				accounts: vec![account(ALICE), account(BOB)],
+			};
+			let unaudited = call.get_dispatch_info().call_weight;
+
+			AccountAudit::set(&true);
+			let audited = call.get_dispatch_info().call_weight;
+
+			// One balance component per account.
+			assert_eq!(
+				audited - unaudited,
+				<Runtime as pallet_ah_migrator::Config>::AhWeightInfo::audit_account_components(2)
+			);
+		});
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	#[test]
+	fn audit_benchmark_works() {
+		use pallet_ah_migrator::benchmarking::benchmarks;
+
+		new_test_ext().execute_with(|| benchmarks::test_audit_account_components::<Runtime>(255));
+	}
+}
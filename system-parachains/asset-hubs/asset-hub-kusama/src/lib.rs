@@ -1292,10 +1292,23 @@ impl pallet_ah_ops::Config for Runtime {
 	type MigrationCompletion = pallet_rc_migrator::types::MigrationCompletion<AhMigrator>;
 	type TreasuryPreMigrationAccount = xcm_config::PreMigrationRelayTreasuryPalletAccount;
 	type TreasuryPostMigrationAccount = xcm_config::PostMigrationTreasuryAccount;
+	type SiblingParaOrigin = ah_migration::EnsureSiblingPara;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ah_migration::AhOpsBenchmarkHelper;
 }
 
 parameter_types! {
 	pub const DmpQueuePriorityPattern: (BlockNumber, BlockNumber) = (18, 2);
+	/// Stay in the cool-off if a migrated total deviates by more than 1 KSM.
+	///
+	/// Both chains count the same items, so a discrepancy only comes from items that failed to
+	/// integrate. This tolerates failed accounts with dust balances, which can not be minted
+	/// below the existential deposit, but holds the migration for any failed account or vesting
+	/// schedule of more than 1 KSM until the quarantine is resolved.
+	pub const ReconciliationThreshold: Option<Balance> = Some(UNITS);
+	/// Whether to record the audit trail of the migrated accounts. It is weighed into the
+	/// `receive_*` calls and can be enabled by governance before the migration starts.
+	pub storage AccountAudit: bool = false;
 }
 
 impl pallet_ah_migrator::Config for Runtime {
@@ -1303,6 +1316,8 @@ impl pallet_ah_migrator::Config for Runtime {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type PortableHoldReason = pallet_rc_migrator::types::PortableHoldReason;
 	type PortableFreezeReason = pallet_rc_migrator::types::PortableFreezeReason;
+	type HoldReasonRegistry = ah_migration::HoldReasonRegistry;
+	type FreezeReasonRegistry = ah_migration::FreezeReasonRegistry;
 	type RuntimeEvent = RuntimeEvent;
 	type AdminOrigin = EitherOfDiverse<
 		EnsureRoot<AccountId>,
@@ -1330,6 +1345,9 @@ impl pallet_ah_migrator::Config for Runtime {
 	type AhPostMigrationCalls = ah_migration::call_filter::CallsEnabledAfterMigration;
 	type MessageQueue = MessageQueue;
 	type DmpQueuePriorityPattern = DmpQueuePriorityPattern;
+	type ReconciliationThreshold = ReconciliationThreshold;
+	type PreimagesInUse = ah_migration::WhitelistedPreimages;
+	type AccountAudit = AccountAudit;
 	#[cfg(feature = "kusama-ahm")]
 	type KusamaConfig = Runtime;
 	#[cfg(feature = "kusama-ahm")]
@@ -1708,7 +1726,7 @@ pub mod migrations {
 	use super::*;
 
 	/// Unreleased migrations. Add new ones here:
-	pub type Unreleased = ();
+	pub type Unreleased = (pallet_ah_migrator::migrations::Migrations<Runtime>,);
 
 	/// Migrations/checks that do not need to be versioned and can run on every update.
 	pub type Permanent = pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>;
@@ -20,9 +20,15 @@ extern crate alloc;
 
 use super::*;
 use alloc::boxed::Box;
-use codec::DecodeAll;
 use frame_support::pallet_prelude::{PalletInfoAccess, TypeInfo};
-use pallet_ah_migrator::LOG_TARGET;
+use pallet_ah_migrator::{
+	account_translation::TranslateAccountRcToAh,
+	call_translation::{
+		MapRcCall, MultisigCalls, ProxyCalls, RcToAhCallTranslator, RemapPallets, SchedulerCalls,
+		TypedCall, UtilityCalls, XcmCalls,
+	},
+	LOG_TARGET,
+};
 use parachains_common::pay::VersionedLocatableAccount;
 use polkadot_runtime_common::impls::{LocatableAssetConverter, VersionedLocatableAsset};
 use sp_core::Get;
@@ -155,27 +161,6 @@ impl TryConvert<RcPalletsOrigin, OriginCaller> for RcToAhPalletsOrigin {
 	}
 }
 
-/// Relay Chain Runtime Call.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcRuntimeCall {
-	#[codec(index = 0u8)]
-	System(frame_system::Call<Runtime>),
-	#[codec(index = 1u8)]
-	Scheduler(RcSchedulerCall),
-	#[codec(index = 19u8)]
-	Treasury(RcTreasuryCall),
-	#[codec(index = 21u8)]
-	Referenda(pallet_referenda::Call<Runtime>),
-	#[codec(index = 26u8)]
-	Utility(RcUtilityCall),
-	#[codec(index = 34u8)]
-	Bounties(pallet_bounties::Call<Runtime>),
-	#[codec(index = 38u8)]
-	ChildBounties(pallet_child_bounties::Call<Runtime>),
-	#[codec(index = 99u8)]
-	XcmPallet(RcXcmCall),
-}
-
 /// Relay Chain Treasury Call obtained from cargo expand.
 #[allow(non_camel_case_types)]
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -211,132 +196,76 @@ pub enum RcTreasuryCall {
 	void_spend { index: pallet_treasury::SpendIndex },
 }
 
-/// Relay Chain Utility Call obtained from cargo expand.
-///
-/// The variants that are not generally used in Governance are not included.
-#[allow(non_camel_case_types)]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcUtilityCall {
-	/// Send a batch of dispatch calls.
-	#[codec(index = 0u8)]
-	batch { calls: Vec<RcRuntimeCall> },
-	/// Send a batch of dispatch calls and atomically execute them.
-	#[codec(index = 2u8)]
-	batch_all { calls: Vec<RcRuntimeCall> },
-	/// Dispatches a function call with a provided origin.
-	#[codec(index = 3u8)]
-	dispatch_as { as_origin: Box<RcPalletsOrigin>, call: Box<RcRuntimeCall> },
-	/// Send a batch of dispatch calls.
-	/// Unlike `batch`, it allows errors and won't interrupt.
-	#[codec(index = 4u8)]
-	force_batch { calls: Vec<RcRuntimeCall> },
-}
-
-/// Relay Chain Scheduler Call.
-///
-/// The variants that are not generally used in Governance are not included.
-#[allow(non_camel_case_types)]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcSchedulerCall {
-	#[codec(index = 4u8)]
-	schedule_after {
-		after: BlockNumber,
-		maybe_periodic: Option<frame_support::traits::schedule::Period<BlockNumber>>,
-		priority: frame_support::traits::schedule::Priority,
-		call: Box<RcRuntimeCall>,
-	},
-}
-
-/// Relay Chain XCM Call.
-///
-/// The variants that are not generally used in Governance are not included.
-#[allow(non_camel_case_types)]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum RcXcmCall {
-	#[codec(index = 0u8)]
-	send { dest: Box<VersionedLocation>, message: Box<VersionedXcm<()>> },
-	#[codec(index = 3u8)]
-	execute { message: Box<VersionedXcm<()>>, max_weight: Weight },
-	#[codec(index = 8u8)]
-	limited_reserve_transfer_assets {
-		dest: Box<VersionedLocation>,
-		beneficiary: Box<VersionedLocation>,
-		assets: Box<VersionedAssets>,
-		fee_asset_item: u32,
-		weight_limit: WeightLimit,
-	},
+parameter_types! {
+	pub const RcSchedulerIndex: u8 = 1;
+	pub const RcTreasuryIndex: u8 = 19;
+	pub const RcUtilityIndex: u8 = 26;
+	pub const RcProxyIndex: u8 = 29;
+	pub const RcMultisigIndex: u8 = 30;
+	pub const RcXcmIndex: u8 = 99;
+	pub AhSchedulerIndex: u8 = <Scheduler as PalletInfoAccess>::index() as u8;
+	pub AhUtilityIndex: u8 = <Utility as PalletInfoAccess>::index() as u8;
+	pub AhProxyIndex: u8 = <Proxy as PalletInfoAccess>::index() as u8;
+	pub AhMultisigIndex: u8 = <Multisig as PalletInfoAccess>::index() as u8;
+	pub AhXcmIndex: u8 = <PolkadotXcm as PalletInfoAccess>::index() as u8;
+	/// Relative location of Asset Hub from the perspective of the Relay Chain.
+	pub RcAhLocation: Location = Location::new(0, [Parachain(1000)]);
+	/// Absolute location of the Relay Chain.
+	pub RcUniversalLocation: InteriorLocation = [GlobalConsensus(NetworkId::Polkadot)].into();
+	/// Relay Chain pallets whose calls are the same on Asset Hub, with their Asset Hub index.
+	pub RcToAhPalletIndices: Vec<(u8, u8)> = vec![
+		(0, <System as PalletInfoAccess>::index() as u8),
+		(21, <Referenda as PalletInfoAccess>::index() as u8),
+		(34, <Bounties as PalletInfoAccess>::index() as u8),
+		(38, <ChildBounties as PalletInfoAccess>::index() as u8),
+	];
 }
 
 /// Convert an encoded Relay Chain Call to a local AH one.
-pub struct RcToAhCall;
-impl<'a> TryConvert<&'a [u8], RuntimeCall> for RcToAhCall {
-	fn try_convert(mut a: &'a [u8]) -> Result<RuntimeCall, &'a [u8]> {
-		let rc_call = match RcRuntimeCall::decode_all(&mut a) {
-			Ok(rc_call) => rc_call,
-			Err(e) => {
-				log::error!(target: LOG_TARGET, "Failed to decode RC call with error: {e:?}");
-				return Err(a)
-			},
-		};
-		Self::map(rc_call).map_err(|_| a)
-	}
-}
-impl RcToAhCall {
-	fn map(rc_call: RcRuntimeCall) -> Result<RuntimeCall, ()> {
+pub type RcToAhCall = RcToAhCallTranslator<
+	RuntimeCall,
+	(
+		UtilityCalls<
+			RcUtilityIndex,
+			AhUtilityIndex,
+			RcPalletsOrigin,
+			OriginCaller,
+			RcToAhPalletsOrigin,
+		>,
+		ProxyCalls<
+			RcProxyIndex,
+			AhProxyIndex,
+			RcProxyType,
+			ProxyType,
+			RcToProxyType,
+			TranslateAccountRcToAh<Runtime>,
+		>,
+		MultisigCalls<RcMultisigIndex, AhMultisigIndex>,
+		SchedulerCalls<RcSchedulerIndex, AhSchedulerIndex>,
+		XcmCalls<RcXcmIndex, AhXcmIndex, RcAhLocation, RcUniversalLocation>,
+		TypedCall<RcTreasuryIndex, RcTreasuryCall, RcToAhTreasuryCall>,
+		RemapPallets<RcToAhPalletIndices>,
+	),
+>;
+
+/// Convert a Relay Chain Treasury call to an Asset Hub one.
+pub struct RcToAhTreasuryCall;
+impl MapRcCall<RcTreasuryCall, RuntimeCall> for RcToAhTreasuryCall {
+	fn map(rc_call: RcTreasuryCall) -> Result<RuntimeCall, ()> {
 		match rc_call {
-			RcRuntimeCall::System(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
+			RcTreasuryCall::spend { asset_kind, amount, beneficiary, valid_from } => {
+				let beneficiary =
+					pallet_ah_migrator::Pallet::<Runtime>::translate_beneficiary_location(
+						*beneficiary,
+					)
+					.map_err(|err| {
 						log::error!(
 							target: LOG_TARGET,
-							"Failed to decode RC Bounties call to AH System call: {err:?}",
+							"Failed to translate RC treasury spend beneficiary: {err:?}",
 						);
 					})?;
-				Ok(RuntimeCall::System(call))
-			},
-			RcRuntimeCall::Scheduler(RcSchedulerCall::schedule_after {
-				after,
-				maybe_periodic,
-				priority,
-				call,
-			}) => Ok(RuntimeCall::Scheduler(pallet_scheduler::Call::<Runtime>::schedule_after {
-				after,
-				maybe_periodic,
-				priority,
-				call: Box::new(Self::map(*call)?),
-			})),
-			RcRuntimeCall::Utility(RcUtilityCall::dispatch_as { as_origin, call }) => {
-				let origin = RcToAhPalletsOrigin::try_convert(*as_origin).map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to decode RC dispatch_as origin: {err:?}"
-					);
-				})?;
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::dispatch_as {
-					as_origin: Box::new(origin),
-					call: Box::new(Self::map(*call)?),
-				}))
-			},
-			RcRuntimeCall::Utility(RcUtilityCall::batch { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::batch {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Utility(RcUtilityCall::batch_all { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::batch_all {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Utility(RcUtilityCall::force_batch { calls }) =>
-				Ok(RuntimeCall::Utility(pallet_utility::Call::<Runtime>::force_batch {
-					calls: calls.into_iter().map(Self::map).collect::<Result<Vec<_>, _>>()?,
-				})),
-			RcRuntimeCall::Treasury(RcTreasuryCall::spend {
-				asset_kind,
-				amount,
-				beneficiary,
-				valid_from,
-			}) => {
 				let (asset_kind, beneficiary) =
-					RcToAhTreasurySpend::convert((*asset_kind, *beneficiary))?;
+					RcToAhTreasurySpend::convert((*asset_kind, beneficiary))?;
 				Ok(RuntimeCall::Treasury(pallet_treasury::Call::<Runtime>::spend {
 					asset_kind: Box::new(asset_kind),
 					amount,
@@ -344,7 +273,7 @@ impl RcToAhCall {
 					valid_from,
 				}))
 			},
-			RcRuntimeCall::Treasury(inner_call) => {
+			inner_call => {
 				let call =
 					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
 						log::error!(
@@ -354,154 +283,6 @@ impl RcToAhCall {
 					})?;
 				Ok(RuntimeCall::Treasury(call))
 			},
-			RcRuntimeCall::Referenda(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC Referenda call to AH Referenda call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::Referenda(call))
-			},
-			RcRuntimeCall::Bounties(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC Bounties call to AH Bounties call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::Bounties(call))
-			},
-			RcRuntimeCall::ChildBounties(inner_call) => {
-				let call =
-					inner_call.using_encoded(|mut e| Decode::decode(&mut e)).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to decode RC ChildBounties call to AH ChildBounties call: {err:?}",
-						);
-					})?;
-				Ok(RuntimeCall::ChildBounties(call))
-			},
-			RcRuntimeCall::XcmPallet(RcXcmCall::send { dest, message }) => {
-				// reanchor the destination and assets, since they are relative to the local
-				// chain, which was previously the Relay Chain for these calls.
-
-				// from the perspective of the Relay Chain.
-				let universal_location: InteriorLocation =
-					[GlobalConsensus(NetworkId::Polkadot)].into();
-				// from the perspective of the Relay Chain.
-				let ah_location = Location::new(0, [Parachain(1000)]);
-
-				// reanchore the destination
-				// example:
-				// from Location: Location { parents: 0, interior: X1(Parachain(2034)) }
-				//   to Location: Location { parents: 1, interior: X1(Parachain(2034)) }
-				let dest: xcm::latest::Location = (*dest).try_into().map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to convert versioned destination to the latest version: {err:?}",
-					);
-				})?;
-				let dest = dest.reanchored(&ah_location, &universal_location).map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to reanchor destination: {err:?}",
-					);
-				})?;
-
-				Ok(RuntimeCall::PolkadotXcm(pallet_xcm::Call::<Runtime>::send {
-					dest: Box::new(dest.into()),
-					message,
-				}))
-			},
-			RcRuntimeCall::XcmPallet(RcXcmCall::execute { message, max_weight }) => {
-				// Go through the instructions and reanchor destinations and assets, since they
-				// are relative to the local chain, which was previously the Relay Chain.
-
-				// Absolute location of the Relay Chain.
-				let universal_location: InteriorLocation =
-					[GlobalConsensus(NetworkId::Polkadot)].into();
-				// Relative location of Asset Hub from the perspective of the Relay Chain.
-				let ah_location = Location::new(0, [Parachain(1000)]);
-
-				// Convert the versioned XCM to latest version for reanchoring
-				let xcm: xcm::latest::Xcm<()> = (*message).try_into().map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to convert versioned XCM to latest version: {err:?}",
-					);
-				})?;
-
-				// Reanchor the XCM message
-				let reanchored_xcm =
-					xcm_mapping::reanchor_xcm(xcm, &ah_location, &universal_location)?;
-
-				Ok(RuntimeCall::PolkadotXcm(pallet_xcm::Call::<Runtime>::execute {
-					message: Box::new(VersionedXcm::from(reanchored_xcm.into())),
-					max_weight,
-				}))
-			},
-			RcRuntimeCall::XcmPallet(RcXcmCall::limited_reserve_transfer_assets {
-				dest,
-				beneficiary,
-				assets,
-				fee_asset_item,
-				weight_limit,
-			}) => {
-				// reanchor the destination and assets, since they are relative to the local
-				// chain, which was previously the Relay Chain for these calls.
-
-				// from the perspective of the Relay Chain.
-				let universal_location: InteriorLocation =
-					[GlobalConsensus(NetworkId::Polkadot)].into();
-				// from the perspective of the Relay Chain.
-				let ah_location = Location::new(0, [Parachain(1000)]);
-
-				// reanchore the destination
-				// example:
-				// from Location: Location { parents: 0, interior: X1(Parachain(2034)) }
-				//   to Location: Location { parents: 1, interior: X1(Parachain(2034)) }
-				let dest: xcm::latest::Location = (*dest).try_into().map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to convert versioned destination to the latest version: {err:?}",
-					);
-				})?;
-				let dest = dest.reanchored(&ah_location, &universal_location).map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to reanchor destination: {err:?}",
-					);
-				})?;
-
-				// reanchore the assets
-				let assets: xcm::latest::Assets = (*assets).try_into().map_err(|err| {
-					log::error!(
-						target: LOG_TARGET,
-						"Failed to convert versioned assets to the latest version: {err:?}",
-					);
-				})?;
-				let assets =
-					assets.reanchored(&ah_location, &universal_location).map_err(|err| {
-						log::error!(
-							target: LOG_TARGET,
-							"Failed to reanchor assets: {err:?}",
-						);
-					})?;
-
-				Ok(RuntimeCall::PolkadotXcm(
-					pallet_xcm::Call::<Runtime>::limited_reserve_transfer_assets {
-						dest: Box::new(dest.into()),
-						// the `beneficiary` is relative to the `dest`, no needs to reanchor it.
-						beneficiary,
-						assets: Box::new(assets.into()),
-						fee_asset_item,
-						weight_limit,
-					},
-				))
-			},
 		}
 	}
 }
@@ -4,6 +4,12 @@ pub mod types;
 pub mod entropy;
 pub mod payment;
 pub mod weights;
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 use frame_support::{
     pallet_prelude::*,
@@ -15,15 +21,59 @@ use types::*;
 use entropy::*;
 use payment::LocalPay;
 
+/// Shard ownership and shard-level configuration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ShardInfo<AccountId, Balance> {
+    /// Account allowed to administer the shard.
+    pub admin: AccountId,
+    /// Rate overriding the worker's own hourly rate for tasks in this shard.
+    pub hourly_rate: Option<Balance>,
+}
+
+/// A worker's profile together with its earnings and shard memberships.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct WorkerSummary<Profile, Balance> {
+    pub profile: Profile,
+    /// Total paid out to the worker by `execute_tasks`.
+    pub earnings: Balance,
+    pub shards: Vec<u32>,
+}
+
+/// A shard's configuration, roster and the tasks still queued for it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ShardSummary<AccountId, Balance> {
+    pub info: ShardInfo<AccountId, Balance>,
+    pub workers: Vec<AccountId>,
+    /// Queued `(hours, optional worker)` tasks for this shard.
+    pub pending_tasks: Vec<(u64, Option<AccountId>)>,
+}
+
+/// A page of the global task queue.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TaskQueuePage<AccountId> {
+    /// Total number of queued tasks.
+    pub depth: u32,
+    pub tasks: Vec<(u32, u64, Option<AccountId>)>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type Currency: Currency<Self::AccountId>;
         type Moment: Parameter + AtLeast32BitUnsigned + Copy + Default;
+        /// Origin allowed to create shards; the returned account becomes the shard admin.
+        type ShardAdminOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
     }
 
     // ------------------------
@@ -51,9 +101,48 @@ pub mod pallet {
     >;
 
     #[pallet::storage]
-    #[pallet::getter(fn task_queue)]
-    /// Queue of tasks: (shard_id, hours, optional worker)
-    pub type TaskQueue<T: Config> = StorageVec<(u32, u64, Option<T::AccountId>), ValueQuery>;
+    /// Shard ID → admin and shard-level rate
+    pub type Shards<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        ShardInfo<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance>,
+        OptionQuery
+    >;
+
+    #[pallet::storage]
+    /// Worker → shards the worker is assigned to
+    pub type WorkerShards<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Vec<u32>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    /// Worker → total paid out by `execute_tasks`
+    pub type WorkerEarnings<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        <T::Currency as Currency<T::AccountId>>::Balance,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    /// Queue position → task: (shard_id, hours, optional worker)
+    pub type TaskQueue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        (u32, u64, Option<T::AccountId>),
+        OptionQuery
+    >;
+
+    #[pallet::storage]
+    /// Number of queued tasks, the positions `0..depth` of `TaskQueue` are occupied
+    pub type TaskQueueDepth<T: Config> = StorageValue<_, u32, ValueQuery>;
 
     // ------------------------
     // Events
@@ -65,6 +154,14 @@ pub mod pallet {
         TaskAssigned(T::AccountId, u32),
         TaskCompleted(T::AccountId, u32, <T::Currency as Currency<T::AccountId>>::Balance),
         PTOTaken(T::AccountId, u32),
+        ShardCreated(u32, T::AccountId),
+        ShardClosed(u32),
+        ShardRateSet(u32, Option<<T::Currency as Currency<T::AccountId>>::Balance>),
+        WorkerRemovedFromShard(T::AccountId, u32),
+        WorkerLeftShard(T::AccountId, u32),
+        WorkerDeregistered(T::AccountId),
+        /// A queued task was dropped because its shard has no workers.
+        TaskDropped(u32, u64),
     }
 
     // ------------------------
@@ -77,6 +174,10 @@ pub mod pallet {
         Overflow,
         ShardNotFound,
         NoWorkersInShard,
+        ShardAlreadyExists,
+        NotShardAdmin,
+        AlreadyInShard,
+        NotInShard,
     }
 
     // ------------------------
@@ -111,30 +212,121 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::weight(10_000)]
+        pub fn create_shard(
+            origin: OriginFor<T>,
+            shard_id: u32,
+            hourly_rate: Option<<T::Currency as Currency<T::AccountId>>::Balance>,
+        ) -> DispatchResult {
+            let admin = T::ShardAdminOrigin::ensure_origin(origin)?;
+            ensure!(!Shards::<T>::contains_key(shard_id), Error::<T>::ShardAlreadyExists);
+
+            Shards::<T>::insert(shard_id, ShardInfo { admin: admin.clone(), hourly_rate });
+            Self::deposit_event(Event::ShardCreated(shard_id, admin));
+            Ok(())
+        }
+
+        /// Close a shard, unassigning all of its workers. Queued tasks for the shard are
+        /// dropped with `TaskDropped` when `execute_tasks` reaches them, since it has no workers
+        /// left.
+        #[pallet::weight(20_000)]
+        pub fn close_shard(origin: OriginFor<T>, shard_id: u32) -> DispatchResult {
+            Self::ensure_shard_admin(origin, shard_id)?;
+
+            for worker in ShardWorkers::<T>::take(shard_id) {
+                Self::remove_from_worker_index(&worker, shard_id);
+            }
+            Shards::<T>::remove(shard_id);
+            Self::deposit_event(Event::ShardClosed(shard_id));
+            Ok(())
+        }
+
+        /// Set the shard-level hourly rate. When set it overrides the worker's own rate for
+        /// tasks executed in this shard.
+        #[pallet::weight(10_000)]
+        pub fn set_shard_rate(
+            origin: OriginFor<T>,
+            shard_id: u32,
+            hourly_rate: Option<<T::Currency as Currency<T::AccountId>>::Balance>,
+        ) -> DispatchResult {
+            Self::ensure_shard_admin(origin, shard_id)?;
+
+            Shards::<T>::mutate(shard_id, |maybe_shard| {
+                if let Some(shard) = maybe_shard {
+                    shard.hourly_rate = hourly_rate;
+                }
+            });
+            Self::deposit_event(Event::ShardRateSet(shard_id, hourly_rate));
+            Ok(())
+        }
+
         #[pallet::weight(10_000)]
         pub fn assign_worker_to_shard(
             origin: OriginFor<T>,
             shard_id: u32,
             worker: T::AccountId,
         ) -> DispatchResult {
-            let _ = ensure_signed(origin)?;
+            Self::ensure_shard_admin(origin, shard_id)?;
             ensure!(Workers::<T>::contains_key(&worker), Error::<T>::NotRegistered);
+            ensure!(
+                !ShardWorkers::<T>::get(shard_id).contains(&worker),
+                Error::<T>::AlreadyInShard
+            );
 
             ShardWorkers::<T>::mutate(shard_id, |list| list.push(worker.clone()));
+            WorkerShards::<T>::mutate(&worker, |shards| shards.push(shard_id));
             Self::deposit_event(Event::TaskAssigned(worker, shard_id));
             Ok(())
         }
 
+        #[pallet::weight(10_000)]
+        pub fn remove_worker_from_shard(
+            origin: OriginFor<T>,
+            shard_id: u32,
+            worker: T::AccountId,
+        ) -> DispatchResult {
+            Self::ensure_shard_admin(origin, shard_id)?;
+            Self::unassign(shard_id, &worker)?;
+
+            Self::deposit_event(Event::WorkerRemovedFromShard(worker, shard_id));
+            Ok(())
+        }
+
+        #[pallet::weight(10_000)]
+        pub fn leave_shard(origin: OriginFor<T>, shard_id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::unassign(shard_id, &who)?;
+
+            Self::deposit_event(Event::WorkerLeftShard(who, shard_id));
+            Ok(())
+        }
+
+        /// Deregister the calling worker, leaving every shard it is assigned to. Its earnings
+        /// are kept.
+        #[pallet::weight(20_000)]
+        pub fn deregister_worker(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Workers::<T>::contains_key(&who), Error::<T>::NotRegistered);
+
+            for shard_id in WorkerShards::<T>::take(&who) {
+                ShardWorkers::<T>::mutate(shard_id, |list| list.retain(|w| *w != who));
+                Self::deposit_event(Event::WorkerLeftShard(who.clone(), shard_id));
+            }
+            Workers::<T>::remove(&who);
+            Self::deposit_event(Event::WorkerDeregistered(who));
+            Ok(())
+        }
+
         #[pallet::weight(10_000)]
         pub fn queue_task(
             origin: OriginFor<T>,
             shard_id: u32,
             hours: u64,
         ) -> DispatchResult {
-            let _ = ensure_signed(origin)?;
-            ensure!(ShardWorkers::<T>::contains_key(&shard_id), Error::<T>::ShardNotFound);
+            Self::ensure_shard_admin(origin, shard_id)?;
+            ensure!(!ShardWorkers::<T>::get(shard_id).is_empty(), Error::<T>::NoWorkersInShard);
 
-            TaskQueue::<T>::append((shard_id, hours, None));
+            Self::push_task((shard_id, hours, None));
             Self::deposit_event(Event::TaskQueued(shard_id, hours));
             Ok(())
         }
@@ -143,9 +335,16 @@ pub mod pallet {
         pub fn execute_tasks(origin: OriginFor<T>) -> DispatchResult {
             let _ = ensure_signed(origin)?;
 
-            while let Some((shard_id, hours, _)) = TaskQueue::<T>::pop() {
+            while let Some((shard_id, hours, _)) = Self::pop_task() {
+                let shard = Shards::<T>::get(shard_id);
                 let workers = ShardWorkers::<T>::get(shard_id);
-                ensure!(!workers.is_empty(), Error::<T>::NoWorkersInShard);
+                // Drop tasks of shards that were closed or lost their last worker, reverting
+                // here would leave the task on top of the queue and jam it. Shards that predate
+                // `Shards` still have workers and are paid at the worker's own rate.
+                if workers.is_empty() {
+                    Self::deposit_event(Event::TaskDropped(shard_id, hours));
+                    continue;
+                }
 
                 // Pick first available worker
                 let worker = workers[0].clone();
@@ -159,7 +358,9 @@ pub mod pallet {
                     .checked_add(hours)
                     .ok_or(Error::<T>::Overflow)?;
 
-                let pay_amount = profile.hourly_rate
+                let pay_amount = shard
+                    .and_then(|shard| shard.hourly_rate)
+                    .unwrap_or(profile.hourly_rate)
                     .checked_mul(&T::Currency::Balance::from(hours))
                     .ok_or(Error::<T>::Overflow)?;
 
@@ -168,6 +369,7 @@ pub mod pallet {
 
                 // Update worker storage
                 Workers::<T>::insert(&worker, profile.clone());
+                WorkerEarnings::<T>::mutate(&worker, |e| *e = e.saturating_add(pay_amount));
 
                 Self::deposit_event(Event::TaskCompleted(worker.clone(), hours, pay_amount));
             }
@@ -189,4 +391,97 @@ pub mod pallet {
             Ok(())
         }
     }
+
+    impl<T: Config> Pallet<T> {
+        /// Ensure `origin` passes `ShardAdminOrigin` and administers `shard_id`.
+        fn ensure_shard_admin(
+            origin: OriginFor<T>,
+            shard_id: u32,
+        ) -> Result<T::AccountId, DispatchError> {
+            let who = T::ShardAdminOrigin::ensure_origin(origin)?;
+            let shard = Shards::<T>::get(shard_id).ok_or(Error::<T>::ShardNotFound)?;
+            ensure!(shard.admin == who, Error::<T>::NotShardAdmin);
+            Ok(who)
+        }
+
+        /// Append `task` to the end of the queue.
+        pub(crate) fn push_task(task: (u32, u64, Option<T::AccountId>)) {
+            let depth = TaskQueueDepth::<T>::get();
+            TaskQueue::<T>::insert(depth, task);
+            TaskQueueDepth::<T>::put(depth.saturating_add(1));
+        }
+
+        /// Take the task at the end of the queue.
+        fn pop_task() -> Option<(u32, u64, Option<T::AccountId>)> {
+            let depth = TaskQueueDepth::<T>::get().checked_sub(1)?;
+            TaskQueueDepth::<T>::put(depth);
+            TaskQueue::<T>::take(depth)
+        }
+
+        /// Remove `worker` from `shard_id` and from the worker's shard index.
+        fn unassign(shard_id: u32, worker: &T::AccountId) -> DispatchResult {
+            ShardWorkers::<T>::try_mutate(shard_id, |list| {
+                let pos = list.iter().position(|w| w == worker).ok_or(Error::<T>::NotInShard)?;
+                list.remove(pos);
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::remove_from_worker_index(worker, shard_id);
+            Ok(())
+        }
+
+        fn remove_from_worker_index(worker: &T::AccountId, shard_id: u32) {
+            WorkerShards::<T>::mutate_exists(worker, |maybe_shards| {
+                if let Some(shards) = maybe_shards {
+                    shards.retain(|s| *s != shard_id);
+                    if shards.is_empty() {
+                        *maybe_shards = None;
+                    }
+                }
+            });
+        }
+    }
+    // ------------------------
+    // View Functions
+    // ------------------------
+    #[pallet::view_functions]
+    impl<T: Config> Pallet<T> {
+        /// Profile, earnings and shard memberships of `who`, if registered.
+        pub fn worker_summary(
+            who: T::AccountId,
+        ) -> Option<
+            WorkerSummary<
+                WorkerProfile<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance, T::Moment>,
+                <T::Currency as Currency<T::AccountId>>::Balance,
+            >,
+        > {
+            let profile = Workers::<T>::get(&who)?;
+            Some(WorkerSummary {
+                profile,
+                earnings: WorkerEarnings::<T>::get(&who),
+                shards: WorkerShards::<T>::get(&who),
+            })
+        }
+
+        /// Configuration, roster and pending tasks of `shard_id`, if it exists.
+        pub fn shard_summary(
+            shard_id: u32,
+        ) -> Option<ShardSummary<T::AccountId, <T::Currency as Currency<T::AccountId>>::Balance>> {
+            let info = Shards::<T>::get(shard_id)?;
+            let pending_tasks = (0..TaskQueueDepth::<T>::get())
+                .filter_map(TaskQueue::<T>::get)
+                .filter(|(shard, _, _)| *shard == shard_id)
+                .map(|(_, hours, worker)| (hours, worker))
+                .collect();
+            Some(ShardSummary { info, workers: ShardWorkers::<T>::get(shard_id), pending_tasks })
+        }
+
+        /// Up to `limit` queued tasks starting at position `start`, along with the total queue
+        /// depth.
+        pub fn task_queue_page(start: u32, limit: u32) -> TaskQueuePage<T::AccountId> {
+            let depth = TaskQueueDepth::<T>::get();
+            let end = start.saturating_add(limit).min(depth);
+            let tasks = (start..end).filter_map(TaskQueue::<T>::get).collect();
+            TaskQueuePage { depth, tasks }
+        }
+    }
 }
@@ -0,0 +1,93 @@
+//! Storage migrations of the worker pallet.
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+};
+use pallet::*;
+
+/// Migrate to storage version 1, which tracks shards in `Shards` and `WorkerShards`.
+///
+/// Shards that only exist in `ShardWorkers` get a `Shards` entry administered by `Admin`
+/// without a shard rate, and their workers are indexed in `WorkerShards`.
+pub type MigrateToV1<T, Admin> = VersionedMigration<
+    0,
+    1,
+    v1::BackfillShards<T, Admin>,
+    Pallet<T>,
+    <T as frame_system::Config>::DbWeight,
+>;
+
+/// Migrate to storage version 2, which stores the task queue by position.
+pub type MigrateToV2<T> = VersionedMigration<
+    1,
+    2,
+    v2::IndexTaskQueue<T>,
+    Pallet<T>,
+    <T as frame_system::Config>::DbWeight,
+>;
+
+pub mod v1 {
+    use super::*;
+
+    pub struct BackfillShards<T, Admin>(core::marker::PhantomData<(T, Admin)>);
+
+    impl<T: Config, Admin: Get<T::AccountId>> UncheckedOnRuntimeUpgrade
+        for BackfillShards<T, Admin>
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let (mut reads, mut writes) = (0u64, 0u64);
+
+            for (shard_id, workers) in ShardWorkers::<T>::iter() {
+                reads += 2;
+                if !Shards::<T>::contains_key(shard_id) {
+                    let info = ShardInfo { admin: Admin::get(), hourly_rate: None };
+                    Shards::<T>::insert(shard_id, info);
+                    writes += 1;
+                }
+
+                for worker in workers {
+                    reads += 1;
+                    WorkerShards::<T>::mutate(&worker, |shards| {
+                        if !shards.contains(&shard_id) {
+                            shards.push(shard_id);
+                            writes += 1;
+                        }
+                    });
+                }
+            }
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+    use frame_support::storage_alias;
+
+    /// The task queue before version 2, as a single value.
+    #[storage_alias]
+    pub type TaskQueue<T: Config> = StorageValue<
+        Pallet<T>,
+        Vec<(u32, u64, Option<<T as frame_system::Config>::AccountId>)>,
+        ValueQuery,
+    >;
+
+    pub struct IndexTaskQueue<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for IndexTaskQueue<T> {
+        fn on_runtime_upgrade() -> Weight {
+            // The old value shares its key with the prefix of the new map, take it first.
+            let tasks = TaskQueue::<T>::take();
+            let depth = tasks.len() as u64;
+            for task in tasks {
+                Pallet::<T>::push_task(task);
+            }
+
+            let (reads, writes) = (depth.saturating_add(1), depth.saturating_mul(2).saturating_add(1));
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Mock runtime for the worker pallet tests.
+
+use crate::pallet as pallet_workers;
+use frame_support::{derive_impl, traits::ConstU64};
+use frame_system::EnsureSigned;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Registry: pallet_workers,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type ExistentialDeposit = ConstU64<1>;
+}
+
+impl pallet_workers::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type Moment = u64;
+    type ShardAdminOrigin = EnsureSigned<u64>;
+}
+
+pub const ADMIN: u64 = 1;
+pub const ALICE: u64 = 2;
+pub const BOB: u64 = 3;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ADMIN, 1_000), (ALICE, 1_000), (BOB, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Register `who` as a worker with the given hourly rate.
+pub fn register(who: u64, hourly_rate: u64) {
+    frame_support::assert_ok!(Registry::register_worker(
+        RuntimeOrigin::signed(who),
+        hourly_rate,
+        vec![],
+        vec![],
+    ));
+}
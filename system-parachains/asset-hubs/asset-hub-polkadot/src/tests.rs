@@ -0,0 +1,307 @@
+//! Tests of the worker pallet.
+
+use crate::{mock::*, pallet::*, ShardInfo};
+use crate::{
+    migrations::{v2, MigrateToV1, MigrateToV2},
+    ShardSummary, TaskQueuePage,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{ConstU64, OnRuntimeUpgrade, StorageVersion},
+};
+
+fn create_shard(shard_id: u32, rate: Option<u64>) {
+    assert_ok!(Registry::create_shard(RuntimeOrigin::signed(ADMIN), shard_id, rate));
+}
+
+fn assign(shard_id: u32, worker: u64) {
+    assert_ok!(Registry::assign_worker_to_shard(RuntimeOrigin::signed(ADMIN), shard_id, worker));
+}
+
+#[test]
+fn create_shard_works() {
+    new_test_ext().execute_with(|| {
+        create_shard(7, Some(5));
+
+        assert_eq!(Shards::<Test>::get(7), Some(ShardInfo { admin: ADMIN, hourly_rate: Some(5) }));
+        System::assert_last_event(Event::ShardCreated(7, ADMIN).into());
+        assert_noop!(
+            Registry::create_shard(RuntimeOrigin::signed(ALICE), 7, None),
+            Error::<Test>::ShardAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn close_shard_unassigns_workers() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, None);
+        create_shard(2, None);
+        assign(1, ALICE);
+        assign(2, ALICE);
+
+        assert_noop!(
+            Registry::close_shard(RuntimeOrigin::signed(ALICE), 1),
+            Error::<Test>::NotShardAdmin
+        );
+        assert_ok!(Registry::close_shard(RuntimeOrigin::signed(ADMIN), 1));
+
+        assert!(!Shards::<Test>::contains_key(1));
+        assert!(ShardWorkers::<Test>::get(1).is_empty());
+        assert_eq!(WorkerShards::<Test>::get(ALICE), vec![2]);
+        System::assert_last_event(Event::ShardClosed(1).into());
+        assert_noop!(
+            Registry::close_shard(RuntimeOrigin::signed(ADMIN), 1),
+            Error::<Test>::ShardNotFound
+        );
+    });
+}
+
+#[test]
+fn set_shard_rate_works() {
+    new_test_ext().execute_with(|| {
+        create_shard(1, None);
+
+        assert_noop!(
+            Registry::set_shard_rate(RuntimeOrigin::signed(BOB), 1, Some(3)),
+            Error::<Test>::NotShardAdmin
+        );
+        assert_ok!(Registry::set_shard_rate(RuntimeOrigin::signed(ADMIN), 1, Some(3)));
+
+        assert_eq!(Shards::<Test>::get(1).unwrap().hourly_rate, Some(3));
+        System::assert_last_event(Event::ShardRateSet(1, Some(3)).into());
+    });
+}
+
+#[test]
+fn remove_worker_from_shard_works() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, None);
+        assign(1, ALICE);
+
+        assert_noop!(
+            Registry::remove_worker_from_shard(RuntimeOrigin::signed(ALICE), 1, ALICE),
+            Error::<Test>::NotShardAdmin
+        );
+        assert_ok!(Registry::remove_worker_from_shard(RuntimeOrigin::signed(ADMIN), 1, ALICE));
+
+        assert!(ShardWorkers::<Test>::get(1).is_empty());
+        assert!(!WorkerShards::<Test>::contains_key(ALICE));
+        System::assert_last_event(Event::WorkerRemovedFromShard(ALICE, 1).into());
+        assert_noop!(
+            Registry::remove_worker_from_shard(RuntimeOrigin::signed(ADMIN), 1, ALICE),
+            Error::<Test>::NotInShard
+        );
+    });
+}
+
+#[test]
+fn leave_shard_works() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        register(BOB, 10);
+        create_shard(1, None);
+        assign(1, ALICE);
+        assign(1, BOB);
+
+        assert_ok!(Registry::leave_shard(RuntimeOrigin::signed(ALICE), 1));
+
+        assert_eq!(ShardWorkers::<Test>::get(1), vec![BOB]);
+        assert!(!WorkerShards::<Test>::contains_key(ALICE));
+        System::assert_last_event(Event::WorkerLeftShard(ALICE, 1).into());
+        assert_noop!(
+            Registry::leave_shard(RuntimeOrigin::signed(ALICE), 1),
+            Error::<Test>::NotInShard
+        );
+    });
+}
+
+#[test]
+fn deregister_worker_leaves_all_shards() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, None);
+        create_shard(2, None);
+        assign(1, ALICE);
+        assign(2, ALICE);
+
+        assert_ok!(Registry::deregister_worker(RuntimeOrigin::signed(ALICE)));
+
+        assert!(!Workers::<Test>::contains_key(ALICE));
+        assert!(ShardWorkers::<Test>::get(1).is_empty());
+        assert!(ShardWorkers::<Test>::get(2).is_empty());
+        assert!(!WorkerShards::<Test>::contains_key(ALICE));
+        System::assert_last_event(Event::WorkerDeregistered(ALICE).into());
+        assert_noop!(
+            Registry::deregister_worker(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NotRegistered
+        );
+    });
+}
+
+#[test]
+fn migration_backfills_shards_of_existing_workers() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        register(BOB, 10);
+        // Shards assigned before `Shards` and `WorkerShards` existed.
+        ShardWorkers::<Test>::insert(1, vec![ALICE, BOB]);
+        ShardWorkers::<Test>::insert(2, vec![ALICE]);
+        create_shard(3, Some(4));
+        assign(3, BOB);
+        StorageVersion::new(0).put::<Registry>();
+
+        MigrateToV1::<Test, ConstU64<ADMIN>>::on_runtime_upgrade();
+
+        assert_eq!(Shards::<Test>::get(1), Some(ShardInfo { admin: ADMIN, hourly_rate: None }));
+        assert_eq!(Shards::<Test>::get(2), Some(ShardInfo { admin: ADMIN, hourly_rate: None }));
+        assert_eq!(Shards::<Test>::get(3).unwrap().hourly_rate, Some(4));
+        let mut alice_shards = WorkerShards::<Test>::get(ALICE);
+        alice_shards.sort();
+        assert_eq!(alice_shards, vec![1, 2]);
+        let mut bob_shards = WorkerShards::<Test>::get(BOB);
+        bob_shards.sort();
+        assert_eq!(bob_shards, vec![1, 3]);
+        assert_eq!(StorageVersion::get::<Registry>(), 1);
+
+        // The migrated workers can now be deregistered from every shard.
+        assert_ok!(Registry::deregister_worker(RuntimeOrigin::signed(ALICE)));
+        assert_eq!(ShardWorkers::<Test>::get(1), vec![BOB]);
+        assert!(ShardWorkers::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn execute_tasks_keeps_tasks_of_shards_without_info() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        // A shard assigned before `Shards` existed.
+        ShardWorkers::<Test>::insert(1, vec![ALICE]);
+        Registry::push_task((1, 2, None));
+
+        assert_ok!(Registry::execute_tasks(RuntimeOrigin::signed(BOB)));
+
+        System::assert_last_event(Event::TaskCompleted(ALICE, 2, 20).into());
+        assert_eq!(WorkerEarnings::<Test>::get(ALICE), 20);
+    });
+}
+
+#[test]
+fn execute_tasks_skips_tasks_of_closed_shards() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, None);
+        assign(1, ALICE);
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 1, 2));
+        assert_ok!(Registry::close_shard(RuntimeOrigin::signed(ADMIN), 1));
+
+        assert_ok!(Registry::execute_tasks(RuntimeOrigin::signed(BOB)));
+
+        assert_eq!(WorkerEarnings::<Test>::get(ALICE), 0);
+        System::assert_last_event(Event::TaskDropped(1, 2).into());
+    });
+}
+
+#[test]
+fn execute_tasks_drops_tasks_of_shards_without_workers() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        register(BOB, 10);
+        create_shard(1, None);
+        create_shard(2, None);
+        assign(1, ALICE);
+        assign(2, BOB);
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 2, 3));
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 1, 2));
+        // The last worker leaves while the shard still has a task queued on top.
+        assert_ok!(Registry::leave_shard(RuntimeOrigin::signed(ALICE), 1));
+
+        assert_ok!(Registry::execute_tasks(RuntimeOrigin::signed(BOB)));
+
+        System::assert_has_event(Event::TaskDropped(1, 2).into());
+        System::assert_last_event(Event::TaskCompleted(BOB, 3, 30).into());
+        assert_eq!(WorkerEarnings::<Test>::get(ALICE), 0);
+        assert_eq!(WorkerEarnings::<Test>::get(BOB), 30);
+        assert_eq!(TaskQueueDepth::<Test>::get(), 0);
+        assert_eq!(Shards::<Test>::get(1).map(|shard| shard.admin), Some(ADMIN));
+    });
+}
+
+#[test]
+fn deregister_worker_keeps_earnings() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, None);
+        assign(1, ALICE);
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 1, 3));
+        assert_ok!(Registry::execute_tasks(RuntimeOrigin::signed(BOB)));
+
+        assert_ok!(Registry::deregister_worker(RuntimeOrigin::signed(ALICE)));
+
+        assert_eq!(WorkerEarnings::<Test>::get(ALICE), 30);
+    });
+}
+
+#[test]
+fn worker_and_shard_summaries_work() {
+    new_test_ext().execute_with(|| {
+        register(ALICE, 10);
+        create_shard(1, Some(4));
+        create_shard(2, None);
+        assign(1, ALICE);
+        assign(2, ALICE);
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 1, 3));
+        assert_ok!(Registry::queue_task(RuntimeOrigin::signed(ADMIN), 2, 5));
+
+        let summary = Registry::worker_summary(ALICE).unwrap();
+        assert_eq!(summary.shards, vec![1, 2]);
+        assert_eq!(summary.earnings, 0);
+        assert!(Registry::worker_summary(BOB).is_none());
+
+        assert_eq!(
+            Registry::shard_summary(1),
+            Some(ShardSummary {
+                info: ShardInfo { admin: ADMIN, hourly_rate: Some(4) },
+                workers: vec![ALICE],
+                pending_tasks: vec![(3, None)],
+            })
+        );
+        assert_eq!(Registry::shard_summary(3), None);
+    });
+}
+
+#[test]
+fn task_queue_page_reads_only_the_page() {
+    new_test_ext().execute_with(|| {
+        for hours in 0..5 {
+            Registry::push_task((1, hours, None));
+        }
+
+        assert_eq!(
+            Registry::task_queue_page(1, 2),
+            TaskQueuePage { depth: 5, tasks: vec![(1, 1, None), (1, 2, None)] }
+        );
+        assert_eq!(
+            Registry::task_queue_page(4, 10),
+            TaskQueuePage { depth: 5, tasks: vec![(1, 4, None)] }
+        );
+        assert_eq!(Registry::task_queue_page(7, 10), TaskQueuePage { depth: 5, tasks: vec![] });
+    });
+}
+
+#[test]
+fn migration_indexes_the_task_queue() {
+    new_test_ext().execute_with(|| {
+        v2::TaskQueue::<Test>::put(vec![(1, 2, None), (3, 4, Some(ALICE))]);
+        StorageVersion::new(1).put::<Registry>();
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TaskQueueDepth::<Test>::get(), 2);
+        assert_eq!(TaskQueue::<Test>::get(0), Some((1, 2, None)));
+        assert_eq!(TaskQueue::<Test>::get(1), Some((3, 4, Some(ALICE))));
+        assert_eq!(StorageVersion::get::<Registry>(), 2);
+    });
+}